@@ -32,6 +32,9 @@ pub struct DivisionConfig {
     pub min: Age,
     /// The inclusive maximum Age for lifters in this division.
     pub max: Age,
+    /// Whether `min` and `max` are the ages that lifters turn during the
+    /// calendar year of the meet, instead of their ages on the meet date.
+    pub birthyear_rule: bool,
     /// Optional restriction of this Division to a single Sex.
     pub sex: Option<Sex>,
     /// Optional restriction of this Division to certain Equipment.
@@ -201,6 +204,28 @@ fn parse_divisions(value: &Value, report: &mut Report) -> Vec<DivisionConfig> {
             continue;
         }
 
+        // Divisions may be defined by the age that lifters turn during the year,
+        // as in the IPF, instead of by their age on the day of the meet.
+        let birthyear_rule: bool = match division.get("birthyear_rule") {
+            Some(v) => match v.as_bool() {
+                Some(b) => b,
+                None => {
+                    report.error(format!("{}.birthyear_rule must be a Boolean", key));
+                    false
+                }
+            },
+            None => false,
+        };
+
+        // Ages turned during a year are always whole numbers.
+        if birthyear_rule && (!min_age.is_exact() || !max_age.is_exact()) {
+            report.error(format!(
+                "Division '{}' uses birthyear_rule, so its ages must be integers",
+                key
+            ));
+            continue;
+        }
+
         // An optional sex restriction may be provided.
         let sex: Option<Sex> = match division.get("sex") {
             Some(v) => match v.clone().try_into::<Sex>() {
@@ -281,6 +306,7 @@ fn parse_divisions(value: &Value, report: &mut Report) -> Vec<DivisionConfig> {
             name: name.to_string(),
             min: min_age,
             max: max_age,
+            birthyear_rule,
             sex,
             equipment,
             tested,
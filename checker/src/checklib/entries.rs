@@ -1,6 +1,6 @@
 //! Checks for entries.csv files.
 
-use coefficients::{glossbrenner, ipf, wilks};
+use coefficients::PointsInput;
 use csv;
 use opltypes::*;
 use strum::IntoEnumIterator;
@@ -9,7 +9,7 @@ use std::error::Error;
 use std::io;
 use std::path::PathBuf;
 
-use crate::checklib::config::{Config, Exemption, WeightClassConfig};
+use crate::checklib::config::{Config, DivisionConfig, Exemption, WeightClassConfig};
 use crate::checklib::meet::Meet;
 use crate::{EntryIndex, Report};
 
@@ -111,12 +111,15 @@ pub struct Entry {
     // Optional age information.
     pub age: Age,
     pub ageclass: AgeClass,
+    pub birthyearclass: BirthYearClass,
     pub birthyear: Option<u32>,
     pub birthdate: Option<Date>,
     /// Minimum Age associated with the Division per the CONFIG, inclusive.
     pub division_age_min: Age,
     /// Maximum Age associated with the Division per the CONFIG, inclusive.
     pub division_age_max: Age,
+    /// Whether the Division ages are the ages turned during the year of the meet.
+    pub division_birthyear_rule: bool,
 
     pub weightclasskg: WeightClassKg,
     pub bodyweightkg: WeightKg,
@@ -142,15 +145,6 @@ pub struct Entry {
     pub tested: bool,
     pub country: Option<Country>,
 
-    // Points are always recalculated, never taken from the data.
-    //
-    // McCulloch points are not calculated here, because they are Age-dependent.
-    // Because the Age may be inferred by the compiler from surrounding data,
-    // McCulloch calculation is left to a later phase.
-    pub wilks: Points,
-    pub glossbrenner: Points,
-    pub ipfpoints: Points,
-
     /// The index of this `Entry` in the `AllMeetData`.
     ///
     /// Because this refers to vector indices in the final `AllMeetData`,
@@ -189,6 +183,35 @@ impl Entry {
             || self.deadlift4kg.is_non_zero()
     }
 
+    /// Describes the Entry for use by a points formula.
+    ///
+    /// Points are always recalculated, never taken from the data.
+    /// Because some formulas are Age-dependent, and the Age may be inferred
+    /// by post-checker phases, this should be called as late as possible.
+    pub fn points_input(&self) -> PointsInput {
+        let age = if !self.age.is_none() {
+            self.age
+        } else {
+            // From known bounds, choose the one that's closest to Senior (~30).
+            self.ageclass.to_range().map_or(Age::None, |(min, max)| {
+                if max < Age::Exact(30) {
+                    max
+                } else {
+                    min
+                }
+            })
+        };
+
+        PointsInput {
+            sex: self.sex,
+            equipment: self.equipment,
+            event: self.event,
+            bodyweightkg: self.bodyweightkg,
+            totalkg: self.totalkg,
+            age,
+        }
+    }
+
     /// Uses `Age`, `BirthYear`, and `BirthDate` columns to calculate
     /// the lifter's `Age` on a given date.
     pub fn age_on(&self, date: Date) -> Age {
@@ -1478,10 +1501,76 @@ fn check_weightclass_consistency(
     }
 }
 
+/// Converts an age turned during the year of the meet into an Age on the
+/// meet date: a lifter who turns 23 that year is either 22 or 23.
+fn age_from_year_age(age: Age) -> Age {
+    match age {
+        Age::Exact(n) if n > 0 => Age::Approximate(n - 1),
+        _ => age,
+    }
+}
+
+/// Checks that an Entry is consistent with a Division defined by the ages
+/// that lifters turn during the calendar year of the meet.
+fn check_division_birthyear_consistency(
+    entry: &Entry,
+    meet_date: Date,
+    division: &DivisionConfig,
+    line: u64,
+    report: &mut Report,
+) {
+    // Determine the range of ages the lifter may turn in the year of the meet.
+    let year = meet_date.year();
+    let birthyear = entry
+        .birthyear
+        .or_else(|| entry.birthdate.map(|d| d.year()));
+    let (turns_min, turns_max) = match (birthyear, entry.age) {
+        (Some(birthyear), _) if birthyear <= year => (year - birthyear, year - birthyear),
+        (Some(_), _) => {
+            return;
+        }
+        // The lifter's birthday may not have happened yet.
+        (None, Age::Exact(age)) => (u32::from(age), u32::from(age) + 1),
+        // An Approximate Age means the lifter turns the greater age that year.
+        (None, Age::Approximate(age)) => (u32::from(age) + 1, u32::from(age) + 1),
+        (None, Age::None) => {
+            return;
+        }
+    };
+
+    // The config check guarantees that the ages are Exact.
+    let min = division.min.to_u8_option().map_or(0, u32::from);
+    let max = division
+        .max
+        .to_u8_option()
+        .map_or(u32::max_value(), u32::from);
+
+    if turns_max < min {
+        report.error_on(
+            line,
+            format!(
+                "Lifter turns at most {} in {}, too young for division '{}': min age {}",
+                turns_max, year, entry.division, min
+            ),
+        );
+    }
+
+    if turns_min > max {
+        report.error_on(
+            line,
+            format!(
+                "Lifter turns at least {} in {}, too old for division '{}': max age {}",
+                turns_min, year, entry.division, max
+            ),
+        );
+    }
+}
+
 /// Checks internal age consistency, and checks that the CONFIG-controlled Age
 /// range is consistent with the known data.
 ///
-/// Returns the (min_age, max_age) associated with the Division.
+/// Returns the (min_age, max_age) associated with the Division, and whether
+/// those are the ages turned during the year of the meet.
 fn check_division_age_consistency(
     entry: &Entry,
     meet: Option<&Meet>,
@@ -1489,12 +1578,12 @@ fn check_division_age_consistency(
     exempt_division: bool,
     line: u64,
     report: &mut Report,
-) -> (Age, Age) {
+) -> (Age, Age, bool) {
     // If we don't know when the meet was, there's nothing to diff against.
     let meet_date = match meet {
         Some(m) => m.date,
         None => {
-            return (Age::None, Age::None);
+            return (Age::None, Age::None, false);
         }
     };
 
@@ -1573,14 +1662,14 @@ fn check_division_age_consistency(
 
     // Allow exemptions from division-specific checks.
     if exempt_division || entry.division.is_empty() {
-        return (Age::None, Age::None);
+        return (Age::None, Age::None, false);
     }
 
     // If no divisions are configured, there's nothing left to do.
     let config = match config {
         Some(config) => config,
         None => {
-            return (Age::None, Age::None);
+            return (Age::None, Age::None, false);
         }
     };
 
@@ -1588,17 +1677,23 @@ fn check_division_age_consistency(
     // several federations, such as meet-data/plusa, can omit
     // the list of divisions to effectively cause full exemption.
     if config.divisions.is_empty() {
-        return (Age::None, Age::None);
+        return (Age::None, Age::None, false);
     }
 
     // Division string errors are already handled by check_column_division().
-    let (min_age, max_age) =
-        match config.divisions.iter().find(|d| d.name == entry.division) {
-            Some(div) => (div.min, div.max),
-            None => {
-                return (Age::None, Age::None);
-            }
-        };
+    let division = match config.divisions.iter().find(|d| d.name == entry.division) {
+        Some(div) => div,
+        None => {
+            return (Age::None, Age::None, false);
+        }
+    };
+    let (min_age, max_age) = (division.min, division.max);
+
+    // Ages on the meet date don't apply to divisions by BirthYear.
+    if division.birthyear_rule {
+        check_division_birthyear_consistency(entry, meet_date, division, line, report);
+        return (min_age, max_age, true);
+    }
 
     // Use the various age-related columns to calculate a representative Age value.
     let age = entry.age_on(meet_date);
@@ -1660,7 +1755,7 @@ fn check_division_age_consistency(
         }
     }
 
-    (min_age, max_age)
+    (min_age, max_age, false)
 }
 
 /// Checks that a configured division is consistent with any sex restrictions.
@@ -2053,16 +2148,18 @@ where
             &mut report,
         );
 
-        let (division_age_min, division_age_max) = check_division_age_consistency(
-            &entry,
-            meet,
-            config,
-            exempt_division,
-            line,
-            &mut report,
-        );
+        let (division_age_min, division_age_max, division_birthyear_rule) =
+            check_division_age_consistency(
+                &entry,
+                meet,
+                config,
+                exempt_division,
+                line,
+                &mut report,
+            );
         entry.division_age_min = division_age_min;
         entry.division_age_max = division_age_max;
+        entry.division_birthyear_rule = division_birthyear_rule;
 
         check_division_sex_consistency(&entry, config, line, &mut report);
         check_division_place_consistency(&entry, config, line, &mut report);
@@ -2092,14 +2189,26 @@ where
         entry.ageclass = AgeClass::from_age(entry.age);
         // Or assign the AgeClass based on Division information.
         if entry.ageclass == AgeClass::None {
-            entry.ageclass = AgeClass::from_range(division_age_min, division_age_max);
+            entry.ageclass = if division_birthyear_rule {
+                AgeClass::from_range(
+                    age_from_year_age(division_age_min),
+                    age_from_year_age(division_age_max),
+                )
+            } else {
+                AgeClass::from_range(division_age_min, division_age_max)
+            };
         }
 
-        // Calculate points (except for McCulloch, which is Age-dependent).
-        let bw = entry.bodyweightkg;
-        entry.wilks = wilks(entry.sex, bw, entry.totalkg);
-        entry.glossbrenner = glossbrenner(entry.sex, bw, entry.totalkg);
-        entry.ipfpoints = ipf(entry.sex, entry.equipment, entry.event, bw, entry.totalkg);
+        // Assign the BirthYearClass based on the year of birth, if known.
+        if let Some(meet) = meet {
+            let birthyear = entry
+                .birthyear
+                .or_else(|| entry.birthdate.map(|d| d.year()));
+            if let Some(birthyear) = birthyear {
+                entry.birthyearclass =
+                    BirthYearClass::from_birthyear(birthyear, meet.date);
+            }
+        }
 
         // If the Name isn't provided, but there is an international name,
         // just use the international name.
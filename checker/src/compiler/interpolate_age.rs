@@ -136,6 +136,11 @@ impl BirthDateRange {
         }
     }
 
+    /// Returns the BirthYearClass on a given Date given the known range.
+    pub fn birthyearclass_on(&self, date: Date) -> BirthYearClass {
+        BirthYearClass::from_birthyear_range(self.min.year(), self.max.year(), date)
+    }
+
     /// Intersects this BirthDateRange with another.
     pub fn intersect(&mut self, other: &BirthDateRange) -> NarrowResult {
         if self.min > other.max || other.min > self.max {
@@ -237,6 +242,38 @@ impl BirthDateRange {
         let range = BirthDateRange::new(birthdate_min, birthdate_max);
         self.intersect(&range)
     }
+
+    /// Narrows the range by a known Division Age range on a specific Date,
+    /// for a Division defined by the ages turned during the year.
+    pub fn narrow_by_division_birthyear(
+        &mut self,
+        min: Age,
+        max: Age,
+        on_date: Date,
+    ) -> NarrowResult {
+        let year = on_date.year();
+
+        // Determine the maximum BirthDate from the lower Age (they are younger).
+        // The youngest possible lifter turns that Age on Dec 31.
+        let birthdate_max = match min {
+            Age::Exact(age) | Age::Approximate(age) => {
+                Date::from_u32((year - u32::from(age)) * 1_00_00 + 12_31)
+            }
+            Age::None => BDR_DEFAULT_MAX,
+        };
+
+        // Determine the minimum BirthDate from the greater Age (they are older).
+        // The oldest possible lifter turns that Age on Jan 1.
+        let birthdate_min = match max {
+            Age::Exact(age) | Age::Approximate(age) => {
+                Date::from_u32((year - u32::from(age)) * 1_00_00 + 01_01)
+            }
+            Age::None => BDR_DEFAULT_MIN,
+        };
+
+        let range = BirthDateRange::new(birthdate_min, birthdate_max);
+        self.intersect(&range)
+    }
 }
 
 /// Helper function for debug-mode printing to keep the code legible.
@@ -367,12 +404,21 @@ fn get_birthdate_range(
                 max: entry.division_age_max,
             };
 
-            if range.narrow_by_division(
-                entry.division_age_min,
-                entry.division_age_max,
-                mdate,
-            ) == NarrowResult::Conflict
-            {
+            let result = if entry.division_birthyear_rule {
+                range.narrow_by_division_birthyear(
+                    entry.division_age_min,
+                    entry.division_age_max,
+                    mdate,
+                )
+            } else {
+                range.narrow_by_division(
+                    entry.division_age_min,
+                    entry.division_age_max,
+                    mdate,
+                )
+            };
+
+            if result == NarrowResult::Conflict {
                 trace_conflict(debug, &range, mdate, "Division", &agerange, &path);
                 return unknown;
             }
@@ -442,6 +488,14 @@ fn infer_from_range(
             entry.birthyear = birthyear;
         }
 
+        // Update the BirthYearClass, which only depends on the year of birth.
+        if entry.birthyearclass == BirthYearClass::None {
+            entry.birthyearclass = range.birthyearclass_on(mdate);
+            if entry.birthyearclass != BirthYearClass::None {
+                trace_inference(debug, "BirthYearClass", &entry.birthyearclass, mdate);
+            }
+        }
+
         // Update the AgeClass to match the Age, if applicable.
         //
         // If the entry initially had an Age::Approximate, the AgeClass matched
@@ -582,6 +636,25 @@ mod tests {
         assert_eq!(bdr.max, Date::from_u32(1982_05_06));
     }
 
+    #[test]
+    fn range_birthyearclass() {
+        let date = Date::from_u32(2019_06_01);
+
+        // An Age::Exact spans two BirthYears, which may be in the same class.
+        let mut bdr = BirthDateRange::default();
+        assert_eq!(bdr.narrow_by_age(Age::Exact(20), date), Integrated);
+        assert_eq!(bdr.birthyearclass_on(date), BirthYearClass::ClassY19Y23);
+
+        // Or they may straddle a boundary.
+        let mut bdr = BirthDateRange::default();
+        assert_eq!(bdr.narrow_by_age(Age::Exact(23), date), Integrated);
+        assert_eq!(bdr.birthyearclass_on(date), BirthYearClass::None);
+
+        // A known upper bound is enough for the oldest class.
+        let bdr = BirthDateRange::at(None, Some(1940_01_01));
+        assert_eq!(bdr.birthyearclass_on(date), BirthYearClass::ClassY70Y999);
+    }
+
     #[test]
     fn range_narrow_by_age() {
         // Test an Age::Exact against unknown bounds.
@@ -623,4 +696,23 @@ mod tests {
         let (min, max) = (Age::Exact(0), Age::Approximate(17));
         assert_eq!(bdr.narrow_by_division(min, max, date), Integrated);
     }
+
+    #[test]
+    fn range_narrow_by_division_birthyear() {
+        // Juniors who turn 19-23 during 2019 were born in 1996-2000.
+        let mut bdr = BirthDateRange::default();
+        let date = Date::from_u32(2019_06_01);
+        let (min, max) = (Age::Exact(19), Age::Exact(23));
+        assert_eq!(bdr.narrow_by_division_birthyear(min, max, date), Integrated);
+        assert_eq!(bdr.min, Date::from_u32(1996_01_01));
+        assert_eq!(bdr.max, Date::from_u32(2000_12_31));
+
+        // A lifter who is already 24 on the meet date can't be in it.
+        let mut bdr = BirthDateRange::at(Some(1995_03_01), Some(1995_03_01));
+        assert_eq!(bdr.narrow_by_division_birthyear(min, max, date), Conflict);
+
+        // A lifter who is still 23 on the meet date, but turns 24 later, can't either.
+        let mut bdr = BirthDateRange::at(Some(1995_12_01), Some(1995_12_01));
+        assert_eq!(bdr.narrow_by_division_birthyear(min, max, date), Conflict);
+    }
 }
@@ -1,14 +1,16 @@
 //! Transforms `AllMeetData` into the final CSV files.
 
-use coefficients::mcculloch;
+use coefficients::{stored_formulas, PointsInput, NUM_STORED};
 use csv::{QuoteStyle, Terminator, WriterBuilder};
 use hashbrown::HashMap;
 use opltypes::*;
+use serde::ser::{Serialize, SerializeStruct, Serializer};
 
+use std::collections::BTreeMap;
 use std::path::Path;
 
 use crate::checklib::{Entry, LifterData, LifterDataMap, Meet};
-use crate::{AllMeetData, SingleMeetData};
+use crate::{AllMeetData, Config, SingleMeetData};
 
 /// Serialization source for the meets.csv.
 /// The AllMeetData continues as the backing store.
@@ -67,6 +69,8 @@ struct EntriesRow<'d> {
     age: Age,
     #[serde(rename = "AgeClass")]
     ageclass: AgeClass,
+    #[serde(rename = "BirthYearClass")]
+    birthyearclass: BirthYearClass,
     #[serde(rename = "Division")]
     division: &'d str,
     #[serde(rename = "BodyweightKg")]
@@ -107,14 +111,6 @@ struct EntriesRow<'d> {
     totalkg: WeightKg,
     #[serde(rename = "Place")]
     place: Place,
-    #[serde(rename = "Wilks")]
-    wilks: Points,
-    #[serde(rename = "McCulloch")]
-    mcculloch: Points,
-    #[serde(rename = "Glossbrenner")]
-    glossbrenner: Points,
-    #[serde(rename = "IPFPoints")]
-    ipfpoints: Points,
     #[serde(rename = "Tested")]
     tested: &'static str,
     #[serde(rename = "Country")]
@@ -123,23 +119,6 @@ struct EntriesRow<'d> {
 
 impl<'d> EntriesRow<'d> {
     fn from(entry: &'d Entry, meet_id: u32, lifter_id: u32) -> EntriesRow<'d> {
-        // McCulloch points are calculated as late as possible because they are
-        // Age-dependent, and the lifter's Age may be inferred by post-checker phases.
-        let est_age = if !entry.age.is_none() {
-            entry.age
-        } else {
-            // From known bounds, choose the one that's closest to Senior (~30).
-            entry.ageclass.to_range().map_or(Age::None, |(min, max)| {
-                if max < Age::Exact(30) {
-                    max
-                } else {
-                    min
-                }
-            })
-        };
-
-        let mcculloch = mcculloch(entry.sex, entry.bodyweightkg, entry.totalkg, est_age);
-
         EntriesRow {
             meet_id,
             lifter_id,
@@ -148,6 +127,7 @@ impl<'d> EntriesRow<'d> {
             equipment: entry.equipment,
             age: entry.age,
             ageclass: entry.ageclass,
+            birthyearclass: entry.birthyearclass,
             division: &entry.division,
             bodyweightkg: entry.bodyweightkg,
             weightclasskg: entry.weightclasskg,
@@ -168,16 +148,30 @@ impl<'d> EntriesRow<'d> {
             best3deadliftkg: entry.best3deadliftkg,
             totalkg: entry.totalkg,
             place: entry.place,
-            wilks: entry.wilks,
-            mcculloch,
-            glossbrenner: entry.glossbrenner,
-            ipfpoints: entry.ipfpoints,
             tested: if entry.tested { "Yes" } else { "" },
             country: entry.country,
         }
     }
 }
 
+/// Serialization source for the points columns of the entries.csv.
+///
+/// There is one column for each formula with a `csv_column`,
+/// so the columns follow the `coefficients` registry.
+struct PointsColumns(PointsInput);
+
+impl Serialize for PointsColumns {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("PointsColumns", NUM_STORED)?;
+        for formula in stored_formulas() {
+            let column = formula.csv_column().unwrap();
+            let points = coefficients::points(formula.system(), &self.0);
+            state.serialize_field(column, &points)?;
+        }
+        state.end()
+    }
+}
+
 /// Serialization source for the lifters.csv.
 ///
 /// The `'md` lifetime refers to the `AllMeetData` data owner, while
@@ -312,7 +306,11 @@ pub fn make_csv(
             };
 
             // Write out to entries.csv.
-            entries_wtr.serialize(EntriesRow::from(&entry, meet_id, lifter_id))?;
+            // Points are calculated as late as possible because some are
+            // Age-dependent, and the Age may be inferred by post-checker phases.
+            let row = EntriesRow::from(&entry, meet_id, lifter_id);
+            let points = PointsColumns(entry.points_input());
+            entries_wtr.serialize((row, points))?;
         }
     }
 
@@ -328,3 +326,73 @@ pub fn make_csv(
 
     Ok(())
 }
+
+/// Serialization source for the weightclasses.csv.
+#[derive(Serialize)]
+struct WeightClassesRow<'c> {
+    #[serde(rename = "Federation")]
+    federation: Federation,
+    #[serde(rename = "Name")]
+    name: &'c str,
+    #[serde(rename = "Sex")]
+    sex: Sex,
+    #[serde(rename = "DateMin")]
+    date_min: Date,
+    #[serde(rename = "DateMax")]
+    date_max: Date,
+    /// The classes, separated by spaces.
+    #[serde(rename = "Classes")]
+    classes: String,
+}
+
+/// Writes out the weightclasses from every CONFIG.toml, so that the server
+/// can offer the classes that each federation actually uses.
+///
+/// Configs are per-folder, so each table is written once for every
+/// federation that has meets in the folder.
+pub fn make_weightclasses_csv(
+    meetdata: &AllMeetData,
+    configs: &BTreeMap<String, Config>,
+    buildpath: &Path,
+) -> Result<(), csv::Error> {
+    let mut wtr = WriterBuilder::new()
+        .quote_style(QuoteStyle::Never)
+        .terminator(Terminator::Any(b'\n'))
+        .from_path(&buildpath.join("weightclasses.csv"))?;
+
+    // Map from folder name to the federations with meets in that folder.
+    let mut folder_feds: HashMap<&str, Vec<Federation>> = HashMap::new();
+    for SingleMeetData { meet, .. } in meetdata.get_meets() {
+        if let Some(folder) = meet.path.rsplit('/').nth(1) {
+            let feds = folder_feds.entry(folder).or_insert_with(Vec::new);
+            if !feds.contains(&meet.federation) {
+                feds.push(meet.federation);
+            }
+        }
+    }
+
+    for (folder, config) in configs {
+        let feds = match folder_feds.get(folder.as_str()) {
+            Some(feds) => feds,
+            None => continue,
+        };
+
+        for &federation in feds {
+            for scheme in &config.weightclasses {
+                let classes: Vec<String> =
+                    scheme.classes.iter().map(|c| c.to_string()).collect();
+
+                wtr.serialize(WeightClassesRow {
+                    federation,
+                    name: &scheme.name,
+                    sex: scheme.sex,
+                    date_min: scheme.date_min,
+                    date_max: scheme.date_max,
+                    classes: classes.join(" "),
+                })?;
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -2,7 +2,7 @@
 //! offered on the website's Data page. Unlike the other CSV files, which are
 //! intended for use by the server, this variant is intended for use by humans.
 
-use coefficients::mcculloch;
+use coefficients::points;
 use csv::{QuoteStyle, Terminator, WriterBuilder};
 use opltypes::*;
 
@@ -27,6 +27,8 @@ struct Row<'d> {
     pub age: Age,
     #[serde(rename = "AgeClass")]
     pub ageclass: AgeClass,
+    #[serde(rename = "BirthYearClass")]
+    pub birthyearclass: BirthYearClass,
     #[serde(rename = "Division")]
     pub division: &'d str,
     #[serde(rename = "BodyweightKg")]
@@ -93,23 +95,7 @@ struct Row<'d> {
 
 impl<'d> Row<'d> {
     fn from(entry: &'d Entry, meet: &'d Meet) -> Row<'d> {
-        // McCulloch points are calculated as late as possible because they are
-        // Age-dependent, and the lifter's Age may be inferred by post-checker phases.
-        // TODO: Share this code with make_csv.rs.
-        let est_age = if !entry.age.is_none() {
-            entry.age
-        } else {
-            // From known bounds, choose the one that's closest to Senior (~30).
-            entry.ageclass.to_range().map_or(Age::None, |(min, max)| {
-                if max < Age::Exact(30) {
-                    max
-                } else {
-                    min
-                }
-            })
-        };
-
-        let mcculloch = mcculloch(entry.sex, entry.bodyweightkg, entry.totalkg, est_age);
+        let input = entry.points_input();
 
         Row {
             name: &entry.name,
@@ -118,6 +104,7 @@ impl<'d> Row<'d> {
             equipment: entry.equipment,
             age: entry.age,
             ageclass: entry.ageclass,
+            birthyearclass: entry.birthyearclass,
             division: &entry.division,
             bodyweightkg: entry.bodyweightkg,
             weightclasskg: entry.weightclasskg,
@@ -138,10 +125,10 @@ impl<'d> Row<'d> {
             best3deadliftkg: entry.best3deadliftkg,
             totalkg: entry.totalkg,
             place: entry.place,
-            wilks: entry.wilks,
-            mcculloch,
-            glossbrenner: entry.glossbrenner,
-            ipfpoints: entry.ipfpoints,
+            wilks: points(PointsSystem::Wilks, &input),
+            mcculloch: points(PointsSystem::McCulloch, &input),
+            glossbrenner: points(PointsSystem::Glossbrenner, &input),
+            ipfpoints: points(PointsSystem::IPFPoints, &input),
             tested: if entry.tested { "Yes" } else { "" },
             country: entry.country,
             federation: meet.federation,
@@ -7,7 +7,7 @@ mod interpolate_country;
 pub use interpolate_country::{interpolate_country, interpolate_country_debug_for};
 
 mod make_csv;
-pub use make_csv::make_csv;
+pub use make_csv::{make_csv, make_weightclasses_csv};
 
 mod make_onefile_csv;
 pub use make_onefile_csv::make_onefile_csv;
@@ -370,6 +370,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         if argmatches.is_present("compile") {
             let buildpath = project_root.join("build");
             compiler::make_csv(&meetdata, &lifterdata, &buildpath)?;
+            compiler::make_weightclasses_csv(&meetdata, &configmap, &buildpath)?;
         }
         if argmatches.is_present("compile-onefile") {
             let buildpath = project_root.join("build");
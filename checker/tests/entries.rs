@@ -2,9 +2,12 @@
 
 extern crate checker;
 extern crate csv;
+extern crate opltypes;
 
+use checker::checklib::config::{DivisionConfig, WeightClassConfig};
 use checker::checklib::entries::do_check;
-use checker::Report;
+use checker::{Config, Meet, Report};
+use opltypes::*;
 
 use std::path::PathBuf;
 
@@ -19,6 +22,50 @@ fn check(csv: &str) -> usize {
     checkresult.report.count_errors()
 }
 
+/// Executes checks against a CSV for a meet on 2019-03-01, configured with
+/// a single Juniors division for lifters who turn 19-23 that year.
+fn check_birthyear_division(csv: &str) -> usize {
+    let meet = Meet {
+        path: "test/1901".to_string(),
+        federation: Federation::IPF,
+        date: Date::from_u32(2019_03_01),
+        country: Country::USA,
+        state: None,
+        town: None,
+        name: "Test Meet".to_string(),
+        ruleset: RuleSet::default(),
+    };
+    let config = Config {
+        divisions: vec![DivisionConfig {
+            name: "Juniors".to_string(),
+            min: Age::Exact(19),
+            max: Age::Exact(23),
+            birthyear_rule: true,
+            sex: None,
+            equipment: None,
+            tested: None,
+            place: None,
+        }],
+        weightclasses: vec![WeightClassConfig {
+            name: "default_M".to_string(),
+            classes: vec!["93".parse::<WeightClassKg>().unwrap()],
+            date_min: Date::from_u32(2011_01_01),
+            date_max: Date::from_u32(9999_01_01),
+            sex: Sex::M,
+            divisions: None,
+        }],
+        exemptions: vec![],
+        rulesets: vec![],
+    };
+
+    let report = Report::new(PathBuf::from("[inline_parent]/[inline]"));
+    let mut rdr = csv::ReaderBuilder::new()
+        .quoting(false)
+        .from_reader(csv.as_bytes());
+    let checkresult = do_check(&mut rdr, Some(&meet), Some(&config), report).unwrap();
+    checkresult.report.count_errors()
+}
+
 #[test]
 fn test_empty_file() {
     assert!(check("") > 0);
@@ -197,3 +244,27 @@ fn regression_tests() {
                 Aleksey Krasnoshchekov,M,86,,,,,,170,90,SBD,Raw,No,-135,-135,-135,,,160,165,170,170,,Pro Masters 40-44,42,1";
     assert_eq!(check(data), 1);
 }
+
+#[test]
+fn test_birthyear_division() {
+    let check_with = |column: &str, value: &str| {
+        let data = format!(
+            "Name,Sex,Division,WeightClassKg,Best3BenchKg,TotalKg,Equipment,Event,Place,{}\n\
+             Test User,M,Juniors,93,100,100,Raw,B,1,{}",
+            column, value
+        );
+        check_birthyear_division(&data)
+    };
+
+    // Born in 1996, so turns 23 during 2019, even if still 22 on the meet date.
+    assert_eq!(check_with("BirthYear", "1996"), 0);
+    assert_eq!(check_with("Age", "22"), 0);
+
+    // Born in 1995, so turns 24 during 2019, even if still 23 on the meet date.
+    assert_eq!(check_with("BirthYear", "1995"), 1);
+    assert_eq!(check_with("Age", "24"), 1);
+
+    // An exact Age of 18 may turn 19 later that year, but a BirthYear is known.
+    assert_eq!(check_with("Age", "18"), 0);
+    assert_eq!(check_with("BirthYear", "2001"), 1);
+}
@@ -0,0 +1,141 @@
+//! Definition of IPF GL (Goodlift) Points.
+
+use opltypes::*;
+
+/// Hardcoded formula parameters: `(A, B, C)`.
+type Parameters = (f64, f64, f64);
+
+/// Gets formula parameters from what is effectively a lookup table.
+fn get_parameters(sex: Sex, equipment: Equipment, event: Event) -> Parameters {
+    // Since the formula was made for the IPF, it only covers Raw and Single-ply.
+    // We do our best and just reuse those for Wraps and Multi-ply, respectively.
+    let equipment = match equipment {
+        Equipment::Raw | Equipment::Wraps | Equipment::Straps => Equipment::Raw,
+        Equipment::Single | Equipment::Multi => Equipment::Single,
+    };
+
+    const SBD: Event = Event::sbd();
+    const B: Event = Event::b();
+
+    match (event, sex, equipment) {
+        (SBD, Sex::M, Equipment::Raw) => (1199.72839, 1025.18162, 0.00921),
+        (SBD, Sex::M, Equipment::Single) => (1236.25115, 1449.21864, 0.01644),
+        (SBD, Sex::F, Equipment::Raw) => (610.32796, 1045.59282, 0.03048),
+        (SBD, Sex::F, Equipment::Single) => (758.63878, 949.31382, 0.02435),
+
+        (B, Sex::M, Equipment::Raw) => (320.98041, 281.40258, 0.01008),
+        (B, Sex::M, Equipment::Single) => (381.22073, 733.79378, 0.02398),
+        (B, Sex::F, Equipment::Raw) => (142.40398, 442.52671, 0.04724),
+        (B, Sex::F, Equipment::Single) => (221.82209, 357.00377, 0.02937),
+
+        _ => (0.0, 0.0, 0.0),
+    }
+}
+
+/// Calculates IPF GL (Goodlift) Points.
+///
+/// The IPF GL formula replaced IPF Points in 2020. It scales the result
+/// by the expected performance at the lifter's bodyweight, so that a lift
+/// at the expected level is worth 100 points.
+pub fn goodlift(
+    sex: Sex,
+    equipment: Equipment,
+    event: Event,
+    bodyweight: WeightKg,
+    total: WeightKg,
+) -> Points {
+    // Look up parameters.
+    let (a, b, c) = get_parameters(sex, equipment, event);
+
+    // Exit early for undefined cases.
+    if a == 0.0 || bodyweight < WeightKg::from_i32(35) || total.is_zero() {
+        return Points::from_i32(0);
+    }
+
+    // The expected performance is A - B * e^(-C * Bwt).
+    let expected = a - b * (-c * f64::from(bodyweight)).exp();
+
+    // Prevent division by zero.
+    if expected == 0.0 {
+        return Points::from_i32(0);
+    }
+
+    // Calculate Goodlift points.
+    // We add the requirement that the value be non-negative.
+    let points: f64 = (0.0_f64).max(f64::from(total) * 100.0 / expected);
+    Points::from(points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A simple helper to pass some defaults, so the testcases aren't too long.
+    fn test_helper(bodyweight: WeightKg, total: WeightKg) -> Points {
+        goodlift(Sex::M, Equipment::Raw, Event::sbd(), bodyweight, total)
+    }
+
+    #[test]
+    fn expected_values() {
+        assert_eq!(
+            test_helper(WeightKg::from_f32(93.0), WeightKg::from_f32(777.5)),
+            Points::from(101.71)
+        );
+
+        let weight = WeightKg::from_f32(73.0);
+        let total = WeightKg::from_f32(337.5);
+        assert_eq!(
+            goodlift(Sex::F, Equipment::Raw, Event::sbd(), weight, total),
+            Points::from(67.86)
+        );
+
+        let weight = WeightKg::from_f32(105.0);
+        let total = WeightKg::from_f32(1000.0);
+        assert_eq!(
+            goodlift(Sex::M, Equipment::Single, Event::sbd(), weight, total),
+            Points::from(102.21)
+        );
+
+        let weight = WeightKg::from_f32(120.0);
+        let total = WeightKg::from_f32(300.0);
+        assert_eq!(
+            goodlift(Sex::M, Equipment::Single, Event::b(), weight, total),
+            Points::from(88.25)
+        );
+    }
+
+    #[test]
+    fn edge_cases() {
+        // Zero bodyweight shouldn't crash.
+        assert_eq!(
+            test_helper(WeightKg::from_f32(0.0), WeightKg::from_f32(400.0)),
+            Points::from(0.0)
+        );
+
+        // Negative bodyweight shouldn't crash.
+        assert_eq!(
+            test_helper(WeightKg::from_f32(-100.0), WeightKg::from_f32(400.0)),
+            Points::from(0.0)
+        );
+
+        // Zero total shouldn't crash.
+        assert_eq!(
+            test_helper(WeightKg::from_f32(100.0), WeightKg::from_f32(0.0)),
+            Points::from(0.0)
+        );
+
+        // Negative total shouldn't crash.
+        assert_eq!(
+            test_helper(WeightKg::from_f32(100.0), WeightKg::from_f32(-100.0)),
+            Points::from(0.0)
+        );
+
+        // Events without published parameters have no points.
+        let weight = WeightKg::from_f32(100.0);
+        let total = WeightKg::from_f32(300.0);
+        assert_eq!(
+            goodlift(Sex::M, Equipment::Raw, Event::d(), weight, total),
+            Points::from(0.0)
+        );
+    }
+}
@@ -0,0 +1,277 @@
+//! Inverses of the points formulas, for questions like
+//! "what total do I need at 82.5kg for 450 Dots?"
+//!
+//! Every formula gives more points for a heavier total, and almost always
+//! no more points for a heavier bodyweight, so the inverses can be found by
+//! binary search over the `WeightKg` representation without per-formula algebra.
+//! Where bodyweight doesn't behave, the search gives up instead of guessing.
+
+use opltypes::*;
+
+use crate::registry::{PointsFormula, PointsInput};
+
+/// The heaviest total considered when searching, in kilograms.
+const MAX_TOTALKG: i32 = 2000;
+
+/// The heaviest bodyweight considered when searching, in kilograms.
+const MAX_BODYWEIGHTKG: i32 = 300;
+
+/// Finds the smallest raw value in `[lo, hi]` for which `passes` is true,
+/// given that `passes` is false up to some point and then true onwards.
+fn lowest_passing<F>(mut lo: i32, mut hi: i32, passes: F) -> Option<i32>
+where
+    F: Fn(i32) -> bool,
+{
+    if !passes(hi) {
+        return None;
+    }
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if passes(mid) {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+    Some(lo)
+}
+
+/// Finds the largest raw value in `[lo, hi]` for which `passes` is true,
+/// given that `passes` is true up to some point and then false onwards.
+fn highest_passing<F>(mut lo: i32, mut hi: i32, passes: F) -> Option<i32>
+where
+    F: Fn(i32) -> bool,
+{
+    if !passes(lo) {
+        return None;
+    }
+    while lo < hi {
+        let mid = hi - (hi - lo) / 2;
+        if passes(mid) {
+            lo = mid;
+        } else {
+            hi = mid - 1;
+        }
+    }
+    Some(lo)
+}
+
+/// Whether `points` never rises over `[lo, hi]`, sampled every kilogram.
+fn is_non_increasing<F>(lo: i32, hi: i32, points: F) -> bool
+where
+    F: Fn(i32) -> Points,
+{
+    let step = WeightKg::from_i32(1).as_raw();
+    let mut raw = lo;
+    let mut prev = points(raw);
+    while raw < hi {
+        raw = (raw + step).min(hi);
+        let next = points(raw);
+        if next > prev {
+            return false;
+        }
+        prev = next;
+    }
+    true
+}
+
+/// Calculates the lightest total that scores at least `target` points,
+/// keeping everything else in the `input` the same.
+///
+/// Returns `None` if no total up to 2000kg is sufficient, or if the formula
+/// doesn't apply to the input's event and equipment.
+pub fn total_for_points(
+    formula: &dyn PointsFormula,
+    input: &PointsInput,
+    target: Points,
+) -> Option<WeightKg> {
+    if !formula.supports(input.event, input.equipment) {
+        return None;
+    }
+
+    let max = WeightKg::from_i32(MAX_TOTALKG);
+    let raw = lowest_passing(1, max.as_raw(), |raw| {
+        let input = PointsInput {
+            totalkg: WeightKg::from_raw(raw),
+            ..*input
+        };
+        formula.points(&input) >= target
+    })?;
+    Some(WeightKg::from_raw(raw))
+}
+
+/// Calculates the heaviest bodyweight at which the input's total
+/// still scores at least `target` points.
+///
+/// The search starts from the input's bodyweight, answering how much weight
+/// a lifter can gain without dropping below the target. Returns `None` if
+/// the target isn't met at the current bodyweight, or if the points rise
+/// again somewhere above it, as IPF Points can for light women with small
+/// totals. Formulas that stop adjusting past some bodyweight may return
+/// the upper bound of 300kg.
+pub fn max_bodyweight_for_points(
+    formula: &dyn PointsFormula,
+    input: &PointsInput,
+    target: Points,
+) -> Option<WeightKg> {
+    if !formula.supports(input.event, input.equipment) {
+        return None;
+    }
+
+    let points = |raw| {
+        let input = PointsInput {
+            bodyweightkg: WeightKg::from_raw(raw),
+            ..*input
+        };
+        formula.points(&input)
+    };
+
+    let lo = input.bodyweightkg.as_raw();
+    let hi = WeightKg::from_i32(MAX_BODYWEIGHTKG).as_raw().max(lo);
+    if !is_non_increasing(lo, hi, points) {
+        return None;
+    }
+    let raw = highest_passing(lo, hi, |raw| points(raw) >= target)?;
+    Some(WeightKg::from_raw(raw))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::formula;
+
+    fn input(bodyweight: f32, total: f32) -> PointsInput {
+        PointsInput {
+            sex: Sex::M,
+            equipment: Equipment::Raw,
+            event: Event::sbd(),
+            bodyweightkg: WeightKg::from_f32(bodyweight),
+            totalkg: WeightKg::from_f32(total),
+            age: Age::None,
+        }
+    }
+
+    #[test]
+    fn total_roundtrips() {
+        // For every formula, the found total is the lightest that reaches
+        // the points of a known performance.
+        let known = input(82.5, 650.0);
+        for f in crate::registry::FORMULAS.iter() {
+            let target = f.points(&known);
+            let total = total_for_points(*f, &known, target).unwrap();
+            assert!(total <= known.totalkg, "{}", f.name());
+
+            let found = PointsInput {
+                totalkg: total,
+                ..known
+            };
+            assert!(f.points(&found) >= target, "{}", f.name());
+
+            let lighter = PointsInput {
+                totalkg: WeightKg::from_raw(total.as_raw() - 1),
+                ..known
+            };
+            assert!(f.points(&lighter) < target, "{}", f.name());
+        }
+    }
+
+    /// Checks that points rise with the total for one kind of lifter,
+    /// and that the bodyweight search gives up wherever they rise with bodyweight.
+    fn check_monotonic(f: &dyn PointsFormula, template: PointsInput) {
+        let at = |bodyweight: i32, total: i32| PointsInput {
+            bodyweightkg: WeightKg::from_i32(bodyweight),
+            totalkg: WeightKg::from_i32(total),
+            ..template
+        };
+
+        for bodyweight in 40..MAX_BODYWEIGHTKG {
+            for total in (25..MAX_TOTALKG).step_by(25) {
+                let here = at(bodyweight, total);
+                let points = f.points(&here);
+                assert!(
+                    points <= f.points(&at(bodyweight, total + 25)),
+                    "{}",
+                    f.name()
+                );
+
+                if points < f.points(&at(bodyweight + 1, total)) {
+                    let max = max_bodyweight_for_points(f, &here, points);
+                    assert!(max.is_none(), "{}", f.name());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn formulas_are_monotonic() {
+        let ages = [Age::None, Age::Exact(14), Age::Exact(23), Age::Exact(80)];
+        let events = [Event::sbd(), Event::s(), Event::b(), Event::d()];
+        for f in crate::registry::FORMULAS.iter() {
+            for &event in events.iter().filter(|&&e| f.supports(e, Equipment::Raw)) {
+                for &sex in &[Sex::M, Sex::F] {
+                    for &equipment in &[Equipment::Raw, Equipment::Single] {
+                        for &age in &ages {
+                            let template = PointsInput {
+                                sex,
+                                equipment,
+                                event,
+                                age,
+                                ..input(0.0, 0.0)
+                            };
+                            check_monotonic(*f, template);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn total_for_dots() {
+        let dots = formula(PointsSystem::Dots).unwrap();
+        let total =
+            total_for_points(dots, &input(82.5, 0.0), Points::from_i32(450)).unwrap();
+        assert_eq!(total, WeightKg::from_f32(664.31));
+    }
+
+    #[test]
+    fn unreachable_targets() {
+        let wilks = formula(PointsSystem::Wilks).unwrap();
+        let target = Points::from_i32(5000);
+        assert!(total_for_points(wilks, &input(82.5, 0.0), target).is_none());
+
+        // Goodlift doesn't apply to Squat-only.
+        let goodlift = formula(PointsSystem::Goodlift).unwrap();
+        let squat = PointsInput {
+            event: Event::s(),
+            ..input(82.5, 0.0)
+        };
+        assert!(total_for_points(goodlift, &squat, Points::from_i32(80)).is_none());
+    }
+
+    #[test]
+    fn bodyweight_for_wilks() {
+        let wilks = formula(PointsSystem::Wilks).unwrap();
+        let current = input(82.5, 650.0);
+        let target = Points::from_i32(400);
+        assert!(wilks.points(&current) >= target);
+
+        let max = max_bodyweight_for_points(wilks, &current, target).unwrap();
+        assert!(max > current.bodyweightkg);
+
+        let at_max = PointsInput {
+            bodyweightkg: max,
+            ..current
+        };
+        assert!(wilks.points(&at_max) >= target);
+        let heavier = PointsInput {
+            bodyweightkg: WeightKg::from_raw(max.as_raw() + 1),
+            ..current
+        };
+        assert!(wilks.points(&heavier) < target);
+
+        // Targets that aren't met now can't be met by gaining weight.
+        let target = Points::from_i32(600);
+        assert!(max_bodyweight_for_points(wilks, &current, target).is_none());
+    }
+}
@@ -9,6 +9,12 @@ pub use crate::dots::dots;
 mod glossbrenner;
 pub use crate::glossbrenner::glossbrenner;
 
+mod goodlift;
+pub use crate::goodlift::goodlift;
+
+mod inverse;
+pub use crate::inverse::{max_bodyweight_for_points, total_for_points};
+
 mod ipf;
 pub use crate::ipf::ipf;
 
@@ -18,6 +24,12 @@ pub use crate::mcculloch::mcculloch;
 mod nasa;
 pub use crate::nasa::nasa;
 
+mod registry;
+pub use crate::registry::{
+    formula, formula_by_slug, points, stored_formulas, stored_index, PointsFormula,
+    PointsInput, FORMULAS, NUM_STORED,
+};
+
 mod reshel;
 pub use crate::reshel::reshel;
 
@@ -26,3 +38,6 @@ pub use crate::schwartzmalone::schwartzmalone;
 
 mod wilks;
 pub use crate::wilks::wilks;
+
+mod wilks2020;
+pub use crate::wilks2020::wilks2020;
@@ -0,0 +1,332 @@
+//! A registry of every implemented points system.
+//!
+//! Code that needs to handle points generically, like sorting or display,
+//! should dispatch through a `PointsFormula` instead of matching on each system.
+
+use opltypes::*;
+
+/// Everything about a performance that a points formula may depend on.
+#[derive(Copy, Clone, Debug)]
+pub struct PointsInput {
+    pub sex: Sex,
+    pub equipment: Equipment,
+    pub event: Event,
+    pub bodyweightkg: WeightKg,
+    pub totalkg: WeightKg,
+    pub age: Age,
+}
+
+/// A powerlifting points system, like Wilks or Glossbrenner.
+pub trait PointsFormula: Sync {
+    /// The `PointsSystem` implemented by this formula.
+    fn system(&self) -> PointsSystem;
+
+    /// The human-readable name of the system, like "Wilks".
+    fn name(&self) -> &'static str;
+
+    /// The name used in URLs, such that sorts are called "by-{slug}".
+    fn slug(&self) -> &'static str;
+
+    /// The entries.csv column that stores precomputed points, if any.
+    ///
+    /// Systems without a column are calculated on demand.
+    fn csv_column(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Whether the formula gives meaningful results for the given division.
+    fn supports(&self, _event: Event, _equipment: Equipment) -> bool {
+        true
+    }
+
+    /// Calculates the points for a performance.
+    fn points(&self, input: &PointsInput) -> Points;
+}
+
+/// Defines a `PointsFormula` for a system whose calculation only depends
+/// on the lifter's sex, bodyweight, and total.
+macro_rules! simple_formula {
+    ($formula:ident, $system:ident, $name:expr, $slug:expr, $function:path) => {
+        simple_formula!($formula, $system, $name, $slug, $function, None);
+    };
+    ($formula:ident, $system:ident, $name:expr, $slug:expr, $function:path, $column:expr) => {
+        struct $formula;
+
+        impl PointsFormula for $formula {
+            fn system(&self) -> PointsSystem {
+                PointsSystem::$system
+            }
+            fn name(&self) -> &'static str {
+                $name
+            }
+            fn slug(&self) -> &'static str {
+                $slug
+            }
+            fn csv_column(&self) -> Option<&'static str> {
+                $column
+            }
+            fn points(&self, input: &PointsInput) -> Points {
+                $function(input.sex, input.bodyweightkg, input.totalkg)
+            }
+        }
+    };
+}
+
+simple_formula!(AHFormula, AH, "AH", "ah", crate::ah);
+simple_formula!(DotsFormula, Dots, "Dots", "dots", crate::dots, Some("Dots"));
+simple_formula!(
+    GlossbrennerFormula,
+    Glossbrenner,
+    "Glossbrenner",
+    "glossbrenner",
+    crate::glossbrenner,
+    Some("Glossbrenner")
+);
+simple_formula!(ReshelFormula, Reshel, "Reshel", "reshel", crate::reshel);
+simple_formula!(
+    SchwartzMaloneFormula,
+    SchwartzMalone,
+    "Schwartz/Malone",
+    "schwartz-malone",
+    crate::schwartzmalone
+);
+simple_formula!(
+    WilksFormula,
+    Wilks,
+    "Wilks",
+    "wilks",
+    crate::wilks,
+    Some("Wilks")
+);
+simple_formula!(
+    Wilks2020Formula,
+    Wilks2020,
+    "Wilks2020",
+    "wilks2020",
+    crate::wilks2020
+);
+
+struct GoodliftFormula;
+
+impl PointsFormula for GoodliftFormula {
+    fn system(&self) -> PointsSystem {
+        PointsSystem::Goodlift
+    }
+    fn name(&self) -> &'static str {
+        "IPF GL Points"
+    }
+    fn slug(&self) -> &'static str {
+        "goodlift"
+    }
+    fn csv_column(&self) -> Option<&'static str> {
+        Some("Goodlift")
+    }
+    fn supports(&self, event: Event, _equipment: Equipment) -> bool {
+        // Parameters are only published for full power and bench-only.
+        event == Event::sbd() || event == Event::b()
+    }
+    fn points(&self, input: &PointsInput) -> Points {
+        crate::goodlift(
+            input.sex,
+            input.equipment,
+            input.event,
+            input.bodyweightkg,
+            input.totalkg,
+        )
+    }
+}
+
+struct IPFPointsFormula;
+
+impl PointsFormula for IPFPointsFormula {
+    fn system(&self) -> PointsSystem {
+        PointsSystem::IPFPoints
+    }
+    fn name(&self) -> &'static str {
+        "IPF Points"
+    }
+    fn slug(&self) -> &'static str {
+        "ipf-points"
+    }
+    fn csv_column(&self) -> Option<&'static str> {
+        Some("IPFPoints")
+    }
+    fn supports(&self, event: Event, _equipment: Equipment) -> bool {
+        // Parameters are published for full power and each single lift.
+        event == Event::sbd()
+            || event == Event::s()
+            || event == Event::b()
+            || event == Event::d()
+    }
+    fn points(&self, input: &PointsInput) -> Points {
+        crate::ipf(
+            input.sex,
+            input.equipment,
+            input.event,
+            input.bodyweightkg,
+            input.totalkg,
+        )
+    }
+}
+
+struct McCullochFormula;
+
+impl PointsFormula for McCullochFormula {
+    fn system(&self) -> PointsSystem {
+        PointsSystem::McCulloch
+    }
+    fn name(&self) -> &'static str {
+        "McCulloch"
+    }
+    fn slug(&self) -> &'static str {
+        "mcculloch"
+    }
+    fn csv_column(&self) -> Option<&'static str> {
+        Some("McCulloch")
+    }
+    fn points(&self, input: &PointsInput) -> Points {
+        crate::mcculloch(input.sex, input.bodyweightkg, input.totalkg, input.age)
+    }
+}
+
+struct NASAFormula;
+
+impl PointsFormula for NASAFormula {
+    fn system(&self) -> PointsSystem {
+        PointsSystem::NASA
+    }
+    fn name(&self) -> &'static str {
+        "NASA"
+    }
+    fn slug(&self) -> &'static str {
+        "nasa"
+    }
+    fn points(&self, input: &PointsInput) -> Points {
+        crate::nasa(input.bodyweightkg, input.totalkg)
+    }
+}
+
+/// Every implemented points system, in alphabetical order.
+pub static FORMULAS: [&dyn PointsFormula; 11] = [
+    &AHFormula,
+    &DotsFormula,
+    &GlossbrennerFormula,
+    &GoodliftFormula,
+    &IPFPointsFormula,
+    &McCullochFormula,
+    &NASAFormula,
+    &ReshelFormula,
+    &SchwartzMaloneFormula,
+    &WilksFormula,
+    &Wilks2020Formula,
+];
+
+/// The number of formulas with a `csv_column`.
+pub const NUM_STORED: usize = 6;
+
+/// Iterates over the formulas with a `csv_column`, in `FORMULAS` order.
+///
+/// The position of a formula in this sequence is its `stored_index()`.
+pub fn stored_formulas() -> impl Iterator<Item = &'static dyn PointsFormula> {
+    FORMULAS
+        .iter()
+        .copied()
+        .filter(|f| f.csv_column().is_some())
+}
+
+/// Looks up the position of a system in `stored_formulas()`, if it has one.
+pub fn stored_index(system: PointsSystem) -> Option<usize> {
+    stored_formulas().position(|f| f.system() == system)
+}
+
+/// Looks up the formula for a `PointsSystem`.
+///
+/// Returns `None` for `PointsSystem::Total`, which isn't a formula.
+pub fn formula(system: PointsSystem) -> Option<&'static dyn PointsFormula> {
+    // This is called per-comparison when sorting, so avoid searching FORMULAS.
+    match system {
+        PointsSystem::AH => Some(&AHFormula),
+        PointsSystem::Dots => Some(&DotsFormula),
+        PointsSystem::Glossbrenner => Some(&GlossbrennerFormula),
+        PointsSystem::Goodlift => Some(&GoodliftFormula),
+        PointsSystem::IPFPoints => Some(&IPFPointsFormula),
+        PointsSystem::McCulloch => Some(&McCullochFormula),
+        PointsSystem::NASA => Some(&NASAFormula),
+        PointsSystem::Reshel => Some(&ReshelFormula),
+        PointsSystem::SchwartzMalone => Some(&SchwartzMaloneFormula),
+        PointsSystem::Wilks => Some(&WilksFormula),
+        PointsSystem::Wilks2020 => Some(&Wilks2020Formula),
+        PointsSystem::Total => None,
+    }
+}
+
+/// Looks up a formula by its URL slug, like "ipf-points".
+pub fn formula_by_slug(slug: &str) -> Option<&'static dyn PointsFormula> {
+    FORMULAS.iter().find(|f| f.slug() == slug).copied()
+}
+
+/// Calculates points in the given system.
+///
+/// `PointsSystem::Total` is not a formula, so it always gives zero points.
+pub fn points(system: PointsSystem, input: &PointsInput) -> Points {
+    match formula(system) {
+        Some(f) if f.supports(input.event, input.equipment) => f.points(input),
+        _ => Points::from_i32(0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_is_consistent() {
+        for f in FORMULAS.iter() {
+            let found = formula(f.system()).unwrap();
+            assert_eq!(found.system(), f.system());
+            assert_eq!(found.name(), f.name());
+
+            let by_slug = formula_by_slug(f.slug()).unwrap();
+            assert_eq!(by_slug.system(), f.system());
+        }
+        assert!(formula(PointsSystem::Total).is_none());
+
+        assert_eq!(stored_formulas().count(), NUM_STORED);
+        for (i, f) in stored_formulas().enumerate() {
+            assert_eq!(stored_index(f.system()), Some(i));
+        }
+        assert_eq!(stored_index(PointsSystem::NASA), None);
+        assert!(formula_by_slug("total").is_none());
+    }
+
+    #[test]
+    fn dispatches_to_formulas() {
+        let input = PointsInput {
+            sex: Sex::M,
+            equipment: Equipment::Raw,
+            event: Event::sbd(),
+            bodyweightkg: WeightKg::from_f32(100.0),
+            totalkg: WeightKg::from_f32(1000.0),
+            age: Age::None,
+        };
+        assert_eq!(
+            points(PointsSystem::Wilks, &input),
+            crate::wilks(input.sex, input.bodyweightkg, input.totalkg)
+        );
+        assert_eq!(
+            points(PointsSystem::McCulloch, &input),
+            points(PointsSystem::Wilks, &input)
+        );
+        assert_eq!(points(PointsSystem::Total, &input), Points::from_i32(0));
+
+        // Goodlift isn't defined for a Squat-only event.
+        let squat = PointsInput {
+            event: Event::s(),
+            ..input
+        };
+        assert!(!formula(PointsSystem::Goodlift)
+            .unwrap()
+            .supports(squat.event, squat.equipment));
+        assert_eq!(points(PointsSystem::Goodlift, &squat), Points::from_i32(0));
+    }
+}
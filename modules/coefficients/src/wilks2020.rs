@@ -0,0 +1,104 @@
+//! Definition of the 2020 revision of Wilks points.
+
+use opltypes::*;
+
+/// Helper function for the fifth-degree Wilks2020 polynomial.
+///
+/// The 2020 revision uses a numerator of 600, instead of the 500 of the
+/// original formula.
+fn wilks2020_coefficient(a: f64, b: f64, c: f64, d: f64, e: f64, f: f64, x: f64) -> f64 {
+    600.0 / (a + b * x + c * x.powi(2) + d * x.powi(3) + e * x.powi(4) + f * x.powi(5))
+}
+
+pub fn wilks2020_coefficient_men(bodyweightkg: f64) -> f64 {
+    const A: f64 = 47.4617885411949;
+    const B: f64 = 8.47206137941125;
+    const C: f64 = 0.073694103462609;
+    const D: f64 = -0.00139583381094385;
+    const E: f64 = 7.07665973070743E-06;
+    const F: f64 = -1.20804336482315E-08;
+
+    // Upper bound avoids asymptote.
+    // Lower bound avoids children with huge coefficients.
+    let adjusted = bodyweightkg.max(40.0).min(200.95);
+
+    wilks2020_coefficient(A, B, C, D, E, F, adjusted)
+}
+
+pub fn wilks2020_coefficient_women(bodyweightkg: f64) -> f64 {
+    const A: f64 = -125.425539779509;
+    const B: f64 = 13.7121941940668;
+    const C: f64 = -0.0330725063103405;
+    const D: f64 = -0.0010504000506583;
+    const E: f64 = 9.38773881462799E-06;
+    const F: f64 = -2.3334613884954E-08;
+
+    // Upper bound avoids asymptote.
+    // Lower bound avoids children with huge coefficients.
+    let adjusted = bodyweightkg.max(40.0).min(150.95);
+
+    wilks2020_coefficient(A, B, C, D, E, F, adjusted)
+}
+
+/// Calculates Wilks points using the 2020 coefficients.
+pub fn wilks2020(sex: Sex, bodyweight: WeightKg, total: WeightKg) -> Points {
+    if bodyweight.is_zero() || total.is_zero() {
+        return Points::from_i32(0);
+    }
+    let coefficient: f64 = match sex {
+        Sex::M => wilks2020_coefficient_men(f64::from(bodyweight)),
+        Sex::F => wilks2020_coefficient_women(f64::from(bodyweight)),
+    };
+    Points::from(coefficient * f64::from(total))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coefficients() {
+        // Coefficients from the published Wilks2020 tables.
+        assert!((wilks2020_coefficient_men(60.0) - 0.99684).abs() < 0.00001);
+        assert!((wilks2020_coefficient_men(100.0) - 0.72936).abs() < 0.00001);
+        assert!((wilks2020_coefficient_women(60.0) - 1.31903).abs() < 0.00001);
+        assert!((wilks2020_coefficient_women(100.0) - 1.05245).abs() < 0.00001);
+
+        // Bodyweights outside the table use the coefficient at the boundary.
+        assert_eq!(
+            wilks2020_coefficient_men(250.0),
+            wilks2020_coefficient_men(200.95)
+        );
+        assert_eq!(
+            wilks2020_coefficient_women(30.0),
+            wilks2020_coefficient_women(40.0)
+        );
+    }
+
+    #[test]
+    fn points() {
+        assert_eq!(
+            wilks2020(Sex::M, WeightKg::from_i32(100), WeightKg::from_i32(1000)),
+            Points::from(729.36)
+        );
+        assert_eq!(
+            wilks2020(Sex::F, WeightKg::from_i32(60), WeightKg::from_i32(500)),
+            Points::from(659.52)
+        );
+    }
+
+    #[test]
+    fn edge_cases() {
+        // Zero bodyweight shouldn't crash.
+        assert_eq!(
+            wilks2020(Sex::M, WeightKg::from_i32(0), WeightKg::from_i32(500)),
+            Points::from_i32(0)
+        );
+
+        // Zero total shouldn't crash.
+        assert_eq!(
+            wilks2020(Sex::F, WeightKg::from_i32(60), WeightKg::from_i32(0)),
+            Points::from_i32(0)
+        );
+    }
+}
@@ -0,0 +1,162 @@
+//! Defines the `BirthYearClass` field for the `entries` table.
+
+use crate::Date;
+
+/// The age categories used by the IPF and its affiliates.
+///
+/// Unlike an `AgeClass`, the category only depends on the year of birth:
+/// every lifter born in 2000 is a Junior (19-23) for every meet in 2019,
+/// whether or not they have had their birthday yet.
+#[derive(Copy, Clone, Debug, Deserialize, EnumString, Serialize, PartialEq)]
+pub enum BirthYearClass {
+    /// Sub-Juniors.
+    #[serde(rename = "14-18")]
+    #[strum(serialize = "14-18")]
+    ClassY14Y18,
+    /// Juniors.
+    #[serde(rename = "19-23")]
+    #[strum(serialize = "19-23")]
+    ClassY19Y23,
+    /// Open lifters who are in no other category.
+    #[serde(rename = "24-39")]
+    #[strum(serialize = "24-39")]
+    ClassY24Y39,
+    /// Masters 1.
+    #[serde(rename = "40-49")]
+    #[strum(serialize = "40-49")]
+    ClassY40Y49,
+    /// Masters 2.
+    #[serde(rename = "50-59")]
+    #[strum(serialize = "50-59")]
+    ClassY50Y59,
+    /// Masters 3.
+    #[serde(rename = "60-69")]
+    #[strum(serialize = "60-69")]
+    ClassY60Y69,
+    /// Masters 4.
+    #[serde(rename = "70-999")]
+    #[strum(serialize = "70-999")]
+    ClassY70Y999,
+    #[serde(rename = "")]
+    #[strum(serialize = "")]
+    None,
+}
+
+impl Default for BirthYearClass {
+    fn default() -> BirthYearClass {
+        BirthYearClass::None
+    }
+}
+
+impl BirthYearClass {
+    /// Assign a BirthYearClass based on the age a lifter turns in the
+    /// year of the meet.
+    pub fn from_year_age(age: u32) -> BirthYearClass {
+        match age {
+            14..=18 => BirthYearClass::ClassY14Y18,
+            19..=23 => BirthYearClass::ClassY19Y23,
+            24..=39 => BirthYearClass::ClassY24Y39,
+            40..=49 => BirthYearClass::ClassY40Y49,
+            50..=59 => BirthYearClass::ClassY50Y59,
+            60..=69 => BirthYearClass::ClassY60Y69,
+            70..=999 => BirthYearClass::ClassY70Y999,
+            _ => BirthYearClass::None,
+        }
+    }
+
+    /// Assign a BirthYearClass based on the BirthYear, for a meet on the given Date.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opltypes::{BirthYearClass, Date};
+    /// let date = Date::from_u32(2019_02_16);
+    /// let class = BirthYearClass::from_birthyear(2000, date);
+    /// assert_eq!(class, BirthYearClass::ClassY19Y23);
+    /// ```
+    pub fn from_birthyear(birthyear: u32, on_date: Date) -> BirthYearClass {
+        let on_year = on_date.year();
+        if on_year < birthyear {
+            return BirthYearClass::None;
+        }
+        BirthYearClass::from_year_age(on_year - birthyear)
+    }
+
+    /// Assign a BirthYearClass based on a range of possible BirthYears.
+    ///
+    /// The range generally comes from age interpolation. If the BirthYears
+    /// fall into different classes, the class is unknown.
+    pub fn from_birthyear_range(min: u32, max: u32, on_date: Date) -> BirthYearClass {
+        let class_min = BirthYearClass::from_birthyear(min, on_date);
+        let class_max = BirthYearClass::from_birthyear(max, on_date);
+        if class_min == class_max {
+            class_min
+        } else {
+            BirthYearClass::None
+        }
+    }
+
+    /// Whether the given BirthYearClass is a BirthYearClass::None.
+    pub fn is_none(self) -> bool {
+        self == BirthYearClass::None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_birthyear() {
+        // The IPF categories change on January 1st, not on birthdays.
+        let jan = Date::from_u32(2019_01_01);
+        let dec = Date::from_u32(2019_12_31);
+        assert_eq!(
+            BirthYearClass::from_birthyear(2001, jan),
+            BirthYearClass::ClassY14Y18
+        );
+        assert_eq!(
+            BirthYearClass::from_birthyear(2000, jan),
+            BirthYearClass::ClassY19Y23
+        );
+        assert_eq!(
+            BirthYearClass::from_birthyear(2000, dec),
+            BirthYearClass::ClassY19Y23
+        );
+        assert_eq!(
+            BirthYearClass::from_birthyear(1996, dec),
+            BirthYearClass::ClassY19Y23
+        );
+        assert_eq!(
+            BirthYearClass::from_birthyear(1995, jan),
+            BirthYearClass::ClassY24Y39
+        );
+        assert_eq!(
+            BirthYearClass::from_birthyear(1979, jan),
+            BirthYearClass::ClassY40Y49
+        );
+        assert_eq!(
+            BirthYearClass::from_birthyear(1949, jan),
+            BirthYearClass::ClassY70Y999
+        );
+
+        // Too young for any category, or born after the meet.
+        assert_eq!(
+            BirthYearClass::from_birthyear(2006, jan),
+            BirthYearClass::None
+        );
+        assert_eq!(
+            BirthYearClass::from_birthyear(2020, jan),
+            BirthYearClass::None
+        );
+    }
+
+    #[test]
+    fn from_birthyear_range() {
+        let date = Date::from_u32(2019_06_01);
+        let class = BirthYearClass::from_birthyear_range(1997, 1998, date);
+        assert_eq!(class, BirthYearClass::ClassY19Y23);
+        let class = BirthYearClass::from_birthyear_range(1995, 1996, date);
+        assert_eq!(class, BirthYearClass::None);
+    }
+}
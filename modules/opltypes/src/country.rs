@@ -1,7 +1,9 @@
 //! Defines the `Country` field for the `meets` table.
 
 /// The Country column.
-#[derive(Copy, Clone, Debug, Deserialize, Serialize, PartialEq, EnumString, ToString)]
+#[derive(
+    Copy, Clone, Debug, Deserialize, Serialize, PartialEq, EnumIter, EnumString, ToString,
+)]
 pub enum Country {
     Afghanistan,
     Albania,
@@ -149,6 +149,11 @@ impl Serialize for Date {
     where
         S: serde::Serializer,
     {
+        // Binary formats store the internal representation.
+        if !serializer.is_human_readable() {
+            return serializer.serialize_u32(self.0);
+        }
+
         let (y, m, d) = (self.year(), self.month(), self.day());
         // TODO: Write into a stack-allocated fixed-size buffer.
         serializer.serialize_str(&format!("{:04}-{:02}-{:02}", y, m, d))
@@ -209,6 +214,13 @@ impl<'de> Visitor<'de> for DateVisitor {
         formatter.write_str("a string in the format YYYY-MM-DD")
     }
 
+    fn visit_u32<E>(self, value: u32) -> Result<Date, E>
+    where
+        E: de::Error,
+    {
+        Ok(Date(value))
+    }
+
     fn visit_str<E>(self, value: &str) -> Result<Date, E>
     where
         E: de::Error,
@@ -222,7 +234,11 @@ impl<'de> Deserialize<'de> for Date {
     where
         D: serde::Deserializer<'de>,
     {
-        deserializer.deserialize_str(DateVisitor)
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(DateVisitor)
+        } else {
+            deserializer.deserialize_u32(DateVisitor)
+        }
     }
 }
 
@@ -199,6 +199,11 @@ impl Serialize for Event {
     where
         S: serde::Serializer,
     {
+        // Binary formats store the internal representation.
+        if !serializer.is_human_readable() {
+            return serializer.serialize_u8(self.0);
+        }
+
         serializer.serialize_str(&format!("{}", self))
     }
 }
@@ -249,6 +254,13 @@ impl<'de> Visitor<'de> for EventVisitor {
         formatter.write_str("a string containing only the characters S,B,D")
     }
 
+    fn visit_u8<E>(self, value: u8) -> Result<Event, E>
+    where
+        E: de::Error,
+    {
+        Ok(Event(value))
+    }
+
     fn visit_str<E>(self, value: &str) -> Result<Event, E>
     where
         E: de::Error,
@@ -262,7 +274,11 @@ impl<'de> Deserialize<'de> for Event {
     where
         D: serde::Deserializer<'de>,
     {
-        deserializer.deserialize_str(EventVisitor)
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(EventVisitor)
+        } else {
+            deserializer.deserialize_u8(EventVisitor)
+        }
     }
 }
 
@@ -1849,14 +1849,27 @@ impl Federation {
     #[inline]
     fn ipf_rules_on(date: Date) -> PointsSystem {
         // The IPF and their affiliates developed a new federation-specific
-        // formula beginning in 2019.
-        if date.year() >= 2019 {
+        // formula beginning in 2019, and replaced it with IPF GL in May 2020.
+        if date >= Date::from_u32(2020_05_01) {
+            PointsSystem::Goodlift
+        } else if date.year() >= 2019 {
             PointsSystem::IPFPoints
         } else {
             PointsSystem::Wilks
         }
     }
 
+    /// Helper function for specifying the PointsSystem of federations under WP rules.
+    #[inline]
+    fn wp_rules_on(date: Date) -> PointsSystem {
+        // World Powerlifting adopted the revised Wilks coefficients in 2020.
+        if date.year() >= 2020 {
+            PointsSystem::Wilks2020
+        } else {
+            PointsSystem::Wilks
+        }
+    }
+
     /// Which points system is default for a federation's meet.
     pub fn default_points(self, date: Date) -> PointsSystem {
         match self {
@@ -1997,7 +2010,7 @@ impl Federation {
             Federation::ORPF => Federation::ipf_rules_on(date),
             Federation::OEVK => Federation::ipf_rules_on(date),
             Federation::ParaPL => PointsSystem::AH,
-            Federation::PA => PointsSystem::Wilks,
+            Federation::PA => Federation::wp_rules_on(date),
             Federation::PAP => Federation::ipf_rules_on(date),
             Federation::PHPL => PointsSystem::Reshel,
             Federation::PLSS => Federation::ipf_rules_on(date),
@@ -2059,7 +2072,7 @@ impl Federation {
             Federation::WBC => PointsSystem::Wilks,
             Federation::WDFPF => PointsSystem::Wilks,
             Federation::WelshPA => Federation::ipf_rules_on(date),
-            Federation::WP => PointsSystem::Wilks,
+            Federation::WP => Federation::wp_rules_on(date),
             Federation::WPA => PointsSystem::Wilks,
             Federation::WPARUS => PointsSystem::Wilks,
             Federation::WPAU => PointsSystem::Wilks,
@@ -2080,7 +2093,7 @@ impl Federation {
             Federation::WPCUKR => PointsSystem::Glossbrenner,
             Federation::WPF => PointsSystem::Wilks,
             Federation::WPLeague => PointsSystem::Wilks,
-            Federation::WPNZ => PointsSystem::Wilks,
+            Federation::WPNZ => Federation::wp_rules_on(date),
             Federation::WPRO => PointsSystem::Wilks,
             Federation::WPU => PointsSystem::Wilks,
             Federation::WPUF => PointsSystem::Wilks,
@@ -2122,4 +2135,20 @@ mod tests {
         // The default to_string() should be the upper-case form.
         assert_eq!(Federation::WRPF.to_string(), "WRPF");
     }
+
+    #[test]
+    fn test_ipf_default_points() {
+        let points = |date| Federation::USAPL.default_points(Date::from_u32(date));
+        assert_eq!(points(2018_12_31), PointsSystem::Wilks);
+        assert_eq!(points(2019_01_01), PointsSystem::IPFPoints);
+        assert_eq!(points(2020_04_30), PointsSystem::IPFPoints);
+        assert_eq!(points(2020_05_01), PointsSystem::Goodlift);
+    }
+
+    #[test]
+    fn test_wp_default_points() {
+        let points = |date| Federation::WP.default_points(Date::from_u32(date));
+        assert_eq!(points(2019_12_31), PointsSystem::Wilks);
+        assert_eq!(points(2020_01_01), PointsSystem::Wilks2020);
+    }
 }
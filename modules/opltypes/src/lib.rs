@@ -12,6 +12,8 @@ extern crate strum_macros;
 
 // External dependencies used only in testing code.
 #[cfg(test)]
+extern crate bincode;
+#[cfg(test)]
 #[macro_use]
 extern crate serde_json;
 
@@ -44,6 +46,8 @@ mod weightclasskg;
 pub use self::weightclasskg::{WeightClassAny, WeightClassKg};
 mod ageclass;
 pub use self::ageclass::AgeClass;
+mod birthyearclass;
+pub use self::birthyearclass::BirthYearClass;
 mod yesno;
 pub use self::yesno::deserialize_yes_no;
 
@@ -2,6 +2,7 @@
 
 use serde;
 use serde::de::{self, Deserialize, Visitor};
+use serde::ser::Serialize;
 
 use std::error::Error;
 use std::fmt;
@@ -9,7 +10,7 @@ use std::num;
 use std::str::FromStr;
 
 /// The definition of the "Place" column.
-#[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Ord, Eq, Serialize)]
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Ord, Eq)]
 pub enum Place {
     /// The placing assigned to the entry.
     P(num::NonZeroU8),
@@ -71,6 +72,35 @@ impl FromStr for Place {
     }
 }
 
+/// Binary encodings of the non-numeric places, which don't fit in a `u8`.
+const BINARY_G: u16 = 256;
+const BINARY_DQ: u16 = 257;
+const BINARY_DD: u16 = 258;
+const BINARY_NS: u16 = 259;
+
+impl Serialize for Place {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        // Binary formats store a compact numeric encoding.
+        if !serializer.is_human_readable() {
+            return serializer.serialize_u16(match *self {
+                Place::P(p) => u16::from(p.get()),
+                Place::G => BINARY_G,
+                Place::DQ => BINARY_DQ,
+                Place::DD => BINARY_DD,
+                Place::NS => BINARY_NS,
+            });
+        }
+
+        match *self {
+            Place::P(p) => serializer.serialize_u8(p.get()),
+            _ => serializer.serialize_str(&format!("{}", self)),
+        }
+    }
+}
+
 struct PlaceVisitor;
 
 impl<'de> Visitor<'de> for PlaceVisitor {
@@ -80,6 +110,22 @@ impl<'de> Visitor<'de> for PlaceVisitor {
         formatter.write_str("an integer or G, DQ, DD, NS")
     }
 
+    fn visit_u16<E>(self, value: u16) -> Result<Place, E>
+    where
+        E: de::Error,
+    {
+        match value {
+            BINARY_G => Ok(Place::G),
+            BINARY_DQ => Ok(Place::DQ),
+            BINARY_DD => Ok(Place::DD),
+            BINARY_NS => Ok(Place::NS),
+            _ if value < 256 => num::NonZeroU8::new(value as u8)
+                .map(Place::P)
+                .ok_or_else(|| E::custom("Place cannot be '0'")),
+            _ => Err(E::custom("invalid binary Place")),
+        }
+    }
+
     fn visit_str<E>(self, value: &str) -> Result<Place, E>
     where
         E: de::Error,
@@ -93,7 +139,11 @@ impl<'de> Deserialize<'de> for Place {
     where
         D: serde::Deserializer<'de>,
     {
-        deserializer.deserialize_str(PlaceVisitor)
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(PlaceVisitor)
+        } else {
+            deserializer.deserialize_u16(PlaceVisitor)
+        }
     }
 }
 
@@ -148,4 +198,17 @@ mod tests {
         let place = "NS".parse::<Place>().unwrap();
         assert_eq!(format!("{}", place), "NS");
     }
+
+    #[test]
+    fn test_place_binary_roundtrip() {
+        for s in &["1", "27", "255", "G", "DQ", "DD", "NS"] {
+            let place = s.parse::<Place>().unwrap();
+            let bytes = bincode::serialize(&place).unwrap();
+            assert_eq!(bincode::deserialize::<Place>(&bytes).unwrap(), place);
+        }
+
+        // Zero is not a valid Place.
+        let bytes = bincode::serialize(&0u16).unwrap();
+        assert!(bincode::deserialize::<Place>(&bytes).is_err());
+    }
 }
@@ -19,14 +19,22 @@ use std::str::FromStr;
 pub struct Points(i32);
 
 /// Enum of known powerlifting points systems, like Wilks and Glossbrenner.
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum PointsSystem {
     AH,
+    Dots,
     Glossbrenner,
+    /// IPF GL Points, which replaced IPF Points in 2020.
+    Goodlift,
     IPFPoints,
+    /// Age-adjusted Wilks, as popularized by the USPA.
+    McCulloch,
     NASA,
     Reshel,
+    SchwartzMalone,
     Wilks,
+    /// The 2020 revision of Wilks.
+    Wilks2020,
 
     /// Expresses that the meet doesn't use a points system.
     Total,
@@ -103,6 +111,11 @@ impl Serialize for Points {
     where
         S: serde::Serializer,
     {
+        // Binary formats store the internal representation.
+        if !serializer.is_human_readable() {
+            return serializer.serialize_i32(self.0);
+        }
+
         // TODO: Write into a stack-allocated fixed-size buffer.
         serializer.serialize_str(&format!("{}", self))
     }
@@ -117,6 +130,13 @@ impl<'de> Visitor<'de> for PointsVisitor {
         formatter.write_str("A floating-point value or the empty string.")
     }
 
+    fn visit_i32<E>(self, value: i32) -> Result<Points, E>
+    where
+        E: de::Error,
+    {
+        Ok(Points(value))
+    }
+
     fn visit_str<E>(self, value: &str) -> Result<Points, E>
     where
         E: de::Error,
@@ -130,7 +150,11 @@ impl<'de> Deserialize<'de> for Points {
     where
         D: serde::Deserializer<'de>,
     {
-        deserializer.deserialize_str(PointsVisitor)
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(PointsVisitor)
+        } else {
+            deserializer.deserialize_i32(PointsVisitor)
+        }
     }
 }
 
@@ -80,7 +80,7 @@ impl Serialize for RuleSet {
     where
         S: serde::Serializer,
     {
-        if self.0 == 0 {
+        if self.0 == 0 && serializer.is_human_readable() {
             // Output nothing instead of zero to save some space.
             serializer.serialize_str("")
         } else {
@@ -125,6 +125,13 @@ impl<'de> Visitor<'de> for RuleSetVisitor {
         formatter.write_str("a space-separated list of rules")
     }
 
+    fn visit_u32<E>(self, value: u32) -> Result<RuleSet, E>
+    where
+        E: de::Error,
+    {
+        Ok(RuleSet(value))
+    }
+
     fn visit_str<E>(self, value: &str) -> Result<RuleSet, E>
     where
         E: de::Error,
@@ -138,7 +145,11 @@ impl<'de> Deserialize<'de> for RuleSet {
     where
         D: serde::Deserializer<'de>,
     {
-        deserializer.deserialize_str(RuleSetVisitor)
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(RuleSetVisitor)
+        } else {
+            deserializer.deserialize_u32(RuleSetVisitor)
+        }
     }
 }
 
@@ -3,6 +3,8 @@
 use serde::ser::Serialize;
 use strum::ParseError;
 
+use std::fmt;
+
 use crate::Country;
 
 /// The State column.
@@ -47,6 +49,48 @@ impl State {
             _ => Err(ParseError::VariantNotFound),
         }
     }
+
+    /// Returns the Country that contains the State.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opltypes::{Country, State, USAState};
+    /// assert_eq!(State::InUSA(USAState::NY).to_country(), Country::USA);
+    /// ```
+    pub fn to_country(self) -> Country {
+        match self {
+            State::InArgentina(_) => Country::Argentina,
+            State::InAustralia(_) => Country::Australia,
+            State::InBrazil(_) => Country::Brazil,
+            State::InCanada(_) => Country::Canada,
+            State::InGermany(_) => Country::Germany,
+            State::InIndia(_) => Country::India,
+            State::InMexico(_) => Country::Mexico,
+            State::InNetherlands(_) => Country::Netherlands,
+            State::InNewZealand(_) => Country::NewZealand,
+            State::InRussia(_) => Country::Russia,
+            State::InUSA(_) => Country::USA,
+        }
+    }
+}
+
+impl fmt::Display for State {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            State::InArgentina(s) => s.fmt(f),
+            State::InAustralia(s) => s.fmt(f),
+            State::InBrazil(s) => s.fmt(f),
+            State::InCanada(s) => s.fmt(f),
+            State::InGermany(s) => s.fmt(f),
+            State::InIndia(s) => s.fmt(f),
+            State::InMexico(s) => s.fmt(f),
+            State::InNetherlands(s) => s.fmt(f),
+            State::InNewZealand(s) => s.fmt(f),
+            State::InRussia(s) => s.fmt(f),
+            State::InUSA(s) => s.fmt(f),
+        }
+    }
 }
 
 impl Serialize for State {
@@ -71,7 +115,7 @@ impl Serialize for State {
 }
 
 /// A state in Argentina.
-#[derive(Copy, Clone, Debug, EnumString, PartialEq, Serialize)]
+#[derive(Copy, Clone, Debug, Display, EnumString, PartialEq, Serialize)]
 pub enum ArgentinaState {
     /// Ciudad Autónoma de Buenos Aires.
     CA,
@@ -125,14 +169,14 @@ pub enum ArgentinaState {
 
 /// A state in Australia.
 #[rustfmt::skip]
-#[derive(Copy, Clone, Debug, EnumString, PartialEq, Serialize)]
+#[derive(Copy, Clone, Debug, Display, EnumString, PartialEq, Serialize)]
 pub enum AustraliaState {
     ACT, NSW, NT, QLD, SA, TAS, VIC, WA
 }
 
 /// A state in Brazil.
 #[rustfmt::skip]
-#[derive(Copy, Clone, Debug, EnumString, PartialEq, Serialize)]
+#[derive(Copy, Clone, Debug, Display, EnumString, PartialEq, Serialize)]
 pub enum BrazilState {
     AC, AL, AP, AM, BA, CE, DF, ES, GO, MA, MT, MS, MG, PA,
     PB, PR, PE, PI, RJ, RN, RS, RO, RR, SC, SP, SE, TO
@@ -140,13 +184,13 @@ pub enum BrazilState {
 
 /// A state in Canada.
 #[rustfmt::skip]
-#[derive(Copy, Clone, Debug, EnumString, PartialEq, Serialize)]
+#[derive(Copy, Clone, Debug, Display, EnumString, PartialEq, Serialize)]
 pub enum CanadaState {
     AB, BC, MB, NB, NL, NT, NS, NU, ON, PE, QC, SK, YT
 }
 
 /// A state in Germany.
-#[derive(Copy, Clone, Debug, EnumString, PartialEq, Serialize)]
+#[derive(Copy, Clone, Debug, Display, EnumString, PartialEq, Serialize)]
 pub enum GermanyState {
     /// Baden-Württemberg.
     BW,
@@ -184,7 +228,7 @@ pub enum GermanyState {
 
 /// A state in India.
 #[rustfmt::skip]
-#[derive(Copy, Clone, Debug, EnumString, PartialEq, Serialize)]
+#[derive(Copy, Clone, Debug, Display, EnumString, PartialEq, Serialize)]
 pub enum IndiaState {
     AP, AR, AS, BR, CG, GA, GJ, HR, HP, JK, JH, KA,
     KL, MP, MH, MN, ML, MZ, NL, OR, PB, RJ, SK, TN,
@@ -193,7 +237,7 @@ pub enum IndiaState {
 
 /// A state in Mexico.
 #[rustfmt::skip]
-#[derive(Copy, Clone, Debug, EnumString, PartialEq, Serialize)]
+#[derive(Copy, Clone, Debug, Display, EnumString, PartialEq, Serialize)]
 pub enum MexicoState {
     AG, BC, BS, CM, CS, CH, CO, CL, DF, DG, GT,
     GR, HG, JA, EM, MI, MO, NA, NL, OA, PU, QT,
@@ -202,7 +246,7 @@ pub enum MexicoState {
 
 /// A state in the Netherlands
 #[rustfmt::skip]
-#[derive(Copy, Clone, Debug, EnumString, PartialEq, Serialize)]
+#[derive(Copy, Clone, Debug, Display, EnumString, PartialEq, Serialize)]
 pub enum NetherlandsState {
     DR, FL, FR, GE, GR, LI, NB, NH, OV, UT, ZE,
     ZH
@@ -210,7 +254,7 @@ pub enum NetherlandsState {
 
 /// A state in New Zealand.
 #[rustfmt::skip]
-#[derive(Copy, Clone, Debug, EnumString, PartialEq, Serialize)]
+#[derive(Copy, Clone, Debug, Display, EnumString, PartialEq, Serialize)]
 pub enum NewZealandState {
     NTL, AKL, WKO, BOP, GIS, HKB, TKI, MWT, WGN,
     TAS, NSN, MBH, WTC, CAN, OTA, STL
@@ -218,7 +262,7 @@ pub enum NewZealandState {
 
 /// A state in Russia.
 #[rustfmt::skip]
-#[derive(Copy, Clone, Debug, EnumString, PartialEq, Serialize)]
+#[derive(Copy, Clone, Debug, Display, EnumString, PartialEq, Serialize)]
 pub enum RussiaState {
     AD, AL, BA, BU, CE, CU, DA, IN, KB, KL, KC, KR, KK, KO, ME, MO, SA,
     SE, TA, TY, UD, ALT, KAM, KHA, KDA, KYA, PER, PRO, STA, ZAB, AMU, ARK,
@@ -230,7 +274,7 @@ pub enum RussiaState {
 
 /// A state in the USA.
 #[rustfmt::skip]
-#[derive(Copy, Clone, Debug, EnumString, PartialEq, Serialize)]
+#[derive(Copy, Clone, Debug, Display, EnumString, PartialEq, Serialize)]
 pub enum USAState {
     AL, AK, AZ, AR, CA, CO, CT, DE, DC, FL, GA, HI, ID, IL, IN, IA, KS,
     KY, LA, ME, MD, MA, MI, MN, MS, MO, MT, NE, NV, NH, NJ, NM, NY, NC,
@@ -35,10 +35,15 @@ impl Serialize for WeightKg {
     /// Serialize with two decimal places, exactly as in the original.
     ///
     /// This is intended for use by the compiler when writing the entries.csv.
+    /// Binary formats store the internal representation instead.
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
+        if !serializer.is_human_readable() {
+            return serializer.serialize_i32(self.0);
+        }
+
         if self.0 == 0 {
             return serializer.serialize_str("");
         }
@@ -110,6 +115,12 @@ impl WeightKg {
         WeightKg(i)
     }
 
+    /// Gets the internal representation, in hundredths of a kilogram.
+    #[inline]
+    pub const fn as_raw(self) -> i32 {
+        self.0
+    }
+
     #[inline]
     pub fn from_f32(f: f32) -> WeightKg {
         if f.is_finite() {
@@ -291,6 +302,13 @@ impl<'de> Visitor<'de> for WeightKgVisitor {
         formatter.write_str("A floating-point value or the empty string.")
     }
 
+    fn visit_i32<E>(self, value: i32) -> Result<WeightKg, E>
+    where
+        E: de::Error,
+    {
+        Ok(WeightKg(value))
+    }
+
     fn visit_str<E>(self, value: &str) -> Result<WeightKg, E>
     where
         E: de::Error,
@@ -304,7 +322,11 @@ impl<'de> Deserialize<'de> for WeightKg {
     where
         D: serde::Deserializer<'de>,
     {
-        deserializer.deserialize_str(WeightKgVisitor)
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(WeightKgVisitor)
+        } else {
+            deserializer.deserialize_i32(WeightKgVisitor)
+        }
     }
 }
 
@@ -433,4 +455,14 @@ mod tests {
         let w2 = "200".parse::<WeightKg>().unwrap();
         assert!(w1 < w2);
     }
+
+    #[test]
+    fn test_weightkg_binary_roundtrip() {
+        for s in &["", "0.5", "-102.5", "109.04", "1000"] {
+            let w = s.parse::<WeightKg>().unwrap();
+            let bytes = bincode::serialize(&w).unwrap();
+            assert_eq!(bytes.len(), 4);
+            assert_eq!(bincode::deserialize::<WeightKg>(&bytes).unwrap(), w);
+        }
+    }
 }
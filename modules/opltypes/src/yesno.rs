@@ -15,6 +15,13 @@ impl<'de> Visitor<'de> for YesNo {
         formatter.write_str("'Yes' or 'No'")
     }
 
+    fn visit_bool<E>(self, value: bool) -> Result<bool, E>
+    where
+        E: de::Error,
+    {
+        Ok(value)
+    }
+
     fn visit_str<E>(self, value: &str) -> Result<bool, E>
     where
         E: de::Error,
@@ -28,9 +35,15 @@ impl<'de> Visitor<'de> for YesNo {
 }
 
 /// Deserialization helper, converting "Yes" and "No" to a boolean.
+///
+/// Binary formats store the boolean directly.
 pub fn deserialize_yes_no<'de, D>(de: D) -> Result<bool, D::Error>
 where
     D: serde::Deserializer<'de>,
 {
-    de.deserialize_str(YesNo)
+    if de.is_human_readable() {
+        de.deserialize_str(YesNo)
+    } else {
+        de.deserialize_bool(YesNo)
+    }
 }
@@ -2,9 +2,11 @@
 
 #![feature(test)]
 
+extern crate opltypes;
 extern crate server;
+use opltypes::PointsSystem;
 use server::opldb::algorithms::*;
-use server::opldb::OplDb;
+use server::opldb::{Entry, Meet, OplDb};
 
 use std::sync::Once;
 
@@ -15,11 +17,21 @@ fn db() -> &'static OplDb {
     const LIFTERS_CSV: &str = "../build/lifters.csv";
     const MEETS_CSV: &str = "../build/meets.csv";
     const ENTRIES_CSV: &str = "../build/entries.csv";
+    const WEIGHTCLASSES_CSV: &str = "../build/weightclasses.csv";
+    const SNAPSHOT: &str = "../build/opldb.snapshot";
 
     unsafe {
         OPLDB_INIT.call_once(|| {
-            OPLDB_GLOBAL =
-                Some(OplDb::from_csv(LIFTERS_CSV, MEETS_CSV, ENTRIES_CSV).unwrap())
+            OPLDB_GLOBAL = Some(
+                OplDb::from_snapshot_or_csv(
+                    SNAPSHOT,
+                    LIFTERS_CSV,
+                    MEETS_CSV,
+                    ENTRIES_CSV,
+                    WEIGHTCLASSES_CSV,
+                )
+                .unwrap(),
+            )
         });
 
         OPLDB_GLOBAL.as_ref().unwrap()
@@ -60,8 +72,10 @@ mod benches {
             cache.log_linear_time.raw_wraps.sort_and_unique_by(
                 opldb.get_entries(),
                 opldb.get_meets(),
-                &cmp_wilks,
-                &filter_wilks,
+                &|m: &[Meet], a: &Entry, b: &Entry| {
+                    cmp_points(PointsSystem::Wilks, m, a, b)
+                },
+                &|e: &Entry| filter_points(PointsSystem::Wilks, e),
             );
         });
     }
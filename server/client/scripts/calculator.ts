@@ -0,0 +1,103 @@
+// vim: set ts=4 sts=4 sw=4 et:
+//
+// Implementation of main logic for the Points Calculator page.
+
+'use strict';
+
+// These are generated inline via templates/calculator.html.tera.
+declare const translation_out_of_reach: string;
+declare const translation_needs_total: string;
+declare const translation_needs_total_up_to: string;
+
+let inputIds = ["sex", "equipment", "event", "bodyweight", "total", "age", "target", "system"];
+let activeRequest: XMLHttpRequest | null = null;
+
+function inputValue(id: string): string {
+    return (document.getElementById(id) as HTMLInputElement).value;
+}
+
+// Builds the URL for the calculator API from the form inputs.
+// Optional inputs are only passed along if they are filled in.
+function makeApiUrl(): string {
+    let url = "/api/v1/calculator?sex=" + encodeURIComponent(inputValue("sex"));
+    url += "&equipment=" + encodeURIComponent(inputValue("equipment"));
+    url += "&event=" + encodeURIComponent(inputValue("event"));
+    url += "&bodyweight=" + encodeURIComponent(inputValue("bodyweight"));
+    url += "&total=" + encodeURIComponent(inputValue("total"));
+    if (inputValue("age") !== "") {
+        url += "&age=" + encodeURIComponent(inputValue("age"));
+    }
+    if (inputValue("target") !== "") {
+        url += "&target=" + encodeURIComponent(inputValue("target"));
+        url += "&system=" + encodeURIComponent(inputValue("system"));
+    }
+    return url;
+}
+
+function showResults(calculation) {
+    let tbody = document.getElementById("results") as HTMLTableSectionElement;
+    tbody.innerHTML = "";
+
+    for (let row of calculation.points) {
+        let tr = document.createElement("tr");
+        let name = document.createElement("td");
+        name.textContent = row.system;
+        let points = document.createElement("td");
+        points.textContent = row.points;
+        tr.appendChild(name);
+        tr.appendChild(points);
+        tbody.appendChild(tr);
+    }
+
+    let result = document.getElementById("target-result") as HTMLParagraphElement;
+    let target = calculation.target;
+    if (target === null) {
+        result.textContent = "";
+        return;
+    }
+
+    let text = target.target + " " + target.system + ": ";
+    if (target.totalkg === null) {
+        text += translation_out_of_reach;
+    } else if (target.max_bodyweightkg === null) {
+        text += translation_needs_total.replace("{total}", target.totalkg);
+    } else {
+        text += translation_needs_total_up_to
+            .replace("{total}", target.totalkg)
+            .replace("{bodyweight}", target.max_bodyweightkg);
+    }
+    result.textContent = text;
+}
+
+// Recalculates whenever the inputs change.
+function recalculate() {
+    if (inputValue("bodyweight") === "" || inputValue("total") === "") {
+        return;
+    }
+
+    if (activeRequest !== null) {
+        activeRequest.abort();
+    }
+
+    let handle = new XMLHttpRequest();
+    handle.open("GET", makeApiUrl());
+    handle.responseType = "json";
+    handle.addEventListener("load", function(e) {
+        if (handle.status === 200) {
+            showResults(handle.response);
+        }
+        activeRequest = null;
+    });
+    activeRequest = handle;
+    handle.send();
+}
+
+function addEventListeners() {
+    for (let id of inputIds) {
+        let elem = document.getElementById(id) as HTMLElement;
+        elem.addEventListener("change", recalculate);
+        elem.addEventListener("input", recalculate);
+    }
+}
+
+document.addEventListener("DOMContentLoaded", addEventListeners);
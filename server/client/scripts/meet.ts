@@ -8,11 +8,14 @@
 declare const path_if_by_ah: string;
 declare const path_if_by_division: string;
 declare const path_if_by_glossbrenner: string;
+declare const path_if_by_goodlift: string;
 declare const path_if_by_ipfpoints: string;
 declare const path_if_by_nasa: string;
 declare const path_if_by_reshel: string;
+declare const path_if_by_schwartzmalone: string;
 declare const path_if_by_total: string;
 declare const path_if_by_wilks: string;
+declare const path_if_by_wilks2020: string;
 
 let selSort: HTMLSelectElement;
 
@@ -29,6 +32,9 @@ function redirect() {
         case "by-glossbrenner":
             window.location.href = path_if_by_glossbrenner;
             break;
+        case "by-goodlift":
+            window.location.href = path_if_by_goodlift;
+            break;
         case "by-ipf-points":
             window.location.href = path_if_by_ipfpoints;
             break;
@@ -38,12 +44,18 @@ function redirect() {
         case "by-reshel":
             window.location.href = path_if_by_reshel;
             break;
+        case "by-schwartz-malone":
+            window.location.href = path_if_by_schwartzmalone;
+            break;
         case "by-total":
             window.location.href = path_if_by_total;
             break;
         case "by-wilks":
             window.location.href = path_if_by_wilks;
             break;
+        case "by-wilks2020":
+            window.location.href = path_if_by_wilks2020;
+            break;
     }
 }
 
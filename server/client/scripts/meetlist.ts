@@ -6,6 +6,7 @@
 
 let selFed: HTMLSelectElement;
 let selYear: HTMLSelectElement;
+let selTested: HTMLSelectElement;
 
 // Returns a string like "/uspa/2018", or the empty string
 // for the default selection.
@@ -17,6 +18,9 @@ function selection_to_path(): string {
     if (selYear.value !== "all") {
         url += "/" + selYear.value;
     }
+    if (selTested.value !== "all") {
+        url += "/" + selTested.value;
+    }
     return url;
 }
 
@@ -39,9 +43,11 @@ function addSelectorListeners(selector) {
 function addEventListeners() {
     selFed = document.getElementById("fedselect") as HTMLSelectElement;
     selYear = document.getElementById("yearselect") as HTMLSelectElement;
+    selTested = document.getElementById("testedselect") as HTMLSelectElement;
 
     addSelectorListeners(selFed);
     addSelectorListeners(selYear);
+    addSelectorListeners(selTested);
 }
 
 document.addEventListener("DOMContentLoaded", addEventListeners);
@@ -20,8 +20,13 @@ declare const default_fed: string;
 declare const default_sex: string;
 declare const default_ageclass: string;
 declare const default_year: string;
+declare const default_daterange: string;
+declare const default_country: string;
+declare const default_location: string;
+declare const default_tested: string | undefined;
 declare const default_event: string;
 declare const default_sort: string;
+declare const default_performances: string | undefined;
 
 declare const translation_column_formulaplace: string;
 declare const translation_column_liftername: string;
@@ -38,9 +43,13 @@ declare const translation_column_bench: string;
 declare const translation_column_deadlift: string;
 declare const translation_column_total: string;
 declare const translation_column_wilks: string;
+declare const translation_column_wilks2020: string;
 declare const translation_column_mcculloch: string;
 declare const translation_column_glossbrenner: string;
 declare const translation_column_ipfpoints: string;
+declare const translation_column_goodlift: string;
+declare const translation_column_dots: string;
+declare const translation_column_ratio: string;
 
 let global_grid;  // The SlickGrid.
 let global_cache;  // The active RemoteCache rendered in the SlickGrid.
@@ -65,9 +74,14 @@ let selWeightClass: HTMLSelectElement;
 let selFed: HTMLSelectElement;
 let selAgeClass: HTMLSelectElement;
 let selYear: HTMLSelectElement;
+let selDateRange: HTMLSelectElement;
+let selCountry: HTMLSelectElement;
+let selLocation: HTMLSelectElement;
+let selTested: HTMLSelectElement | null; // OpenIPF doesn't use this.
 let selSex: HTMLSelectElement;
 let selEvent: HTMLSelectElement;
 let selSort: HTMLSelectElement;
+let selPerformances: HTMLSelectElement | null; // OpenIPF doesn't use this.
 
 let searchField: HTMLInputElement;
 let searchButton: HTMLButtonElement;
@@ -182,6 +196,31 @@ function selection_to_points_title(): string {
     if (sort === "by-ipf-points") {
         return translation_column_ipfpoints;
     }
+    if (sort === "by-goodlift") {
+        return translation_column_goodlift;
+    }
+    if (sort === "by-wilks2020") {
+        return translation_column_wilks2020;
+    }
+    if (sort === "by-dots") {
+        return translation_column_dots;
+    }
+    if (sort === "by-ah") {
+        return "AH";
+    }
+    if (sort === "by-nasa") {
+        return "NASA";
+    }
+    if (sort === "by-reshel") {
+        return "Reshel";
+    }
+    if (sort === "by-schwartz-malone") {
+        return "Schwartz/Malone";
+    }
+    if (sort === "by-squat-ratio" || sort === "by-bench-ratio" ||
+        sort === "by-deadlift-ratio" || sort === "by-total-ratio") {
+        return translation_column_ratio;
+    }
     return translation_column_wilks;
 }
 
@@ -216,12 +255,27 @@ function selection_to_path(): string {
     if (selYear.value !== default_year) {
         url += "/" + selYear.value;
     }
+    if (selDateRange.value !== default_daterange) {
+        url += "/" + selDateRange.value;
+    }
+    if (selCountry.value !== default_country) {
+        url += "/" + selCountry.value;
+    }
+    if (selLocation.value !== default_location) {
+        url += "/" + selLocation.value;
+    }
+    if (selTested && selTested.value !== default_tested) {
+        url += "/" + selTested.value;
+    }
     if (selEvent.value !== default_event) {
         url += "/" + selEvent.value;
     }
     if (selSort.value !== default_sort) {
         url += "/" + selSort.value;
     }
+    if (selPerformances && selPerformances.value !== default_performances) {
+        url += "/" + selPerformances.value;
+    }
     return url;
 }
 
@@ -234,8 +288,13 @@ function saveSelectionState() {
         "sex": selSex.value,
         "ageclass": selAgeClass.value,
         "year": selYear.value,
+        "daterange": selDateRange.value,
+        "country": selCountry.value,
+        "location": selLocation.value,
+        "tested": selTested ? selTested.value : undefined,
         "event": selEvent.value,
-        "sort": selSort.value
+        "sort": selSort.value,
+        "performances": selPerformances ? selPerformances.value : undefined
     };
 }
 
@@ -251,8 +310,17 @@ function restoreSelectionState(state) {
     selSex.value = state["sex"];
     selAgeClass.value = state["ageclass"];
     selYear.value = state["year"];
+    selDateRange.value = state["daterange"];
+    selCountry.value = state["country"];
+    selLocation.value = state["location"];
+    if (selTested) {
+        selTested.value = state["tested"];
+    }
     selEvent.value = state["event"];
     selSort.value = state["sort"];
+    if (selPerformances) {
+        selPerformances.value = state["performances"];
+    }
 
     addAllSelectorListeners();
 }
@@ -287,13 +355,17 @@ function changeSelection() {
 }
 
 function addSelectorListeners(selector) {
-    selector.addEventListener("change", changeSelection);
+    if (selector) {
+        selector.addEventListener("change", changeSelection);
+    }
 }
 
 // Used when navigating through history: otherwise navigation
 // would add more history events.
 function removeSelectorListeners(selector) {
-    selector.removeEventListener("change", changeSelection);
+    if (selector) {
+        selector.removeEventListener("change", changeSelection);
+    }
 }
 
 function addAllSelectorListeners() {
@@ -302,9 +374,14 @@ function addAllSelectorListeners() {
     addSelectorListeners(selFed);
     addSelectorListeners(selAgeClass);
     addSelectorListeners(selYear);
+    addSelectorListeners(selDateRange);
+    addSelectorListeners(selCountry);
+    addSelectorListeners(selLocation);
+    addSelectorListeners(selTested);
     addSelectorListeners(selSex);
     addSelectorListeners(selEvent);
     addSelectorListeners(selSort);
+    addSelectorListeners(selPerformances);
 }
 
 function removeAllSelectorListeners() {
@@ -313,9 +390,14 @@ function removeAllSelectorListeners() {
     removeSelectorListeners(selFed);
     removeSelectorListeners(selAgeClass);
     removeSelectorListeners(selYear);
+    removeSelectorListeners(selDateRange);
+    removeSelectorListeners(selCountry);
+    removeSelectorListeners(selLocation);
+    removeSelectorListeners(selTested);
     removeSelectorListeners(selSex);
     removeSelectorListeners(selEvent);
     removeSelectorListeners(selSort);
+    removeSelectorListeners(selPerformances);
 }
 
 function initializeEventListeners() {
@@ -324,9 +406,14 @@ function initializeEventListeners() {
     selFed = document.getElementById("fedselect") as HTMLSelectElement;
     selAgeClass = document.getElementById("ageselect") as HTMLSelectElement;
     selYear = document.getElementById("yearselect") as HTMLSelectElement;
+    selDateRange = document.getElementById("dateselect") as HTMLSelectElement;
+    selCountry = document.getElementById("countryselect") as HTMLSelectElement;
+    selLocation = document.getElementById("locationselect") as HTMLSelectElement;
+    selTested = document.getElementById("testedselect") as HTMLSelectElement;
     selSex = document.getElementById("sexselect") as HTMLSelectElement;
     selEvent = document.getElementById("eventselect") as HTMLSelectElement;
     selSort = document.getElementById("sortselect") as HTMLSelectElement;
+    selPerformances = document.getElementById("performancesselect") as HTMLSelectElement;
     searchField = document.getElementById("searchfield") as HTMLInputElement;
     searchButton = document.getElementById("searchbutton") as HTMLButtonElement;
 
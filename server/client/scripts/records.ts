@@ -13,6 +13,7 @@ declare const default_fed: string;
 declare const default_sex: string;
 declare const default_ageclass: string;
 declare const default_year: string;
+declare const default_tested: string | undefined;
 
 let selEquipment: HTMLSelectElement;
 let selClassKind: HTMLSelectElement | null; // OpenIPF doesn't use this.
@@ -20,6 +21,7 @@ let selSex: HTMLSelectElement;
 let selFederation: HTMLSelectElement;
 let selAgeClass: HTMLSelectElement;
 let selRecordsYear: HTMLSelectElement;
+let selTested: HTMLSelectElement | null; // OpenIPF doesn't use this.
 
 // Returns a string like "/women/uspa", or the empty string
 // for the default selection.
@@ -43,6 +45,9 @@ function records_selection_to_path(): string {
     if (selRecordsYear.value !== default_year) {
         url += "/" + selRecordsYear.value;
     }
+    if (selTested && selTested.value !== default_tested) {
+        url += "/" + selTested.value;
+    }
     return url;
 }
 
@@ -71,6 +76,7 @@ function records_addEventListeners() {
     selFederation = document.getElementById("fedselect") as HTMLSelectElement;
     selAgeClass = document.getElementById("ageselect") as HTMLSelectElement;
     selRecordsYear = document.getElementById("yearselect") as HTMLSelectElement;
+    selTested = document.getElementById("testedselect") as HTMLSelectElement;
 
     records_addSelectorListeners(selEquipment);
     records_addSelectorListeners(selClassKind);
@@ -78,6 +84,7 @@ function records_addEventListeners() {
     records_addSelectorListeners(selFederation);
     records_addSelectorListeners(selAgeClass);
     records_addSelectorListeners(selRecordsYear);
+    records_addSelectorListeners(selTested);
 }
 
 document.addEventListener("DOMContentLoaded", records_addEventListeners);
@@ -3,18 +3,21 @@
 use rocket::http::{Cookies, Status};
 use rocket::request::{self, FromRequest, Request};
 use rocket::response::{self, content, Responder};
-use rocket::Outcome;
+use rocket::{Outcome, State};
 
 use opltypes::WeightUnits;
 
 use server::langpack::{self, LangInfo, Language, Locale};
 use server::opldb;
+use server::pages;
 
-// Use a single static database when testing.
-#[cfg(not(test))]
-pub type ManagedOplDb = opldb::OplDb;
-#[cfg(test)]
-pub type ManagedOplDb = &'static opldb::OplDb;
+use std::env;
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// The database is swappable at runtime, so that new data can be loaded
+/// without restarting the server. Handlers should use `CurrentOplDb`.
+pub type ManagedOplDb = opldb::SharedOplDb;
 
 // Use a single static langpack when testing.
 #[cfg(not(test))]
@@ -22,6 +25,81 @@ pub type ManagedLangInfo = langpack::LangInfo;
 #[cfg(test)]
 pub type ManagedLangInfo = &'static langpack::LangInfo;
 
+/// Request guard for the database that is current when the request begins.
+///
+/// Holding the database for the whole request means that a reload
+/// happening at the same time can't change the data partway through.
+pub struct CurrentOplDb(Arc<opldb::OplDb>);
+
+impl Deref for CurrentOplDb {
+    type Target = opldb::OplDb;
+
+    fn deref(&self) -> &opldb::OplDb {
+        &self.0
+    }
+}
+
+impl<'a, 'r> FromRequest<'a, 'r> for CurrentOplDb {
+    type Error = ();
+
+    fn from_request(request: &'a Request<'r>) -> request::Outcome<CurrentOplDb, ()> {
+        let shared = request.guard::<State<ManagedOplDb>>()?;
+        Outcome::Success(CurrentOplDb(shared.get()))
+    }
+}
+
+/// Configuration for administrative endpoints, managed by Rocket.
+pub struct AdminConfig {
+    /// The token that `AdminToken` expects, or `None` if administrative
+    /// endpoints are disabled.
+    pub token: Option<String>,
+}
+
+impl AdminConfig {
+    /// Reads the token from the ADMIN_TOKEN environment variable.
+    /// If ADMIN_TOKEN is unset or empty, administrative endpoints are disabled.
+    pub fn from_env() -> AdminConfig {
+        AdminConfig {
+            token: env::var("ADMIN_TOKEN").ok().filter(|t| !t.is_empty()),
+        }
+    }
+}
+
+/// Request guard for administrative endpoints.
+///
+/// The request must have an "Authorization: Bearer <token>" header,
+/// where the token matches the one in the managed `AdminConfig`.
+/// If there is no token, administrative endpoints don't exist.
+pub struct AdminToken;
+
+/// Compares strings in time independent of where they first differ.
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0, |acc, (x, y)| acc | (x ^ y))
+            == 0
+}
+
+impl<'a, 'r> FromRequest<'a, 'r> for AdminToken {
+    type Error = ();
+
+    fn from_request(request: &'a Request<'r>) -> request::Outcome<AdminToken, ()> {
+        let config = request.guard::<State<AdminConfig>>()?;
+        let expected = match config.token {
+            Some(ref token) => format!("Bearer {}", token),
+            None => return Outcome::Failure((Status::NotFound, ())),
+        };
+
+        match request.headers().get_one("Authorization") {
+            Some(auth) if constant_time_eq(auth, &expected) => {
+                Outcome::Success(AdminToken)
+            }
+            _ => Outcome::Failure((Status::Unauthorized, ())),
+        }
+    }
+}
+
 /// Request guard for reading the "Accept-Encoding" HTTP header.
 pub struct AcceptEncoding(pub Option<String>);
 
@@ -145,9 +223,44 @@ pub struct RankingsApiQuery {
     pub units: String,
 }
 
+/// Paging parameters for the public /api/v1/ endpoints.
+#[derive(FromForm)]
+pub struct ApiV1PagingQuery {
+    pub start: Option<usize>,
+    pub limit: Option<usize>,
+}
+
 // TODO: Version / magicValue / etc.
 #[derive(FromForm)]
 pub struct SearchRankingsApiQuery {
     pub q: String,
     pub start: usize,
 }
+
+/// Inputs to the points calculator API. Weights are in kilograms.
+#[derive(FromForm)]
+pub struct CalculatorApiQuery {
+    pub sex: String,
+    pub bodyweight: String,
+    pub total: String,
+    pub equipment: Option<String>,
+    pub event: Option<String>,
+    pub age: Option<String>,
+    pub target: Option<String>,
+    pub system: Option<String>,
+}
+
+impl CalculatorApiQuery {
+    pub fn as_query(&self) -> pages::calculator::Query {
+        pages::calculator::Query {
+            sex: &self.sex,
+            bodyweight: &self.bodyweight,
+            total: &self.total,
+            equipment: self.equipment.as_ref().map(String::as_str),
+            event: self.event.as_ref().map(String::as_str),
+            age: self.age.as_ref().map(String::as_str),
+            target: self.target.as_ref().map(String::as_str),
+            system: self.system.as_ref().map(String::as_str),
+        }
+    }
+}
@@ -36,8 +36,14 @@ fn default_openipf_selection() -> pages::selection::Selection {
         sex: SexSelection::AllSexes,
         ageclass: AgeClassSelection::AllAges,
         year: YearSelection::AllYears,
+        daterange: DateRangeSelection::AllDates,
+        country: LifterCountrySelection::AllCountries,
+        meetcountry: MeetCountrySelection::AllCountries,
+        meetstate: MeetStateSelection::AllStates,
+        tested: TestedSelection::AllEntries,
         event: EventSelection::FullPower,
-        sort: SortSelection::ByIPFPoints,
+        sort: SortSelection::ByPoints(PointsSystem::IPFPoints),
+        performances: PerformancesSelection::BestPerLifter,
     }
 }
 
@@ -46,7 +52,7 @@ fn default_openipf_selection() -> pages::selection::Selection {
 #[get("/?<lang>")]
 pub fn index(
     lang: Option<String>,
-    opldb: State<ManagedOplDb>,
+    opldb: CurrentOplDb,
     langinfo: State<ManagedLangInfo>,
     languages: AcceptLanguage,
     cookies: Cookies,
@@ -67,7 +73,7 @@ pub fn index(
 pub fn rankings(
     selections: PathBuf,
     lang: Option<String>,
-    opldb: State<ManagedOplDb>,
+    opldb: CurrentOplDb,
     langinfo: State<ManagedLangInfo>,
     languages: AcceptLanguage,
     cookies: Cookies,
@@ -85,7 +91,7 @@ pub fn rankings(
 pub fn rankings_api(
     selections: Option<PathBuf>,
     query: Form<RankingsApiQuery>,
-    opldb: State<ManagedOplDb>,
+    opldb: CurrentOplDb,
     langinfo: State<ManagedLangInfo>,
 ) -> Option<JsonString> {
     let default = default_openipf_selection();
@@ -114,7 +120,7 @@ pub fn rankings_api(
 #[get("/api/rankings?<query..>")]
 pub fn default_rankings_api(
     query: Form<RankingsApiQuery>,
-    opldb: State<ManagedOplDb>,
+    opldb: CurrentOplDb,
     langinfo: State<ManagedLangInfo>,
 ) -> Option<JsonString> {
     rankings_api(None, query, opldb, langinfo)
@@ -125,7 +131,7 @@ pub fn default_rankings_api(
 pub fn search_rankings_api<'db>(
     selections: Option<PathBuf>,
     query: Form<SearchRankingsApiQuery>,
-    opldb: State<ManagedOplDb>,
+    opldb: CurrentOplDb,
 ) -> Option<JsonString> {
     let default = default_openipf_selection();
     let selection = match selections {
@@ -142,7 +148,7 @@ pub fn search_rankings_api<'db>(
 #[get("/api/search/rankings?<query..>")]
 pub fn default_search_rankings_api(
     query: Form<SearchRankingsApiQuery>,
-    opldb: State<ManagedOplDb>,
+    opldb: CurrentOplDb,
 ) -> Option<JsonString> {
     search_rankings_api(None, query, opldb)
 }
@@ -151,7 +157,7 @@ pub fn default_search_rankings_api(
 pub fn records(
     selections: Option<PathBuf>,
     lang: Option<String>,
-    opldb: State<ManagedOplDb>,
+    opldb: CurrentOplDb,
     langinfo: State<ManagedLangInfo>,
     languages: AcceptLanguage,
     cookies: Cookies,
@@ -164,6 +170,7 @@ pub fn records(
         classkind: pages::records::ClassKindSelection::IPF,
         ageclass: default_rankings.ageclass,
         year: default_rankings.year,
+        tested: default_rankings.tested,
     };
 
     let selection = if let Some(sel) = selections {
@@ -185,7 +192,7 @@ pub fn records(
 #[get("/records?<lang>")]
 pub fn records_default(
     lang: Option<String>,
-    opldb: State<ManagedOplDb>,
+    opldb: CurrentOplDb,
     langinfo: State<ManagedLangInfo>,
     languages: AcceptLanguage,
     cookies: Cookies,
@@ -203,31 +210,14 @@ fn ipf_only_filter(opldb: &opldb::OplDb, e: &Entry) -> bool {
 pub fn lifter(
     username: String,
     lang: Option<String>,
-    opldb: State<ManagedOplDb>,
+    opldb: CurrentOplDb,
     langinfo: State<ManagedLangInfo>,
     languages: AcceptLanguage,
     cookies: Cookies,
 ) -> Option<Result<Template, Redirect>> {
     let locale = make_locale(&langinfo, lang, languages, &cookies);
 
-    // Disambiguations end with a digit.
-    // Some lifters may have failed to be merged with their disambiguated username.
-    // Therefore, for usernames without a digit, it cannot be assumed that they are
-    // *not* a disambiguation.
-    let is_definitely_disambiguation: bool = username
-        .chars()
-        .last()
-        .map_or(false, |c| c.is_ascii_digit());
-
-    let lifter_ids: Vec<u32> = if is_definitely_disambiguation {
-        if let Some(id) = opldb.get_lifter_id(&username) {
-            vec![id]
-        } else {
-            vec![]
-        }
-    } else {
-        opldb.get_lifters_under_username(&username)
-    };
+    let lifter_ids: Vec<u32> = opldb.get_lifter_ids_for_username(&username);
 
     match lifter_ids.len() {
         // If no LifterID was found, maybe the name just needs to be lowercased.
@@ -286,7 +276,7 @@ pub fn lifter(
 pub fn meet(
     meetpath: PathBuf,
     lang: Option<String>,
-    opldb: State<ManagedOplDb>,
+    opldb: CurrentOplDb,
     langinfo: State<ManagedLangInfo>,
     languages: AcceptLanguage,
     cookies: Cookies,
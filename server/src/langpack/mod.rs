@@ -1,11 +1,13 @@
 //! Internationalization facilities.
 
+use coefficients::PointsFormula;
 use opltypes::*;
 use serde;
 use serde::ser::Serialize;
 use serde_json;
 use strum::IntoEnumIterator;
 
+use std::collections::BTreeMap;
 use std::error::Error;
 use std::fmt;
 use std::fs::File;
@@ -383,11 +385,23 @@ pub struct ColumnTranslations {
     pub bench: String,
     pub deadlift: String,
     pub total: String,
-    pub wilks: String,
-    pub mcculloch: String,
-    pub glossbrenner: String,
-    pub ipfpoints: String,
+    pub ratio: String,
     pub num_lifters: String,
+
+    /// Titles of points columns, keyed by formula slug without dashes,
+    /// like "ipfpoints". Look these up with `points_title()`.
+    #[serde(flatten)]
+    pub points: BTreeMap<String, String>,
+}
+
+impl ColumnTranslations {
+    /// Gets the title of a points column, localized where possible.
+    ///
+    /// Formulas without a translation just use their name.
+    pub fn points_title(&self, formula: &dyn PointsFormula) -> &str {
+        let key = formula.slug().replace('-', "");
+        self.points.get(&key).map_or(formula.name(), String::as_str)
+    }
 }
 
 #[derive(Serialize, Deserialize)]
@@ -401,6 +415,10 @@ pub struct SelectorTranslations {
     pub weightclass: WeightClassSelectorTranslations,
     pub sort: SortSelectorTranslations,
     pub year: YearSelectorTranslations,
+    pub daterange: DateRangeSelectorTranslations,
+    pub location: LocationSelectorTranslations,
+    pub tested: TestedSelectorTranslations,
+    pub performances: PerformancesSelectorTranslations,
     pub sex: SexSelectorTranslations,
     pub event: EventSelectorTranslations,
     pub fed: FedSelectorTranslations,
@@ -414,6 +432,9 @@ pub struct EquipmentSelectorTranslations {
     pub raw_wraps: String,
     pub single: String,
     pub multi: String,
+    pub straps: String,
+    pub equipped: String,
+    pub all: String,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -436,15 +457,24 @@ pub struct SortSelectorTranslations {
     pub by_total: String,
     pub by_ah: String,
     pub by_allometric: String,
+    pub by_dots: String,
     pub by_glossbrenner: String,
     pub by_ipfpoints: String,
+    pub by_goodlift: String,
     pub by_mcculloch: String,
     pub by_nasa: String,
     pub by_reshel: String,
+    pub by_schwartzmalone: String,
     pub by_wilks: String,
+    pub by_wilks2020: String,
+    pub by_squat_ratio: String,
+    pub by_bench_ratio: String,
+    pub by_deadlift_ratio: String,
+    pub by_total_ratio: String,
     pub by_division: String,
     pub weight: String,
     pub points: String,
+    pub ratio: String,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -452,6 +482,31 @@ pub struct YearSelectorTranslations {
     pub all: String,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct DateRangeSelectorTranslations {
+    pub all: String,
+    pub last_12_months: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct LocationSelectorTranslations {
+    pub all_countries: String,
+    pub all_locations: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct TestedSelectorTranslations {
+    pub all: String,
+    pub tested: String,
+    pub untested: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PerformancesSelectorTranslations {
+    pub best_per_lifter: String,
+    pub all_performances: String,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct SexSelectorTranslations {
     pub all: String,
@@ -522,6 +577,18 @@ pub struct LifterPageTranslations {
     pub competition_results: String,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct CalculatorPageTranslations {
+    pub title: String,
+    pub intro: String,
+    pub target: String,
+    pub system: String,
+    pub points: String,
+    pub out_of_reach: String,
+    pub needs_total: String,
+    pub needs_total_up_to: String,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct Translations {
     pub units: UnitsTranslations,
@@ -533,6 +600,7 @@ pub struct Translations {
     pub buttons: ButtonTranslations,
     pub selectors: SelectorTranslations,
     pub lifter_page: LifterPageTranslations,
+    pub calculator_page: CalculatorPageTranslations,
 }
 
 /// Owner struct of all translation state.
@@ -1,6 +1,9 @@
 //! Implementation of server functionality.
 
 // External dependencies.
+extern crate bincode;
+extern crate chrono;
+extern crate crc32fast;
 extern crate csv;
 extern crate itertools;
 extern crate serde;
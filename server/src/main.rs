@@ -24,6 +24,7 @@ mod tests;
 use rocket::fairing::AdHoc;
 use rocket::http::{ContentType, Cookies, Status};
 use rocket::request::{Form, Request};
+use rocket::response::content::Content;
 use rocket::response::{NamedFile, Redirect, Responder, Response};
 use rocket::State;
 use rocket_contrib::templates::Template;
@@ -103,7 +104,7 @@ fn root_apple_touch_icon(encoding: AcceptEncoding) -> Option<StaticFile> {
 fn rankings(
     selections: PathBuf,
     lang: Option<String>,
-    opldb: State<ManagedOplDb>,
+    opldb: CurrentOplDb,
     langinfo: State<ManagedLangInfo>,
     languages: AcceptLanguage,
     cookies: Cookies,
@@ -124,7 +125,7 @@ fn rankings_redirect() -> Redirect {
 fn records(
     selections: Option<PathBuf>,
     lang: Option<String>,
-    opldb: State<ManagedOplDb>,
+    opldb: CurrentOplDb,
     langinfo: State<ManagedLangInfo>,
     languages: AcceptLanguage,
     cookies: Cookies,
@@ -148,7 +149,7 @@ fn records(
 #[get("/records?<lang>")]
 fn records_default(
     lang: Option<String>,
-    opldb: State<ManagedOplDb>,
+    opldb: CurrentOplDb,
     langinfo: State<ManagedLangInfo>,
     languages: AcceptLanguage,
     cookies: Cookies,
@@ -160,31 +161,14 @@ fn records_default(
 fn lifter(
     username: String,
     lang: Option<String>,
-    opldb: State<ManagedOplDb>,
+    opldb: CurrentOplDb,
     langinfo: State<ManagedLangInfo>,
     languages: AcceptLanguage,
     cookies: Cookies,
 ) -> Option<Result<Template, Redirect>> {
     let locale = make_locale(&langinfo, lang, languages, &cookies);
 
-    // Disambiguations end with a digit.
-    // Some lifters may have failed to be merged with their disambiguated username.
-    // Therefore, for usernames without a digit, it cannot be assumed that they are
-    // *not* a disambiguation.
-    let is_definitely_disambiguation: bool = username
-        .chars()
-        .last()
-        .map_or(false, |c| c.is_ascii_digit());
-
-    let lifter_ids: Vec<u32> = if is_definitely_disambiguation {
-        if let Some(id) = opldb.get_lifter_id(&username) {
-            vec![id]
-        } else {
-            vec![]
-        }
-    } else {
-        opldb.get_lifters_under_username(&username)
-    };
+    let lifter_ids: Vec<u32> = opldb.get_lifter_ids_for_username(&username);
 
     match lifter_ids.len() {
         // If no LifterID was found, maybe the name just needs to be lowercased.
@@ -214,11 +198,23 @@ fn lifter(
     }
 }
 
+/// Exports a lifter's entries as CSV.
+#[get("/u/<username>/csv")]
+fn lifter_csv(username: String, opldb: CurrentOplDb) -> Option<Content<String>> {
+    let lifter_ids: Vec<u32> = opldb.get_lifter_ids_for_username(&username);
+    if lifter_ids.is_empty() {
+        return None;
+    }
+
+    let csv = pages::api_v1_lifter::export_csv(&opldb, &lifter_ids).ok()?;
+    Some(Content(ContentType::CSV, csv))
+}
+
 #[get("/mlist/<mselections..>?<lang>")]
 fn meetlist(
     mselections: Option<PathBuf>,
     lang: Option<String>,
-    opldb: State<ManagedOplDb>,
+    opldb: CurrentOplDb,
     langinfo: State<ManagedLangInfo>,
     languages: AcceptLanguage,
     cookies: Cookies,
@@ -235,7 +231,7 @@ fn meetlist(
 #[get("/mlist?<lang>")]
 fn meetlist_default(
     lang: Option<String>,
-    opldb: State<ManagedOplDb>,
+    opldb: CurrentOplDb,
     langinfo: State<ManagedLangInfo>,
     languages: AcceptLanguage,
     cookies: Cookies,
@@ -243,36 +239,56 @@ fn meetlist_default(
     meetlist(None, lang, opldb, langinfo, languages, cookies)
 }
 
-#[get("/m/<meetpath..>?<lang>")]
-fn meet(
-    meetpath: PathBuf,
-    lang: Option<String>,
-    opldb: State<ManagedOplDb>,
-    langinfo: State<ManagedLangInfo>,
-    languages: AcceptLanguage,
-    cookies: Cookies,
-) -> Option<Template> {
+/// Splits a meet URL path into the MeetPath and the sort selection.
+fn parse_meetpath(meetpath: &Path) -> Option<(&str, pages::meet::MeetSortSelection)> {
     let mut meetpath_str: &str = meetpath.to_str()?;
     let mut sort = pages::meet::MeetSortSelection::ByFederationDefault;
 
     // The meetpath may contain an optional sorting directive.
     // If present, detect and remove that component from the path.
-    let component = meetpath.as_path().file_name()?.to_str()?;
+    let component = meetpath.file_name()?.to_str()?;
     if let Ok(sortselection) = component.parse::<pages::meet::MeetSortSelection>() {
         sort = sortselection;
-        meetpath_str = meetpath.as_path().parent()?.to_str()?;
+        meetpath_str = meetpath.parent()?.to_str()?;
     }
 
+    Some((meetpath_str, sort))
+}
+
+#[derive(Responder)]
+enum MeetReturn {
+    Csv(Content<String>),
+    Template(Template),
+}
+
+#[get("/m/<meetpath..>?<lang>")]
+fn meet(
+    meetpath: PathBuf,
+    lang: Option<String>,
+    opldb: CurrentOplDb,
+    langinfo: State<ManagedLangInfo>,
+    languages: AcceptLanguage,
+    cookies: Cookies,
+) -> Option<MeetReturn> {
+    // The results may be exported as CSV by appending "/csv".
+    if meetpath.file_name()?.to_str()? == "csv" {
+        let (meetpath_str, sort) = parse_meetpath(meetpath.parent()?)?;
+        let meet_id = opldb.get_meet_id(meetpath_str)?;
+        let csv = pages::api_v1_meet::export_csv(&opldb, meet_id, sort).ok()?;
+        return Some(MeetReturn::Csv(Content(ContentType::CSV, csv)));
+    }
+
+    let (meetpath_str, sort) = parse_meetpath(&meetpath)?;
     let meet_id = opldb.get_meet_id(meetpath_str)?;
     let locale = make_locale(&langinfo, lang, languages, &cookies);
     let context = pages::meet::Context::new(&opldb, &locale, meet_id, sort);
-    Some(Template::render("meet", &context))
+    Some(MeetReturn::Template(Template::render("meet", &context)))
 }
 
 #[get("/status?<lang>")]
 fn status(
     lang: Option<String>,
-    opldb: State<ManagedOplDb>,
+    opldb: CurrentOplDb,
     langinfo: State<ManagedLangInfo>,
     languages: AcceptLanguage,
     cookies: Cookies,
@@ -306,6 +322,18 @@ fn faq(
     Some(Template::render("faq", &context))
 }
 
+#[get("/tools/calculator?<lang>")]
+fn calculator(
+    lang: Option<String>,
+    langinfo: State<ManagedLangInfo>,
+    languages: AcceptLanguage,
+    cookies: Cookies,
+) -> Option<Template> {
+    let locale = make_locale(&langinfo, lang, languages, &cookies);
+    let context = pages::calculator::Context::new(&locale);
+    Some(Template::render("calculator", &context))
+}
+
 #[get("/contact?<lang>")]
 fn contact(
     lang: Option<String>,
@@ -328,7 +356,7 @@ enum IndexReturn {
 fn index(
     lang: Option<String>,
     fed: Option<String>, // For handling old-style URLs.
-    opldb: State<ManagedOplDb>,
+    opldb: CurrentOplDb,
     langinfo: State<ManagedLangInfo>,
     languages: AcceptLanguage,
     cookies: Cookies,
@@ -354,7 +382,7 @@ fn index(
 fn rankings_api(
     selections: Option<PathBuf>,
     query: Form<RankingsApiQuery>,
-    opldb: State<ManagedOplDb>,
+    opldb: CurrentOplDb,
     langinfo: State<ManagedLangInfo>,
 ) -> Option<JsonString> {
     let default = pages::selection::Selection::default();
@@ -383,7 +411,7 @@ fn rankings_api(
 #[get("/api/rankings?<query..>")]
 fn default_rankings_api<'db>(
     query: Form<RankingsApiQuery>,
-    opldb: State<ManagedOplDb>,
+    opldb: CurrentOplDb,
     langinfo: State<ManagedLangInfo>,
 ) -> Option<JsonString> {
     rankings_api(None, query, opldb, langinfo)
@@ -394,7 +422,7 @@ fn default_rankings_api<'db>(
 fn search_rankings_api<'db>(
     selections: Option<PathBuf>,
     query: Form<SearchRankingsApiQuery>,
-    opldb: State<ManagedOplDb>,
+    opldb: CurrentOplDb,
 ) -> Option<JsonString> {
     let default = pages::selection::Selection::default();
     let selection = match selections {
@@ -411,13 +439,75 @@ fn search_rankings_api<'db>(
 #[get("/api/search/rankings?<query..>")]
 fn default_search_rankings_api(
     query: Form<SearchRankingsApiQuery>,
-    opldb: State<ManagedOplDb>,
+    opldb: CurrentOplDb,
 ) -> Option<JsonString> {
     search_rankings_api(None, query, opldb)
 }
 
+/// API endpoint for searching all lifters by name.
+#[get("/api/search/lifters?<q>")]
+fn search_lifters_api(q: String, opldb: CurrentOplDb) -> Option<JsonString> {
+    let result = pages::api_search::search_lifters(&opldb, &q);
+    Some(JsonString(serde_json::to_string(&result).ok()?))
+}
+
+/// Public, versioned API endpoint for fetching rankings data as JSON.
+#[get("/api/v1/rankings/<selections..>?<query..>")]
+fn rankings_api_v1(
+    selections: Option<PathBuf>,
+    query: Form<ApiV1PagingQuery>,
+    opldb: CurrentOplDb,
+) -> Option<JsonString> {
+    let default = pages::selection::Selection::default();
+    let selection = match selections {
+        None => default,
+        Some(path) => pages::selection::Selection::from_path(&path, &default).ok()?,
+    };
+
+    let start = query.start.unwrap_or(0);
+    let limit = query.limit.unwrap_or(pages::api_v1_rankings::DEFAULT_LIMIT);
+    let page = pages::api_v1_rankings::get_page(&opldb, &selection, start, limit);
+    Some(JsonString(serde_json::to_string(&page).ok()?))
+}
+
+#[get("/api/v1/rankings?<query..>")]
+fn default_rankings_api_v1(
+    query: Form<ApiV1PagingQuery>,
+    opldb: CurrentOplDb,
+) -> Option<JsonString> {
+    rankings_api_v1(None, query, opldb)
+}
+
+/// Public, versioned API endpoint for fetching a lifter's history as JSON.
+#[get("/api/v1/lifters/<username>")]
+fn lifter_api_v1(username: String, opldb: CurrentOplDb) -> Option<JsonString> {
+    let lifter_ids: Vec<u32> = opldb.get_lifter_ids_for_username(&username);
+    if lifter_ids.is_empty() {
+        return None;
+    }
+
+    let lifters = pages::api_v1_lifter::get_lifters(&opldb, &lifter_ids);
+    Some(JsonString(serde_json::to_string(&lifters).ok()?))
+}
+
+/// Public, versioned API endpoint for fetching meet results as JSON.
+#[get("/api/v1/meets/<meetpath..>")]
+fn meet_api_v1(meetpath: PathBuf, opldb: CurrentOplDb) -> Option<JsonString> {
+    let (meetpath_str, sort) = parse_meetpath(&meetpath)?;
+    let meet_id = opldb.get_meet_id(meetpath_str)?;
+    let meet = pages::api_v1_meet::get_meet(&opldb, meet_id, sort);
+    Some(JsonString(serde_json::to_string(&meet).ok()?))
+}
+
+/// Public, versioned API endpoint for calculating every points system at once.
+#[get("/api/v1/calculator?<query..>")]
+fn calculator_api_v1(query: Form<CalculatorApiQuery>) -> Option<JsonString> {
+    let calculation = pages::calculator::calculate(&query.as_query())?;
+    Some(JsonString(serde_json::to_string(&calculation).ok()?))
+}
+
 #[get("/lifters.html?<q>")]
-fn old_lifters(opldb: State<ManagedOplDb>, q: String) -> Option<Redirect> {
+fn old_lifters(opldb: CurrentOplDb, q: String) -> Option<Redirect> {
     let name = &q;
     let id = opldb.get_lifter_id_by_name(name)?;
     let username = &opldb.get_lifter(id).username;
@@ -430,7 +520,7 @@ fn old_meetlist() -> Redirect {
 }
 
 #[get("/meet.html?<m>")]
-fn old_meet(opldb: State<ManagedOplDb>, m: String) -> Option<Redirect> {
+fn old_meet(opldb: CurrentOplDb, m: String) -> Option<Redirect> {
     let meetpath = &m;
     let id = opldb.get_meet_id(meetpath)?;
     let pathstr = &opldb.get_meet(id).path;
@@ -477,6 +567,19 @@ User-agent: SemrushBot-SA
 Disallow: /"#
 }
 
+/// Rebuilds the database from the data files in the background.
+///
+/// Requests continue to use the old database until the new one is ready.
+#[post("/admin/reload")]
+fn admin_reload(_admin: AdminToken, opldb: State<ManagedOplDb>) -> Status {
+    if opldb.reload_in_background(load_opldb) {
+        Status::Accepted
+    } else {
+        // A reload is already in progress.
+        Status::Conflict
+    }
+}
+
 #[catch(404)]
 fn not_found() -> &'static str {
     "404"
@@ -488,21 +591,21 @@ fn internal_error() -> &'static str {
 }
 
 // Tests want to load the data only once.
-#[cfg(not(test))]
-type ManagedOplDb = opldb::OplDb;
-#[cfg(test)]
-type ManagedOplDb = &'static opldb::OplDb;
-
 #[cfg(not(test))]
 type ManagedLangInfo = langpack::LangInfo;
 #[cfg(test)]
 type ManagedLangInfo = &'static langpack::LangInfo;
 
-fn rocket(opldb: ManagedOplDb, langinfo: ManagedLangInfo) -> rocket::Rocket {
+fn rocket(
+    opldb: ManagedOplDb,
+    langinfo: ManagedLangInfo,
+    admin: AdminConfig,
+) -> rocket::Rocket {
     // Initialize the server.
     rocket::ignite()
         .manage(opldb)
         .manage(langinfo)
+        .manage(admin)
         .mount(
             "/",
             routes![
@@ -512,6 +615,7 @@ fn rocket(opldb: ManagedOplDb, langinfo: ManagedLangInfo) -> rocket::Rocket {
                 records,
                 records_default,
                 lifter,
+                lifter_csv,
                 meetlist,
                 meetlist_default,
                 meet,
@@ -521,6 +625,7 @@ fn rocket(opldb: ManagedOplDb, langinfo: ManagedLangInfo) -> rocket::Rocket {
                 status,
                 data,
                 faq,
+                calculator,
                 contact,
                 robots_txt,
             ],
@@ -531,7 +636,13 @@ fn rocket(opldb: ManagedOplDb, langinfo: ManagedLangInfo) -> rocket::Rocket {
                 rankings_api,
                 default_rankings_api,
                 search_rankings_api,
-                default_search_rankings_api
+                default_search_rankings_api,
+                search_lifters_api,
+                rankings_api_v1,
+                default_rankings_api_v1,
+                lifter_api_v1,
+                meet_api_v1,
+                calculator_api_v1,
             ],
         )
         .mount(
@@ -546,6 +657,7 @@ fn rocket(opldb: ManagedOplDb, langinfo: ManagedLangInfo) -> rocket::Rocket {
                 old_contact,
             ],
         )
+        .mount("/", routes![admin_reload])
         .mount(
             dist::openipf::LOCAL_PREFIX,
             routes![
@@ -581,6 +693,33 @@ fn load_langinfo() -> Result<LangInfo, Box<dyn Error>> {
     Ok(langinfo)
 }
 
+/// Loads the OplDb from the files named by the environment.
+///
+/// This is used both at startup and when reloading.
+fn load_opldb() -> Result<opldb::OplDb, Box<dyn Error>> {
+    let lifters_csv = env::var("LIFTERS_CSV").map_err(|_| "LIFTERS_CSV not set")?;
+    let meets_csv = env::var("MEETS_CSV").map_err(|_| "MEETS_CSV not set")?;
+    let entries_csv = env::var("ENTRIES_CSV").map_err(|_| "ENTRIES_CSV not set")?;
+    let weightclasses_csv =
+        env::var("WEIGHTCLASSES_CSV").map_err(|_| "WEIGHTCLASSES_CSV not set")?;
+
+    match env::var("OPLDB_SNAPSHOT") {
+        Ok(snapshot) => opldb::OplDb::from_snapshot_or_csv(
+            &snapshot,
+            &lifters_csv,
+            &meets_csv,
+            &entries_csv,
+            &weightclasses_csv,
+        ),
+        Err(_) => opldb::OplDb::from_csv(
+            &lifters_csv,
+            &meets_csv,
+            &entries_csv,
+            &weightclasses_csv,
+        ),
+    }
+}
+
 fn main() -> Result<(), Box<dyn Error>> {
     // Accept an optional "--set-cwd" argument to manually specify the
     // current working directory. This allows the binary and the data
@@ -598,16 +737,18 @@ fn main() -> Result<(), Box<dyn Error>> {
     env::var("STATICDIR").expect("STATICDIR envvar not set");
 
     // Load the OplDb.
-    let lifters_csv = env::var("LIFTERS_CSV").expect("LIFTERS_CSV not set");
-    let meets_csv = env::var("MEETS_CSV").expect("MEETS_CSV not set");
-    let entries_csv = env::var("ENTRIES_CSV").expect("ENTRIES_CSV not set");
-    let opldb = opldb::OplDb::from_csv(&lifters_csv, &meets_csv, &entries_csv)?;
+    let opldb = load_opldb()?;
     println!("OplDb loaded in {}MB.", opldb.size_bytes() / 1024 / 1024);
 
     #[allow(unused_variables)]
     let langinfo = load_langinfo()?;
 
     #[cfg(not(test))]
-    rocket(opldb, langinfo).launch();
+    rocket(
+        opldb::SharedOplDb::new(opldb),
+        langinfo,
+        AdminConfig::from_env(),
+    )
+    .launch();
     Ok(())
 }
@@ -1,14 +1,15 @@
 //! Shared algorithms that operate on StaticCache data.
 
-use coefficients;
 use opltypes::*;
 use std::cmp;
+use std::sync::Arc;
 
 use crate::opldb::static_cache::NonSortedNonUnique;
 use crate::opldb::static_cache::PossiblyOwnedNonSortedNonUnique;
-use crate::opldb::static_cache::PossiblyOwnedSortedUnique;
+use crate::opldb::static_cache::PossiblyOwnedRankedEntries;
+use crate::opldb::static_cache::RankedEntries;
 use crate::opldb::static_cache::SortedUnique;
-use crate::opldb::{Entry, Meet, OplDb};
+use crate::opldb::{CachedRankings, Entry, Meet, OplDb, PointsSource};
 use crate::pages::selection::*;
 
 /// Whether an `Entry` should be part of `BySquat` rankings and records.
@@ -36,32 +37,35 @@ pub fn filter_total(entry: &Entry) -> bool {
     entry.totalkg > WeightKg::from_i32(0)
 }
 
-/// Whether an `Entry` should be part of `ByMcCulloch` rankings and records.
+/// Whether an `Entry` should be part of rankings and records by points.
 #[inline]
-pub fn filter_mcculloch(entry: &Entry) -> bool {
-    // McCulloch points are defined to be zero if DQ.
-    entry.mcculloch > Points::from_i32(0)
+pub fn filter_points(source: PointsSource, entry: &Entry) -> bool {
+    // Points are defined to be zero if DQ, or if the formula doesn't apply.
+    entry.points_from(source) > Points::from_i32(0)
 }
 
-/// Whether an `Entry` should be part of `ByWilks` rankings and records.
+/// Whether an `Entry` should be part of `BySquatRatio` rankings.
 #[inline]
-pub fn filter_wilks(entry: &Entry) -> bool {
-    // Wilks is defined to be zero if DQ.
-    entry.wilks > Points::from_i32(0)
+pub fn filter_squat_ratio(entry: &Entry) -> bool {
+    filter_squat(entry) && entry.bodyweightkg > WeightKg::from_i32(0)
 }
 
-/// Whether an `Entry` should be part of `ByGlossbrenner` rankings and records.
+/// Whether an `Entry` should be part of `ByBenchRatio` rankings.
 #[inline]
-pub fn filter_glossbrenner(entry: &Entry) -> bool {
-    // Glossbrenner is defined to be zero if DQ.
-    entry.glossbrenner > Points::from_i32(0)
+pub fn filter_bench_ratio(entry: &Entry) -> bool {
+    filter_bench(entry) && entry.bodyweightkg > WeightKg::from_i32(0)
 }
 
-/// Whether an `Entry` should be part of `ByIPFPoints` rankings and records.
+/// Whether an `Entry` should be part of `ByDeadliftRatio` rankings.
 #[inline]
-pub fn filter_ipfpoints(entry: &Entry) -> bool {
-    // IPF Points are defined to be zero if DQ.
-    entry.ipfpoints > Points::from_i32(0)
+pub fn filter_deadlift_ratio(entry: &Entry) -> bool {
+    filter_deadlift(entry) && entry.bodyweightkg > WeightKg::from_i32(0)
+}
+
+/// Whether an `Entry` should be part of `ByTotalRatio` rankings.
+#[inline]
+pub fn filter_total_ratio(entry: &Entry) -> bool {
+    filter_total(entry) && entry.bodyweightkg > WeightKg::from_i32(0)
 }
 
 /// Defines an `Ordering` of Entries by Squat.
@@ -138,12 +142,18 @@ pub fn cmp_total(meets: &[Meet], a: &Entry, b: &Entry) -> cmp::Ordering {
         .then(a.bodyweightkg.cmp(&b.bodyweightkg))
 }
 
-/// Defines an `Ordering` of Entries by McCulloch points.
+/// Defines an `Ordering` of Entries by the ratio of a lift to bodyweight.
 #[inline]
-pub fn cmp_mcculloch(meets: &[Meet], a: &Entry, b: &Entry) -> cmp::Ordering {
-    // First sort by McCulloch, higher first.
-    a.mcculloch
-        .cmp(&b.mcculloch)
+fn cmp_ratio(
+    meets: &[Meet],
+    a: &Entry,
+    b: &Entry,
+    a_ratio: Points,
+    b_ratio: Points,
+) -> cmp::Ordering {
+    // First sort by the ratio, higher first.
+    a_ratio
+        .cmp(&b_ratio)
         .reverse()
         // If equal, sort by Date, earlier first.
         .then(
@@ -151,122 +161,58 @@ pub fn cmp_mcculloch(meets: &[Meet], a: &Entry, b: &Entry) -> cmp::Ordering {
                 .date
                 .cmp(&meets[b.meet_id as usize].date),
         )
-        // If that's equal too, sort by Total, highest first.
+        // If equal, sort by Bodyweight, lower first.
+        .then(a.bodyweightkg.cmp(&b.bodyweightkg))
+        // If for the same lifter on the same day, prefer Entry with largest Total.
         .then(a.totalkg.cmp(&b.totalkg).reverse())
 }
 
-/// Defines an `Ordering` of Entries by Wilks.
+/// Defines an `Ordering` of Entries by Squat divided by Bodyweight.
 #[inline]
-pub fn cmp_wilks(meets: &[Meet], a: &Entry, b: &Entry) -> cmp::Ordering {
-    // First sort by Wilks, higher first.
-    a.wilks
-        .cmp(&b.wilks)
-        .reverse()
-        // If equal, sort by Date, earlier first.
-        .then(
-            meets[a.meet_id as usize]
-                .date
-                .cmp(&meets[b.meet_id as usize].date),
-        )
-        // If that's equal too, sort by Total, highest first.
-        .then(a.totalkg.cmp(&b.totalkg).reverse())
+pub fn cmp_squat_ratio(meets: &[Meet], a: &Entry, b: &Entry) -> cmp::Ordering {
+    let a_ratio = a.bodyweight_ratio(a.highest_squatkg());
+    let b_ratio = b.bodyweight_ratio(b.highest_squatkg());
+    cmp_ratio(meets, a, b, a_ratio, b_ratio)
 }
 
-/// Defines an `Ordering` of Entries by Glossbrenner.
+/// Defines an `Ordering` of Entries by Bench divided by Bodyweight.
 #[inline]
-pub fn cmp_glossbrenner(meets: &[Meet], a: &Entry, b: &Entry) -> cmp::Ordering {
-    // First sort by Glossbrenner, higher first.
-    a.glossbrenner
-        .cmp(&b.glossbrenner)
-        .reverse()
-        // If equal, sort by Date, earlier first.
-        .then(
-            meets[a.meet_id as usize]
-                .date
-                .cmp(&meets[b.meet_id as usize].date),
-        )
-        // If that's equal too, sort by Total, highest first.
-        .then(a.totalkg.cmp(&b.totalkg).reverse())
-}
-
-/// Defines an `Ordering` of Entries by IPF Points.
-#[inline]
-pub fn cmp_ipfpoints(meets: &[Meet], a: &Entry, b: &Entry) -> cmp::Ordering {
-    // First sort by IPF Points, higher first.
-    a.ipfpoints
-        .cmp(&b.ipfpoints)
-        .reverse()
-        // If equal, sort by Date, earlier first.
-        .then(
-            meets[a.meet_id as usize]
-                .date
-                .cmp(&meets[b.meet_id as usize].date),
-        )
-        // If that's equal too, sort by Total, highest first.
-        .then(a.totalkg.cmp(&b.totalkg).reverse())
+pub fn cmp_bench_ratio(meets: &[Meet], a: &Entry, b: &Entry) -> cmp::Ordering {
+    let a_ratio = a.bodyweight_ratio(a.highest_benchkg());
+    let b_ratio = b.bodyweight_ratio(b.highest_benchkg());
+    cmp_ratio(meets, a, b, a_ratio, b_ratio)
 }
 
-/// Defines an `Ordering` of Entries by NASA Points.
-///
-/// Because NASA points aren't stored on the Entry, they are recalculated
-/// each comparison. The computation is not particularly expensive,
-/// but does involve floating-point division.
+/// Defines an `Ordering` of Entries by Deadlift divided by Bodyweight.
 #[inline]
-pub fn cmp_nasa(meets: &[Meet], a: &Entry, b: &Entry) -> cmp::Ordering {
-    let a_points = coefficients::nasa(a.bodyweightkg, a.totalkg);
-    let b_points = coefficients::nasa(b.bodyweightkg, b.totalkg);
-
-    // First sort by NASA Points, higher first.
-    a_points
-        .cmp(&b_points)
-        .reverse()
-        // If equal, sort by Date, earlier first.
-        .then(
-            meets[a.meet_id as usize]
-                .date
-                .cmp(&meets[b.meet_id as usize].date),
-        )
-        // If that's equal too, sort by Total, highest first.
-        .then(a.totalkg.cmp(&b.totalkg).reverse())
+pub fn cmp_deadlift_ratio(meets: &[Meet], a: &Entry, b: &Entry) -> cmp::Ordering {
+    let a_ratio = a.bodyweight_ratio(a.highest_deadliftkg());
+    let b_ratio = b.bodyweight_ratio(b.highest_deadliftkg());
+    cmp_ratio(meets, a, b, a_ratio, b_ratio)
 }
 
-/// Defines an `Ordering` of Entries by Reshel points.
-///
-/// Because Reshel points aren't stored on the Entry, they are recalculated
-/// each comparison. The computation is not particularly expensive,
-/// but does involve powf().
+/// Defines an `Ordering` of Entries by Total divided by Bodyweight.
 #[inline]
-pub fn cmp_reshel(meets: &[Meet], a: &Entry, b: &Entry) -> cmp::Ordering {
-    let a_points = coefficients::reshel(a.sex, a.bodyweightkg, a.totalkg);
-    let b_points = coefficients::reshel(b.sex, b.bodyweightkg, b.totalkg);
-
-    // First sort by Reshel points, higher first.
-    a_points
-        .cmp(&b_points)
-        .reverse()
-        // If equal, sort by Date, earlier first.
-        .then(
-            meets[a.meet_id as usize]
-                .date
-                .cmp(&meets[b.meet_id as usize].date),
-        )
-        // If that's equal too, sort by Total, highest first.
-        .then(a.totalkg.cmp(&b.totalkg).reverse())
+pub fn cmp_total_ratio(meets: &[Meet], a: &Entry, b: &Entry) -> cmp::Ordering {
+    let a_ratio = a.bodyweight_ratio(a.totalkg);
+    let b_ratio = b.bodyweight_ratio(b.totalkg);
+    cmp_ratio(meets, a, b, a_ratio, b_ratio)
 }
 
-/// Defines an `Ordering` of Entries by AH (Haleczko) points.
+/// Defines an `Ordering` of Entries by points from the given source.
 ///
-/// Because AH points aren't stored on the Entry, they are recalculated
-/// each comparison. The computation is not particularly expensive,
-/// but does involve powf().
+/// Points that aren't stored on the Entry are recalculated each comparison.
+/// The computation is not particularly expensive, but usually involves powf().
 #[inline]
-pub fn cmp_ah(meets: &[Meet], a: &Entry, b: &Entry) -> cmp::Ordering {
-    let a_points = coefficients::ah(a.sex, a.bodyweightkg, a.totalkg);
-    let b_points = coefficients::ah(b.sex, b.bodyweightkg, b.totalkg);
-
-    // First sort by AH points, higher first.
-    a_points
-        .cmp(&b_points)
+pub fn cmp_points(
+    source: PointsSource,
+    meets: &[Meet],
+    a: &Entry,
+    b: &Entry,
+) -> cmp::Ordering {
+    // First sort by points, higher first.
+    a.points_from(source)
+        .cmp(&b.points_from(source))
         .reverse()
         // If equal, sort by Date, earlier first.
         .then(
@@ -285,180 +231,131 @@ pub fn get_entry_indices_for<'db>(
 ) -> PossiblyOwnedNonSortedNonUnique<'db> {
     let cache = opldb.get_static_cache();
 
-    // Use the NonSortedNonUnique cached data.
-    let equipment: &NonSortedNonUnique = match selection.equipment {
-        EquipmentSelection::Raw => &cache.log_linear_time.raw,
-        EquipmentSelection::Wraps => &cache.log_linear_time.wraps,
-        EquipmentSelection::RawAndWraps => &cache.log_linear_time.raw_wraps,
-        EquipmentSelection::Single => &cache.log_linear_time.single,
-        EquipmentSelection::Multi => &cache.log_linear_time.multi,
-    };
-    let mut cur = PossiblyOwnedNonSortedNonUnique::Borrowed(equipment);
+    // Selections that only filter by equipment are already cached.
+    if selection.sex == SexSelection::AllSexes
+        && selection.year == YearSelection::AllYears
+        && selection.daterange == DateRangeSelection::AllDates
+        && selection.country == LifterCountrySelection::AllCountries
+        && selection.meetcountry == MeetCountrySelection::AllCountries
+        && selection.meetstate == MeetStateSelection::AllStates
+        && selection.tested == TestedSelection::AllEntries
+        && selection.federation == FederationSelection::AllFederations
+        && selection.ageclass == AgeClassSelection::AllAges
+        && selection.event == EventSelection::AllEvents
+        && selection.weightclasses == WeightClassSelection::AllClasses
+    {
+        let equipment: &NonSortedNonUnique = match selection.equipment {
+            EquipmentSelection::Raw => &cache.log_linear_time.raw,
+            EquipmentSelection::Wraps => &cache.log_linear_time.wraps,
+            EquipmentSelection::RawAndWraps => &cache.log_linear_time.raw_wraps,
+            EquipmentSelection::Single => &cache.log_linear_time.single,
+            EquipmentSelection::Multi => &cache.log_linear_time.multi,
+            EquipmentSelection::Straps => &cache.log_linear_time.straps,
+            EquipmentSelection::Equipped => &cache.log_linear_time.equipped,
+            EquipmentSelection::AllEquipment => &cache.log_linear_time.all,
+        };
+        return PossiblyOwnedNonSortedNonUnique::Borrowed(equipment);
+    }
 
-    // Apply the Sex filter.
-    cur = match selection.sex {
-        SexSelection::AllSexes => cur,
-        SexSelection::Men => PossiblyOwnedNonSortedNonUnique::Owned(
-            cur.intersect(&cache.log_linear_time.male),
-        ),
-        SexSelection::Women => PossiblyOwnedNonSortedNonUnique::Owned(
-            cur.intersect(&cache.log_linear_time.female),
-        ),
-    };
+    // Otherwise, intersect the column indexes.
+    let columns = &cache.linear_time;
+    let mut set = columns
+        .equipment
+        .select(|e| selection.equipment.contains(e));
 
-    // Apply the Year filter.
-    cur = match selection.year {
-        YearSelection::AllYears => cur,
-        YearSelection::Year2019 => PossiblyOwnedNonSortedNonUnique::Owned(
-            cur.intersect(&cache.log_linear_time.year2019),
-        ),
-        YearSelection::Year2018 => PossiblyOwnedNonSortedNonUnique::Owned(
-            cur.intersect(&cache.log_linear_time.year2018),
-        ),
-        YearSelection::Year2017 => PossiblyOwnedNonSortedNonUnique::Owned(
-            cur.intersect(&cache.log_linear_time.year2017),
-        ),
-        YearSelection::Year2016 => PossiblyOwnedNonSortedNonUnique::Owned(
-            cur.intersect(&cache.log_linear_time.year2016),
-        ),
-        YearSelection::Year2015 => PossiblyOwnedNonSortedNonUnique::Owned(
-            cur.intersect(&cache.log_linear_time.year2015),
-        ),
-        YearSelection::Year2014 => PossiblyOwnedNonSortedNonUnique::Owned(
-            cur.intersect(&cache.log_linear_time.year2014),
-        ),
-        _ => {
-            let year = selection.year.as_u32().unwrap(); // Safe if not AllYears.
-            let filter = NonSortedNonUnique(
-                cur.0
-                    .iter()
-                    .filter_map(|&i| {
-                        match opldb.get_meet(opldb.get_entry(i).meet_id).date.year()
-                            == year
-                        {
-                            true => Some(i),
-                            false => None,
-                        }
-                    })
-                    .collect(),
-            );
-            PossiblyOwnedNonSortedNonUnique::Owned(filter)
-        }
-    };
+    match selection.sex {
+        SexSelection::AllSexes => (),
+        SexSelection::Men => set.intersect_with(&columns.sex.select_one(Sex::M)),
+        SexSelection::Women => set.intersect_with(&columns.sex.select_one(Sex::F)),
+    }
 
-    // Filter by federation manually.
-    if selection.federation != FederationSelection::AllFederations {
-        if let FederationSelection::One(fed) = selection.federation {
-            let filter = NonSortedNonUnique(
-                cur.0
-                    .iter()
-                    .filter_map(|&i| {
-                        match opldb.get_meet(opldb.get_entry(i).meet_id).federation == fed
-                        {
-                            true => Some(i),
-                            false => None,
-                        }
-                    })
-                    .collect(),
-            );
-            cur = PossiblyOwnedNonSortedNonUnique::Owned(filter);
-        } else if let FederationSelection::Meta(metafed) = selection.federation {
-            let meets = opldb.get_meets();
-            let filter = NonSortedNonUnique(
-                cur.0
-                    .iter()
-                    .filter_map(|&i| match metafed.contains(opldb.get_entry(i), meets) {
-                        true => Some(i),
-                        false => None,
-                    })
-                    .collect(),
-            );
-            cur = PossiblyOwnedNonSortedNonUnique::Owned(filter);
+    if let Some(year) = selection.year.as_u32() {
+        set.intersect_with(&columns.year.select_one(year));
+    }
+
+    // Only years are indexed, so narrow the date range down to whole years here,
+    // and check the exact dates below.
+    let date_bounds = selection.daterange.bounds();
+    if let Some((first, last)) = date_bounds {
+        let (first_year, last_year) = (first.year(), last.year());
+        set.intersect_with(&columns.year.select(|y| first_year <= y && y <= last_year));
+    }
+
+    match selection.federation {
+        FederationSelection::AllFederations => (),
+        FederationSelection::One(fed) => {
+            set.intersect_with(&columns.federation.select_one(fed));
+        }
+        FederationSelection::Meta(meta) => {
+            set.intersect_with(&columns.metafederation.select_one(meta));
+        }
+        FederationSelection::Union(union) => {
+            let mut any = columns.federation.select(|f| union.contains_federation(f));
+            for meta in union.metafederations() {
+                any.union_with(&columns.metafederation.select_one(meta));
+            }
+            set.intersect_with(&any);
         }
     }
 
-    // Filter by AgeClass manually.
-    if selection.ageclass != AgeClassSelection::AllAges {
-        let filter = NonSortedNonUnique(
-            cur.0
-                .iter()
-                .filter_map(|&i| {
-                    let class = opldb.get_entry(i).ageclass;
-                    let matches: bool = match selection.ageclass {
-                        AgeClassSelection::AllAges => true,
-                        AgeClassSelection::Youth512 => class == AgeClass::Class5_12,
-                        AgeClassSelection::Teenage1315 => class == AgeClass::Class13_15,
-                        AgeClassSelection::Teenage1617 => class == AgeClass::Class16_17,
-                        AgeClassSelection::Teenage1819 => class == AgeClass::Class18_19,
-                        AgeClassSelection::Juniors2023 => class == AgeClass::Class20_23,
-                        AgeClassSelection::Seniors2434 => class == AgeClass::Class24_34,
-                        AgeClassSelection::Submasters3539 => {
-                            class == AgeClass::Class35_39
-                        }
-                        AgeClassSelection::Masters4049 => {
-                            class == AgeClass::Class40_44 || class == AgeClass::Class45_49
-                        }
-                        AgeClassSelection::Masters5059 => {
-                            class == AgeClass::Class50_54 || class == AgeClass::Class55_59
-                        }
-                        AgeClassSelection::Masters6069 => {
-                            class == AgeClass::Class60_64 || class == AgeClass::Class65_69
-                        }
-                        AgeClassSelection::Masters7079 => {
-                            class == AgeClass::Class70_74 || class == AgeClass::Class75_79
-                        }
-                        AgeClassSelection::Masters4044 => class == AgeClass::Class40_44,
-                        AgeClassSelection::Masters4549 => class == AgeClass::Class45_49,
-                        AgeClassSelection::Masters5054 => class == AgeClass::Class50_54,
-                        AgeClassSelection::Masters5559 => class == AgeClass::Class55_59,
-                        AgeClassSelection::Masters6064 => class == AgeClass::Class60_64,
-                        AgeClassSelection::Masters6569 => class == AgeClass::Class65_69,
-                        AgeClassSelection::Masters7074 => class == AgeClass::Class70_74,
-                        AgeClassSelection::Masters7579 => class == AgeClass::Class75_79,
-                        AgeClassSelection::MastersOver80 => {
-                            class == AgeClass::Class80_999
-                        }
-                    };
-                    if matches {
-                        Some(i)
-                    } else {
-                        None
-                    }
-                })
-                .collect(),
-        );
+    if let LifterCountrySelection::One(country) = selection.country {
+        set.intersect_with(&columns.lifter_country.select_one(Some(country)));
+    }
 
-        cur = PossiblyOwnedNonSortedNonUnique::Owned(filter);
+    if let MeetCountrySelection::One(country) = selection.meetcountry {
+        set.intersect_with(&columns.meet_country.select_one(country));
+    }
+
+    // States aren't indexed, so narrow down to the country here,
+    // and check the exact state below.
+    if let MeetStateSelection::One(state) = selection.meetstate {
+        set.intersect_with(&columns.meet_country.select_one(state.to_country()));
+    }
+
+    if selection.tested != TestedSelection::AllEntries {
+        set.intersect_with(&columns.tested.select(|t| selection.tested.contains(t)));
+    }
+
+    if let Some(class) = selection.ageclass.birthyearclass() {
+        set.intersect_with(&columns.birthyearclass.select_one(class));
+    } else if selection.ageclass != AgeClassSelection::AllAges {
+        set.intersect_with(&columns.ageclass.select(|c| selection.ageclass.contains(c)));
     }
 
-    // Filter by event manually.
     if selection.event != EventSelection::AllEvents {
+        set.intersect_with(&columns.event.select(|e| selection.event.contains(e)));
+    }
+
+    let mut cur = match date_bounds {
+        None => PossiblyOwnedNonSortedNonUnique::Owned(set.to_indices()),
+        Some((first, last)) => {
+            let date_of = |i: u32| opldb.get_meet(opldb.get_entry(i).meet_id).date;
+            PossiblyOwnedNonSortedNonUnique::Owned(NonSortedNonUnique(
+                set.iter()
+                    .filter(|&i| first <= date_of(i) && date_of(i) <= last)
+                    .collect(),
+            ))
+        }
+    };
+
+    // Filter by meet state manually.
+    if let MeetStateSelection::One(state) = selection.meetstate {
+        let code = state.to_string();
         let filter = NonSortedNonUnique(
             cur.0
                 .iter()
-                .filter_map(|&i| {
-                    let ev = opldb.get_entry(i).event;
-                    let matches: bool = match selection.event {
-                        EventSelection::AllEvents => true,
-                        EventSelection::FullPower => ev.is_full_power(),
-                        EventSelection::PushPull => ev.is_push_pull(),
-                        EventSelection::SquatOnly => ev.is_squat_only(),
-                        EventSelection::BenchOnly => ev.is_bench_only(),
-                        EventSelection::DeadliftOnly => ev.is_deadlift_only(),
-                    };
-                    if matches {
-                        Some(i)
-                    } else {
-                        None
-                    }
+                .filter(|&&i| {
+                    let meet = opldb.get_meet(opldb.get_entry(i).meet_id);
+                    meet.state.as_ref().map_or(false, |s| *s == code)
                 })
+                .cloned()
                 .collect(),
         );
-
         cur = PossiblyOwnedNonSortedNonUnique::Owned(filter);
     }
 
     // Filter by weight class manually.
+    // Classes are ranges of bodyweight, so they can't be indexed by value.
     if selection.weightclasses != WeightClassSelection::AllClasses {
         let (lower, upper) = selection.weightclasses.to_bounds();
 
@@ -493,6 +390,32 @@ pub fn get_entry_indices_for<'db>(
     cur
 }
 
+/// Sorts the entries for a selection, by the given comparator.
+///
+/// Unless every performance was selected, only the best entry
+/// for each lifter is kept.
+fn sort_entries<F, G>(
+    cur: &NonSortedNonUnique,
+    selection: &Selection,
+    entries: &[Entry],
+    meets: &[Meet],
+    compare: F,
+    belongs: G,
+) -> RankedEntries
+where
+    F: Fn(&[Meet], &Entry, &Entry) -> cmp::Ordering,
+    G: Fn(&Entry) -> bool,
+{
+    match selection.performances {
+        PerformancesSelection::BestPerLifter => RankedEntries::BestPerLifter(
+            cur.sort_and_unique_by(entries, meets, compare, belongs),
+        ),
+        PerformancesSelection::AllPerformances => {
+            RankedEntries::AllPerformances(cur.sort_by(entries, meets, compare, belongs))
+        }
+    }
+}
+
 /// Gets a full sorted list for the given selection.
 ///
 /// In almost every case it's not necessary to generate the full list,
@@ -500,56 +423,72 @@ pub fn get_entry_indices_for<'db>(
 pub fn get_full_sorted_uniqued<'db>(
     selection: &Selection,
     opldb: &'db OplDb,
-) -> PossiblyOwnedSortedUnique<'db> {
+) -> PossiblyOwnedRankedEntries<'db> {
     let cache = opldb.get_static_cache();
 
     // First, try to use the constant-time cache.
-    if selection.federation == FederationSelection::AllFederations
-        && selection.weightclasses == WeightClassSelection::AllClasses
-        && selection.year == YearSelection::AllYears
-        && selection.ageclass == AgeClassSelection::AllAges
-        && selection.event == EventSelection::AllEvents
-    {
-        let by_sort = match selection.sort {
-            SortSelection::BySquat => &cache.constant_time.squat,
-            SortSelection::ByBench => &cache.constant_time.bench,
-            SortSelection::ByDeadlift => &cache.constant_time.deadlift,
-            SortSelection::ByTotal => &cache.constant_time.total,
-            SortSelection::ByGlossbrenner => &cache.constant_time.glossbrenner,
-            SortSelection::ByIPFPoints => &cache.constant_time.ipfpoints,
-            SortSelection::ByMcCulloch => &cache.constant_time.mcculloch,
-            SortSelection::ByWilks => &cache.constant_time.wilks,
-        };
-
-        let sorted_uniqued = match selection.equipment {
-            EquipmentSelection::Raw => &by_sort.raw,
-            EquipmentSelection::Wraps => &by_sort.wraps,
-            EquipmentSelection::RawAndWraps => &by_sort.raw_wraps,
-            EquipmentSelection::Single => &by_sort.single,
-            EquipmentSelection::Multi => &by_sort.multi,
-        };
+    let constant_time = match selection.sort {
+        SortSelection::BySquat => Some(&cache.constant_time.squat),
+        SortSelection::ByBench => Some(&cache.constant_time.bench),
+        SortSelection::ByDeadlift => Some(&cache.constant_time.deadlift),
+        SortSelection::ByTotal => Some(&cache.constant_time.total),
+        SortSelection::ByPoints(system) => cache.constant_time.by_points(system),
+        // Ratios aren't precomputed.
+        SortSelection::BySquatRatio
+        | SortSelection::ByBenchRatio
+        | SortSelection::ByDeadliftRatio
+        | SortSelection::ByTotalRatio => None,
+    };
 
-        // Since each lifter is only one sex, sex selections
-        // can just be an O(n) filter.
-        if selection.sex != SexSelection::AllSexes {
-            return PossiblyOwnedSortedUnique::Owned(SortedUnique(
-                sorted_uniqued
-                    .0
-                    .iter()
-                    .filter_map(|&n| {
-                        let sex = opldb.get_entry(n).sex;
-                        match (selection.sex == SexSelection::Men && sex == Sex::M)
-                            || (selection.sex == SexSelection::Women && sex == Sex::F)
-                        {
-                            true => Some(n),
-                            false => None,
-                        }
-                    })
-                    .collect(),
-            ));
+    if let Some(by_sort) = constant_time {
+        if selection.federation == FederationSelection::AllFederations
+            && selection.weightclasses == WeightClassSelection::AllClasses
+            && selection.year == YearSelection::AllYears
+            && selection.daterange == DateRangeSelection::AllDates
+            && selection.country == LifterCountrySelection::AllCountries
+            && selection.meetcountry == MeetCountrySelection::AllCountries
+            && selection.meetstate == MeetStateSelection::AllStates
+            && selection.tested == TestedSelection::AllEntries
+            && selection.ageclass == AgeClassSelection::AllAges
+            && selection.event == EventSelection::AllEvents
+            && selection.performances == PerformancesSelection::BestPerLifter
+        {
+            let sorted_uniqued = match selection.equipment {
+                EquipmentSelection::Raw => &by_sort.raw,
+                EquipmentSelection::Wraps => &by_sort.wraps,
+                EquipmentSelection::RawAndWraps => &by_sort.raw_wraps,
+                EquipmentSelection::Single => &by_sort.single,
+                EquipmentSelection::Multi => &by_sort.multi,
+                EquipmentSelection::Straps => &by_sort.straps,
+                EquipmentSelection::Equipped => &by_sort.equipped,
+                EquipmentSelection::AllEquipment => &by_sort.all,
+            };
+
+            // Since each lifter is only one sex, sex selections
+            // can just be an O(n) filter.
+            if selection.sex != SexSelection::AllSexes {
+                let filtered = SortedUnique(
+                    sorted_uniqued
+                        .0
+                        .iter()
+                        .filter_map(|&n| {
+                            let sex = opldb.get_entry(n).sex;
+                            match (selection.sex == SexSelection::Men && sex == Sex::M)
+                                || (selection.sex == SexSelection::Women && sex == Sex::F)
+                            {
+                                true => Some(n),
+                                false => None,
+                            }
+                        })
+                        .collect(),
+                );
+                return PossiblyOwnedRankedEntries::Owned(RankedEntries::BestPerLifter(
+                    filtered,
+                ));
+            }
+
+            return PossiblyOwnedRankedEntries::Borrowed(sorted_uniqued);
         }
-
-        return PossiblyOwnedSortedUnique::Borrowed(sorted_uniqued);
     }
 
     // If the ConstantTime cache fails, use the NonSortedNonUnique cache data.
@@ -559,33 +498,77 @@ pub fn get_full_sorted_uniqued<'db>(
     let meets = opldb.get_meets();
 
     // TODO: Common out sort code with ConstantTimeCache::new()
-    PossiblyOwnedSortedUnique::Owned(match selection.sort {
+    PossiblyOwnedRankedEntries::Owned(match selection.sort {
         SortSelection::BySquat => {
-            cur.sort_and_unique_by(&entries, &meets, cmp_squat, filter_squat)
+            sort_entries(&cur, selection, &entries, &meets, cmp_squat, filter_squat)
         }
         SortSelection::ByBench => {
-            cur.sort_and_unique_by(&entries, &meets, cmp_bench, filter_bench)
-        }
-        SortSelection::ByDeadlift => {
-            cur.sort_and_unique_by(&entries, &meets, cmp_deadlift, filter_deadlift)
+            sort_entries(&cur, selection, &entries, &meets, cmp_bench, filter_bench)
         }
-        SortSelection::ByTotal => {
-            cur.sort_and_unique_by(&entries, &meets, cmp_total, filter_total)
-        }
-        SortSelection::ByGlossbrenner => cur.sort_and_unique_by(
+        SortSelection::ByDeadlift => sort_entries(
+            &cur,
+            selection,
             &entries,
             &meets,
-            cmp_glossbrenner,
-            filter_glossbrenner,
+            cmp_deadlift,
+            filter_deadlift,
         ),
-        SortSelection::ByIPFPoints => {
-            cur.sort_and_unique_by(&entries, &meets, cmp_ipfpoints, filter_ipfpoints)
-        }
-        SortSelection::ByMcCulloch => {
-            cur.sort_and_unique_by(&entries, &meets, cmp_mcculloch, filter_mcculloch)
+        SortSelection::ByTotal => {
+            sort_entries(&cur, selection, &entries, &meets, cmp_total, filter_total)
         }
-        SortSelection::ByWilks => {
-            cur.sort_and_unique_by(&entries, &meets, cmp_wilks, filter_wilks)
+        SortSelection::ByPoints(system) => {
+            let source = PointsSource::of(system);
+            sort_entries(
+                &cur,
+                selection,
+                &entries,
+                &meets,
+                |m, a, b| cmp_points(source, m, a, b),
+                |e| filter_points(source, e),
+            )
         }
+        SortSelection::BySquatRatio => sort_entries(
+            &cur,
+            selection,
+            &entries,
+            &meets,
+            cmp_squat_ratio,
+            filter_squat_ratio,
+        ),
+        SortSelection::ByBenchRatio => sort_entries(
+            &cur,
+            selection,
+            &entries,
+            &meets,
+            cmp_bench_ratio,
+            filter_bench_ratio,
+        ),
+        SortSelection::ByDeadliftRatio => sort_entries(
+            &cur,
+            selection,
+            &entries,
+            &meets,
+            cmp_deadlift_ratio,
+            filter_deadlift_ratio,
+        ),
+        SortSelection::ByTotalRatio => sort_entries(
+            &cur,
+            selection,
+            &entries,
+            &meets,
+            cmp_total_ratio,
+            filter_total_ratio,
+        ),
     })
 }
+
+/// Gets the rankings for a selection, reusing recently computed results.
+///
+/// Prefer this to `get_full_sorted_uniqued()` when the same rankings are
+/// likely to be requested repeatedly, such as when paging.
+pub fn get_cached_sorted_uniqued(
+    selection: &Selection,
+    opldb: &OplDb,
+) -> Arc<CachedRankings> {
+    opldb.get_rankings_cache().get(opldb, selection)
+}
@@ -0,0 +1,303 @@
+//! Per-value indexes of entries, for resolving selections without
+//! scanning the entries themselves.
+//!
+//! Each column of interest, like the federation or the year, is stored as
+//! a set of entry indices for each value that occurs in the data. A selection
+//! is resolved by taking the union of the sets for the values it allows
+//! in each column, and then intersecting across columns 64 entries at a time.
+
+use crate::opldb::Entry;
+use crate::opldb::NonSortedNonUnique;
+
+/// A set of entry indices, stored as one bit per entry.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Bitset {
+    /// The number of entries the set ranges over.
+    len: u32,
+    words: Vec<u64>,
+}
+
+impl Bitset {
+    /// Creates a set containing no entries.
+    pub fn empty(len: usize) -> Bitset {
+        Bitset {
+            len: len as u32,
+            words: vec![0; (len + 63) / 64],
+        }
+    }
+
+    /// Creates a set containing every entry.
+    pub fn full(len: usize) -> Bitset {
+        let mut set = Bitset {
+            len: len as u32,
+            words: vec![!0; (len + 63) / 64],
+        };
+        // Clear the bits past the end, so that counting is correct.
+        if len % 64 != 0 {
+            if let Some(last) = set.words.last_mut() {
+                *last = (1 << (len % 64)) - 1;
+            }
+        }
+        set
+    }
+
+    #[inline]
+    pub fn insert(&mut self, index: u32) {
+        self.words[index as usize / 64] |= 1 << (index % 64);
+    }
+
+    #[inline]
+    pub fn contains(&self, index: u32) -> bool {
+        self.words[index as usize / 64] & (1 << (index % 64)) != 0
+    }
+
+    /// Keeps only the entries that are also in `other`.
+    pub fn intersect_with(&mut self, other: &Bitset) {
+        debug_assert_eq!(self.len, other.len);
+        for (a, b) in self.words.iter_mut().zip(other.words.iter()) {
+            *a &= *b;
+        }
+    }
+
+    /// Adds all the entries in `other`.
+    pub fn union_with(&mut self, other: &Bitset) {
+        debug_assert_eq!(self.len, other.len);
+        for (a, b) in self.words.iter_mut().zip(other.words.iter()) {
+            *a |= *b;
+        }
+    }
+
+    /// Returns the number of entries in the set.
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Iterates over the entry indices in the set, in increasing order.
+    pub fn iter<'a>(&'a self) -> impl Iterator<Item = u32> + 'a {
+        self.words
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &word)| BitIter {
+                word,
+                base: i as u32 * 64,
+            })
+    }
+
+    /// Converts to a list of entry indices.
+    ///
+    /// Because the entries are stored in order of LifterID, the list
+    /// is also ordered by LifterID.
+    pub fn to_indices(&self) -> NonSortedNonUnique {
+        let mut vec = Vec::with_capacity(self.count());
+        vec.extend(self.iter());
+        NonSortedNonUnique(vec)
+    }
+}
+
+/// Iterator over the set bits of a single word.
+struct BitIter {
+    word: u64,
+    base: u32,
+}
+
+impl Iterator for BitIter {
+    type Item = u32;
+
+    #[inline]
+    fn next(&mut self) -> Option<u32> {
+        if self.word == 0 {
+            return None;
+        }
+        let bit = self.word.trailing_zeros();
+        self.word &= self.word - 1; // Clear the lowest set bit.
+        Some(self.base + bit)
+    }
+}
+
+/// The entries having one value of a column.
+///
+/// Rare values are stored as a list of indices, since a Bitset
+/// costs one bit for every entry in the database.
+#[derive(Serialize, Deserialize)]
+pub enum EntrySet {
+    Dense(Bitset),
+    Sparse(Vec<u32>),
+}
+
+impl EntrySet {
+    fn from_indices(len: usize, indices: Vec<u32>) -> EntrySet {
+        // A list costs 32 bits per entry in the set.
+        if indices.len() * 32 < len {
+            EntrySet::Sparse(indices)
+        } else {
+            let mut set = Bitset::empty(len);
+            for i in indices {
+                set.insert(i);
+            }
+            EntrySet::Dense(set)
+        }
+    }
+
+    /// Adds all the entries in this set to `acc`.
+    fn add_to(&self, acc: &mut Bitset) {
+        match self {
+            EntrySet::Dense(set) => acc.union_with(set),
+            EntrySet::Sparse(indices) => {
+                for &i in indices {
+                    acc.insert(i);
+                }
+            }
+        }
+    }
+}
+
+/// Index of the entries having each value of a column.
+#[derive(Serialize, Deserialize)]
+pub struct ColumnIndex<K> {
+    len: u32,
+    /// Each value that occurs, with its entries.
+    /// Columns have few enough values that a linear search is fine.
+    values: Vec<(K, EntrySet)>,
+}
+
+impl<K: Copy + PartialEq> ColumnIndex<K> {
+    /// Indexes a column for which each entry has exactly one value.
+    pub fn new<F>(entries: &[Entry], value_of: F) -> ColumnIndex<K>
+    where
+        F: Fn(&Entry) -> K,
+    {
+        let mut lists: Vec<(K, Vec<u32>)> = Vec::new();
+
+        // Consecutive entries are from the same lifter, so often share values.
+        let mut last = 0;
+        for (i, entry) in entries.iter().enumerate() {
+            let value = value_of(entry);
+            if lists.get(last).map_or(true, |(k, _)| *k != value) {
+                last = match lists.iter().position(|(k, _)| *k == value) {
+                    Some(position) => position,
+                    None => {
+                        lists.push((value, vec![]));
+                        lists.len() - 1
+                    }
+                };
+            }
+            lists[last].1.push(i as u32);
+        }
+        ColumnIndex::from_lists(entries.len(), lists)
+    }
+
+    /// Indexes a column for which each entry may have any number of values,
+    /// given the entries having each value.
+    pub fn new_multi(len: usize, mut lists: Vec<(K, Vec<u32>)>) -> ColumnIndex<K> {
+        lists.retain(|(_, list)| !list.is_empty());
+        for (_, list) in lists.iter_mut() {
+            list.sort_unstable();
+        }
+        ColumnIndex::from_lists(len, lists)
+    }
+
+    fn from_lists(len: usize, lists: Vec<(K, Vec<u32>)>) -> ColumnIndex<K> {
+        ColumnIndex {
+            len: len as u32,
+            values: lists
+                .into_iter()
+                .map(|(k, list)| (k, EntrySet::from_indices(len, list)))
+                .collect(),
+        }
+    }
+
+    /// Iterates over the values that occur in the column.
+    pub fn values<'a>(&'a self) -> impl Iterator<Item = K> + 'a {
+        self.values.iter().map(|(k, _)| *k)
+    }
+
+    /// Returns the entries having any of the values accepted by `accepts`.
+    pub fn select<F>(&self, accepts: F) -> Bitset
+    where
+        F: Fn(K) -> bool,
+    {
+        let mut acc = Bitset::empty(self.len as usize);
+        for (_, set) in self.values.iter().filter(|(k, _)| accepts(*k)) {
+            set.add_to(&mut acc);
+        }
+        acc
+    }
+
+    /// Returns the entries having the given value.
+    pub fn select_one(&self, value: K) -> Bitset {
+        self.select(|k| k == value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bitset_full() {
+        let set = Bitset::full(130);
+        assert_eq!(set.count(), 130);
+        assert!(set.contains(129));
+        assert_eq!(set.iter().last(), Some(129));
+    }
+
+    #[test]
+    fn test_bitset_iter_in_order() {
+        let mut set = Bitset::empty(200);
+        for &i in &[199, 0, 64, 63, 65, 128] {
+            set.insert(i);
+        }
+        let indices: Vec<u32> = set.iter().collect();
+        assert_eq!(indices, vec![0, 63, 64, 65, 128, 199]);
+        assert!(set.to_indices().maintains_invariants());
+    }
+
+    #[test]
+    fn test_bitset_intersect_union() {
+        let mut a = Bitset::empty(100);
+        let mut b = Bitset::empty(100);
+        for i in 0..50 {
+            a.insert(i);
+        }
+        for i in 40..100 {
+            b.insert(i);
+        }
+
+        let mut both = a.clone();
+        both.intersect_with(&b);
+        assert_eq!(
+            both.iter().collect::<Vec<u32>>(),
+            (40..50).collect::<Vec<u32>>()
+        );
+
+        let mut either = a.clone();
+        either.union_with(&b);
+        assert_eq!(either.count(), 100);
+    }
+
+    #[test]
+    fn test_entryset_sparse_and_dense() {
+        let len = 1000;
+
+        // Few entries are stored as a list.
+        let sparse = EntrySet::from_indices(len, vec![1, 500]);
+        match sparse {
+            EntrySet::Sparse(_) => (),
+            EntrySet::Dense(_) => panic!("expected a sparse set"),
+        }
+
+        // Many entries are stored as a Bitset.
+        let dense = EntrySet::from_indices(len, (0..500).collect());
+        match dense {
+            EntrySet::Dense(_) => (),
+            EntrySet::Sparse(_) => panic!("expected a dense set"),
+        }
+
+        // Either way, the entries can be added to a Bitset.
+        let mut acc = Bitset::empty(len);
+        sparse.add_to(&mut acc);
+        assert_eq!(acc.iter().collect::<Vec<u32>>(), vec![1, 500]);
+        dense.add_to(&mut acc);
+        assert_eq!(acc.count(), 501);
+    }
+}
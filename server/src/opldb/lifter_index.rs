@@ -0,0 +1,279 @@
+//! Index of lifter names for free-text search.
+
+use usernames::make_username;
+
+use std::cmp::{self, Ordering, Reverse};
+use std::collections::HashSet;
+use std::mem;
+
+use crate::opldb::{Entry, Lifter};
+
+/// Queries shorter than this only find exact and prefix matches.
+const FUZZY_MIN_LEN: usize = 4;
+
+/// Upper bound on the number of matching terms considered for one query,
+/// so that very short queries can't cause unbounded work.
+const MAX_CANDIDATES: usize = 10_000;
+
+/// Converts text to the normalized form used for matching.
+///
+/// This is the same normalization used to generate usernames from names.
+/// Text that can't be made into a username, like a Cyrillic name,
+/// is instead lowercased with spaces and punctuation removed.
+pub fn normalize(text: &str) -> String {
+    make_username(text).unwrap_or_else(|_| {
+        text.chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect()
+    })
+}
+
+/// How the text of a term relates to the lifter.
+///
+/// Declared in order of preference when ranking matches.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+enum TermKind {
+    /// A whole name, in either order, or an Instagram handle.
+    FullName,
+    /// A single word from a name.
+    Word,
+}
+
+/// A normalized string that refers to a lifter.
+#[derive(Serialize, Deserialize)]
+struct Term {
+    /// Byte offset of the term in `LifterIndex::text`.
+    start: u32,
+    /// Byte length of the term.
+    len: u32,
+    lifter_id: u32,
+    kind: TermKind,
+}
+
+/// Sorted list of normalized terms for every lifter, built at load time.
+///
+/// Each lifter is findable by their name in either word order, each word
+/// of their name, their Cyrillic name, and their Instagram handle.
+#[derive(Serialize, Deserialize)]
+pub struct LifterIndex {
+    /// Text of all terms, concatenated, to avoid an allocation per term.
+    text: String,
+    /// Terms sorted by their text.
+    terms: Vec<Term>,
+    /// Indices into `terms`, grouped by the length of the term in chars,
+    /// so that misspellings are only compared against terms of similar length.
+    by_char_len: Vec<Vec<u32>>,
+    /// Number of entries for each lifter, by LifterID, used to rank
+    /// otherwise-equal matches.
+    num_entries: Vec<u32>,
+}
+
+/// Adds the terms for a single name.
+fn add_name_terms(terms: &mut Vec<(String, u32, TermKind)>, lifter_id: u32, name: &str) {
+    let words: Vec<String> = name
+        .split_whitespace()
+        .map(normalize)
+        .filter(|w| !w.is_empty())
+        .collect();
+
+    terms.push((words.concat(), lifter_id, TermKind::FullName));
+    if words.len() > 1 {
+        let reversed: Vec<&str> = words.iter().rev().map(|w| w.as_str()).collect();
+        terms.push((reversed.concat(), lifter_id, TermKind::FullName));
+        for word in words {
+            terms.push((word, lifter_id, TermKind::Word));
+        }
+    }
+}
+
+/// Calculates the edit distance in chars between two strings, giving up
+/// once it must exceed `max`.
+///
+/// The `prev` and `cur` buffers are reused between calls.
+fn bounded_edit_distance(
+    a: &[char],
+    b: &[char],
+    max: usize,
+    prev: &mut Vec<usize>,
+    cur: &mut Vec<usize>,
+) -> Option<usize> {
+    prev.clear();
+    prev.extend(0..=b.len());
+    cur.clear();
+    cur.resize(b.len() + 1, 0);
+
+    for (i, ca) in a.iter().enumerate() {
+        cur[0] = i + 1;
+        let mut row_min = cur[0];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = prev[j] + if ca == cb { 0 } else { 1 };
+            cur[j + 1] = cmp::min(substitution, cmp::min(prev[j + 1], cur[j]) + 1);
+            row_min = cmp::min(row_min, cur[j + 1]);
+        }
+        if row_min > max {
+            return None;
+        }
+        mem::swap(prev, cur);
+    }
+
+    Some(prev[b.len()]).filter(|&d| d <= max)
+}
+
+impl LifterIndex {
+    pub fn new(lifters: &[Lifter], entries: &[Entry]) -> LifterIndex {
+        let mut unsorted: Vec<(String, u32, TermKind)> =
+            Vec::with_capacity(lifters.len() * 4);
+
+        for (lifter_id, lifter) in lifters.iter().enumerate() {
+            let lifter_id = lifter_id as u32;
+            add_name_terms(&mut unsorted, lifter_id, &lifter.name);
+            if let Some(ref cyrillic_name) = lifter.cyrillic_name {
+                add_name_terms(&mut unsorted, lifter_id, cyrillic_name);
+            }
+            if let Some(ref instagram) = lifter.instagram {
+                unsorted.push((normalize(instagram), lifter_id, TermKind::FullName));
+            }
+        }
+
+        // Keep only the best kind of each term for each lifter.
+        unsorted.retain(|(term, _, _)| !term.is_empty());
+        unsorted.sort_unstable();
+        unsorted.dedup_by(|b, a| a.0 == b.0 && a.1 == b.1);
+
+        let mut text = String::with_capacity(unsorted.iter().map(|t| t.0.len()).sum());
+        let mut terms = Vec::with_capacity(unsorted.len());
+        let mut by_char_len: Vec<Vec<u32>> = Vec::new();
+        for (term, lifter_id, kind) in unsorted {
+            let char_len = term.chars().count();
+            if by_char_len.len() <= char_len {
+                by_char_len.resize_with(char_len + 1, Vec::new);
+            }
+            by_char_len[char_len].push(terms.len() as u32);
+
+            terms.push(Term {
+                start: text.len() as u32,
+                len: term.len() as u32,
+                lifter_id,
+                kind,
+            });
+            text.push_str(&term);
+        }
+
+        let mut num_entries = vec![0; lifters.len()];
+        for entry in entries {
+            num_entries[entry.lifter_id as usize] += 1;
+        }
+
+        LifterIndex {
+            text,
+            terms,
+            by_char_len,
+            num_entries,
+        }
+    }
+
+    /// Returns the size of owned data structures.
+    pub fn size_bytes(&self) -> usize {
+        mem::size_of::<LifterIndex>()
+            + self.text.len()
+            + self.terms.len() * mem::size_of::<Term>()
+            + self.terms.len() * mem::size_of::<u32>()
+            + self.by_char_len.len() * mem::size_of::<Vec<u32>>()
+            + self.num_entries.len() * mem::size_of::<u32>()
+    }
+
+    #[inline]
+    fn term_text(&self, term: &Term) -> &str {
+        let start = term.start as usize;
+        &self.text[start..start + term.len as usize]
+    }
+
+    /// Returns the LifterIDs best matching the query, best first.
+    ///
+    /// Exact matches rank above prefix matches, which rank above fuzzy
+    /// matches. Among matches of the same quality, whole names rank
+    /// above single words, and then lifters with more entries rank first.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<u32> {
+        let query = normalize(query);
+        if query.is_empty() || limit == 0 {
+            return vec![];
+        }
+
+        // Matches are (quality, kind, lifter_id), where lower quality is better:
+        // 0 is an exact match, 1 is a prefix match, and 1 + n is a fuzzy match
+        // with edit distance n.
+        let mut matches: Vec<(usize, TermKind, u32)> = Vec::new();
+
+        // All terms starting with the query are contiguous.
+        let first = self
+            .terms
+            .binary_search_by(|t| self.term_text(t).cmp(&query).then(Ordering::Greater))
+            .unwrap_err();
+        for term in self.terms[first..]
+            .iter()
+            .take_while(|t| self.term_text(t).starts_with(&query))
+            .take(MAX_CANDIDATES)
+        {
+            let quality = if term.len as usize == query.len() {
+                0
+            } else {
+                1
+            };
+            matches.push((quality, term.kind, term.lifter_id));
+        }
+
+        // Only look for misspellings if there aren't enough good matches.
+        let num_lifters = matches.iter().map(|m| m.2).collect::<HashSet<u32>>().len();
+        let query_chars: Vec<char> = query.chars().collect();
+        if num_lifters < limit && query_chars.len() >= FUZZY_MIN_LEN {
+            let max_distance = if query_chars.len() >= 8 { 2 } else { 1 };
+            let (mut prev, mut cur) = (Vec::new(), Vec::new());
+            let mut text_chars = Vec::new();
+
+            // Terms whose length differs by more than the maximum distance
+            // can't match, so only look at terms of similar length.
+            let min_len = query_chars.len() - max_distance;
+            let max_len =
+                cmp::min(query_chars.len() + max_distance + 1, self.by_char_len.len());
+            let candidates = self.by_char_len[min_len.min(max_len)..max_len]
+                .iter()
+                .flatten();
+
+            for &index in candidates {
+                if matches.len() >= MAX_CANDIDATES {
+                    break;
+                }
+                let term = &self.terms[index as usize];
+                let text = self.term_text(term);
+                if text.starts_with(&query) {
+                    continue;
+                }
+                text_chars.clear();
+                text_chars.extend(text.chars());
+                if let Some(distance) = bounded_edit_distance(
+                    &query_chars,
+                    &text_chars,
+                    max_distance,
+                    &mut prev,
+                    &mut cur,
+                ) {
+                    matches.push((1 + distance, term.kind, term.lifter_id));
+                }
+            }
+        }
+
+        matches.sort_unstable_by_key(|&(quality, kind, lifter_id)| {
+            let num_entries = self.num_entries[lifter_id as usize];
+            (quality, kind, Reverse(num_entries), lifter_id)
+        });
+
+        let mut seen = HashSet::new();
+        matches
+            .into_iter()
+            .map(|(_, _, lifter_id)| lifter_id)
+            .filter(|&lifter_id| seen.insert(lifter_id))
+            .take(limit)
+            .collect()
+    }
+}
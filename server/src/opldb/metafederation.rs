@@ -172,6 +172,19 @@ pub enum MetaFederation {
 }
 
 impl MetaFederation {
+    /// Whether membership only depends on the meet, so that every entry
+    /// in a meet is part of the MetaFederation if any of them is.
+    ///
+    /// This must agree with `contains()`.
+    pub fn is_meet_level(self) -> bool {
+        match self {
+            MetaFederation::IPACAN
+            | MetaFederation::IPFAndAffiliates
+            | MetaFederation::IPFInternationals => true,
+            _ => false,
+        }
+    }
+
     /// Defines whether a given `Entry` is part of the MetaFederation.
     ///
     /// Matching is done on Entries instead of on Meets since a MetaFederation
@@ -503,6 +516,7 @@ impl MetaFederation {
 ///
 /// A meet is part of the MetaFederation if it contains
 /// at least one entry such that `MetaFederation::contains(entry)`.
+#[derive(Serialize, Deserialize)]
 pub struct MetaFederationCache {
     /// Uses (MetaFederation as usize) as index to a list of meet_ids
     /// for that MetaFederation.
@@ -4,6 +4,7 @@
 //! better than a "real" database like SQLite3 or PostgreSQL. Additionally,
 //! by storing all the data in formats native to Rust, we avoid copy overhead.
 
+use coefficients;
 use csv;
 use itertools::Itertools;
 use opltypes::*;
@@ -13,10 +14,23 @@ use std::mem;
 
 // Exports.
 pub mod algorithms;
+mod column_index;
+pub use self::column_index::*;
+mod lifter_index;
+pub use self::lifter_index::LifterIndex;
 mod metafederation;
 pub use self::metafederation::*;
+mod range_successor;
+mod rankings_cache;
+pub use self::rankings_cache::*;
+mod shared;
+pub use self::shared::SharedOplDb;
+mod snapshot;
+pub use self::snapshot::SNAPSHOT_VERSION;
 mod static_cache;
 pub use self::static_cache::*;
+mod weightclasses;
+pub use self::weightclasses::WeightClassScheme;
 
 /// The definition of a Lifter in the database.
 #[derive(Serialize, Deserialize)]
@@ -38,7 +52,7 @@ pub struct Lifter {
 }
 
 /// The definition of a Meet in the database.
-#[derive(Deserialize)]
+#[derive(Serialize, Deserialize)]
 pub struct Meet {
     #[serde(rename(deserialize = "MeetPath"))]
     pub path: String,
@@ -64,7 +78,7 @@ pub struct Meet {
 }
 
 /// The definition of an Entry in the database.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Entry {
     #[serde(rename(deserialize = "MeetID"))]
     pub meet_id: u32,
@@ -118,14 +132,12 @@ pub struct Entry {
     pub totalkg: WeightKg,
     #[serde(rename(deserialize = "Place"))]
     pub place: Place,
-    #[serde(rename(deserialize = "Wilks"))]
-    pub wilks: Points,
-    #[serde(rename(deserialize = "McCulloch"))]
-    pub mcculloch: Points,
-    #[serde(rename(deserialize = "Glossbrenner"))]
-    pub glossbrenner: Points,
-    #[serde(rename(deserialize = "IPFPoints"))]
-    pub ipfpoints: Points,
+    /// Points for each of the `coefficients::stored_formulas()`, in order.
+    ///
+    /// These come from the formulas' own columns, which are read separately
+    /// by `import_entries_csv()`.
+    #[serde(default, rename(deserialize = "StoredPoints"))]
+    pub stored_points: [Points; coefficients::NUM_STORED],
     #[serde(
         rename(deserialize = "Tested"),
         deserialize_with = "deserialize_yes_no"
@@ -133,6 +145,8 @@ pub struct Entry {
     pub tested: bool,
     #[serde(rename(deserialize = "AgeClass"))]
     pub ageclass: AgeClass,
+    #[serde(rename(deserialize = "BirthYearClass"))]
+    pub birthyearclass: BirthYearClass,
     #[serde(rename(deserialize = "Country"))]
     pub lifter_country: Option<Country>,
 }
@@ -156,6 +170,55 @@ impl Entry {
         self.best3deadliftkg.max(self.deadlift4kg)
     }
 
+    /// Returns the weight as a multiple of bodyweight,
+    /// or zero if the bodyweight is unknown.
+    #[inline]
+    pub fn bodyweight_ratio(&self, weight: WeightKg) -> Points {
+        if self.bodyweightkg <= WeightKg::from_i32(0) {
+            return Points::from_i32(0);
+        }
+        Points::from(f32::from(weight) / f32::from(self.bodyweightkg))
+    }
+
+    /// Returns the Entry's points in the given system.
+    ///
+    /// When looking up points for many Entries, resolve the `PointsSource`
+    /// once and use `points_from()` instead.
+    #[inline]
+    pub fn points(&self, system: PointsSystem) -> Points {
+        self.points_from(PointsSource::of(system))
+    }
+
+    /// Returns the Entry's points from the given source.
+    #[inline]
+    pub fn points_from(&self, source: PointsSource) -> Points {
+        match source {
+            PointsSource::Stored(index) => self.stored_points[index],
+            PointsSource::Computed(formula) => {
+                let input = self.points_input();
+                if formula.supports(input.event, input.equipment) {
+                    formula.points(&input)
+                } else {
+                    Points::from_i32(0)
+                }
+            }
+            PointsSource::Total => self.totalkg.as_type(WeightUnits::Kg).as_points(),
+        }
+    }
+
+    /// Describes the Entry for use by a points formula.
+    #[inline]
+    pub fn points_input(&self) -> coefficients::PointsInput {
+        coefficients::PointsInput {
+            sex: self.sex,
+            equipment: self.equipment,
+            event: self.event,
+            bodyweightkg: self.bodyweightkg,
+            totalkg: self.totalkg,
+            age: self.age,
+        }
+    }
+
     /// Borrows the Division string.
     #[inline]
     pub fn get_division(&self) -> Option<&str> {
@@ -163,10 +226,35 @@ impl Entry {
     }
 }
 
+/// Where an Entry's points in some `PointsSystem` come from.
+#[derive(Copy, Clone)]
+pub enum PointsSource {
+    /// Points stored in the database, as an index into `Entry::stored_points`.
+    Stored(usize),
+    /// Points calculated on demand, for formulas without a `csv_column`.
+    Computed(&'static dyn coefficients::PointsFormula),
+    /// `PointsSystem::Total` has no points, so it reinterprets the TotalKg.
+    Total,
+}
+
+impl PointsSource {
+    /// Looks up the source for a system in the `coefficients` registry.
+    pub fn of(system: PointsSystem) -> PointsSource {
+        match coefficients::formula(system) {
+            Some(formula) => match coefficients::stored_index(system) {
+                Some(index) => PointsSource::Stored(index),
+                None => PointsSource::Computed(formula),
+            },
+            None => PointsSource::Total,
+        }
+    }
+}
+
 /// The collection of data stores that constitute the complete dataset.
 ///
 /// The data structure is immutable. To prevent the owner from modifying
 /// owned data, the struct contents are private and accessed through getters.
+#[derive(Serialize, Deserialize)]
 pub struct OplDb {
     /// The LifterID is implicit in the backing vector, as the index.
     ///
@@ -187,6 +275,20 @@ pub struct OplDb {
     /// Precalculated caches.
     static_cache: StaticCache,
     metafed_cache: MetaFederationCache,
+    lifter_index: LifterIndex,
+
+    /// Every year from the latest meet to the earliest, latest first.
+    years: Vec<u32>,
+
+    /// Every valid state in which a meet occurred, with its country.
+    meet_states: Vec<(Country, String)>,
+
+    /// The weightclasses used by each federation, from their CONFIG.toml.
+    weightclass_schemes: Vec<WeightClassScheme>,
+
+    /// Rankings computed at runtime, which aren't part of snapshots.
+    #[serde(skip)]
+    rankings_cache: RankingsCache,
 }
 
 /// Reads the `lifters.csv` file into a Vec<Lifter>.
@@ -227,8 +329,24 @@ fn import_entries_csv(
     let mut vec = Vec::with_capacity(700_000);
 
     let mut rdr = csv::Reader::from_path(file)?;
-    for entry in rdr.deserialize() {
-        let entry: Entry = entry?;
+    let headers = rdr.headers()?.clone();
+
+    // Find the column for each stored points formula.
+    let mut points_columns = [0; coefficients::NUM_STORED];
+    for (i, formula) in coefficients::stored_formulas().enumerate() {
+        let column = formula.csv_column().unwrap();
+        points_columns[i] = headers
+            .iter()
+            .position(|h| h == column)
+            .ok_or_else(|| format!("{} is missing the {} column", file, column))?;
+    }
+
+    for record in rdr.records() {
+        let record = record?;
+        let mut entry: Entry = record.deserialize(Some(&headers))?;
+        for (i, &column) in points_columns.iter().enumerate() {
+            entry.stored_points[i] = record[column].parse::<Points>()?;
+        }
         vec.push(entry);
     }
 
@@ -289,6 +407,36 @@ fn precompute_num_unique_lifters(entries: &[Entry], meet_id: u32) -> u32 {
     lifter_ids.into_iter().group_by(|x| *x).into_iter().count() as u32
 }
 
+/// Lists every year from the latest meet to the earliest, latest first.
+///
+/// Years without any meets in between are included, so that the list
+/// is a continuous range.
+fn calculate_years(meets: &[Meet]) -> Vec<u32> {
+    let first = meets.iter().map(|m| m.date.year()).min();
+    let last = meets.iter().map(|m| m.date.year()).max();
+    match (first, last) {
+        (Some(first), Some(last)) => (first..=last).rev().collect(),
+        _ => vec![],
+    }
+}
+
+/// Lists the distinct valid states in which meets occurred,
+/// ordered by country and then by state.
+fn calculate_meet_states(meets: &[Meet]) -> Vec<(Country, String)> {
+    let mut states: Vec<(Country, String)> = meets
+        .iter()
+        .filter_map(|m| {
+            let state = m.state.as_ref()?;
+            State::from_str_and_country(state, m.country).ok()?;
+            Some((m.country, state.clone()))
+        })
+        .collect();
+
+    states.sort_unstable_by(|a, b| (a.0 as u32, &a.1).cmp(&(b.0 as u32, &b.1)));
+    states.dedup();
+    states
+}
+
 impl OplDb {
     /// Constructs the `OplDb` from CSV files produces by the project
     /// build script.
@@ -296,12 +444,18 @@ impl OplDb {
         lifters_csv: &str,
         meets_csv: &str,
         entries_csv: &str,
+        weightclasses_csv: &str,
     ) -> Result<OplDb, Box<dyn Error>> {
         let lifters = import_lifters_csv(lifters_csv)?;
         let mut meets = import_meets_csv(meets_csv)?;
         let (entries, metafed_cache) = import_entries_csv(entries_csv, &mut meets)?;
 
-        let static_cache = StaticCache::new(&meets, &entries);
+        let static_cache = StaticCache::new(&meets, &entries, &metafed_cache);
+        let lifter_index = LifterIndex::new(&lifters, &entries);
+        let years = calculate_years(&meets);
+        let meet_states = calculate_meet_states(&meets);
+        let weightclass_schemes =
+            weightclasses::import_weightclasses_csv(weightclasses_csv)?;
 
         Ok(OplDb {
             lifters,
@@ -309,6 +463,11 @@ impl OplDb {
             entries,
             static_cache,
             metafed_cache,
+            lifter_index,
+            years,
+            meet_states,
+            weightclass_schemes,
+            rankings_cache: RankingsCache::default(),
         })
     }
 
@@ -344,8 +503,19 @@ impl OplDb {
                 owned_strings += mem::size_of::<String>() + division.len();
             }
         }
+        let mut schemes_size = 0;
+        for scheme in &self.weightclass_schemes {
+            schemes_size += mem::size_of::<WeightClassScheme>() + scheme.name.len();
+            schemes_size += mem::size_of::<WeightClassKg>() * scheme.classes.len();
+        }
 
-        mem::size_of::<OplDb>() + owned_vectors + owned_strings
+        mem::size_of::<OplDb>()
+            + owned_vectors
+            + owned_strings
+            + schemes_size
+            + self.lifter_index.size_bytes()
+            + mem::size_of::<u32>() * self.years.len()
+            + mem::size_of::<(Country, String)>() * self.meet_states.len()
     }
 
     /// Borrows the lifters vector.
@@ -396,6 +566,37 @@ impl OplDb {
         &self.metafed_cache
     }
 
+    /// Borrows the cache of computed rankings.
+    #[inline]
+    pub fn get_rankings_cache(&self) -> &RankingsCache {
+        &self.rankings_cache
+    }
+
+    /// Borrows the lifter search index.
+    #[inline]
+    pub fn get_lifter_index(&self) -> &LifterIndex {
+        &self.lifter_index
+    }
+
+    /// Borrows the range of years spanned by the meets, latest first.
+    #[inline]
+    pub fn get_years(&self) -> &[u32] {
+        &self.years
+    }
+
+    /// Borrows the list of valid states in which meets occurred,
+    /// ordered by country.
+    #[inline]
+    pub fn get_meet_states(&self) -> &[(Country, String)] {
+        &self.meet_states
+    }
+
+    /// Borrows the weightclass schemes of every federation.
+    #[inline]
+    pub fn get_weightclass_schemes(&self) -> &[WeightClassScheme] {
+        &self.weightclass_schemes
+    }
+
     /// Look up the lifter_id by username.
     pub fn get_lifter_id(&self, username: &str) -> Option<u32> {
         for i in 0..self.lifters.len() {
@@ -427,6 +628,30 @@ impl OplDb {
         acc
     }
 
+    /// Get a list of all lifters that a "/u/<username>" request refers to.
+    ///
+    /// More than one lifter is returned if the username requires disambiguation.
+    pub fn get_lifter_ids_for_username(&self, username: &str) -> Vec<u32> {
+        // Disambiguations end with a digit.
+        // Some lifters may have failed to be merged with their disambiguated
+        // username. Therefore, for usernames without a digit, it cannot be
+        // assumed that they are *not* a disambiguation.
+        let is_definitely_disambiguation: bool = username
+            .chars()
+            .last()
+            .map_or(false, |c| c.is_ascii_digit());
+
+        if is_definitely_disambiguation {
+            if let Some(id) = self.get_lifter_id(username) {
+                vec![id]
+            } else {
+                vec![]
+            }
+        } else {
+            self.get_lifters_under_username(username)
+        }
+    }
+
     /// Look up the lifter_id by Name.
     ///
     /// This function exists for compatibility for the old site.
@@ -0,0 +1,168 @@
+//! Finds the smallest value at least some bound within a range of a sequence.
+//!
+//! This is implemented as a wavelet matrix: for each bit of the values,
+//! from the highest down, the sequence is stably partitioned by that bit,
+//! and the bits are kept with a rank index. A query follows the bound's
+//! bits down the levels, so it takes time proportional to the number of bits,
+//! regardless of the length of the range.
+
+/// A bit vector that can count the set bits before any position.
+struct RankBits {
+    words: Vec<u64>,
+    /// The number of set bits before each word.
+    ranks: Vec<u32>,
+}
+
+impl RankBits {
+    fn new(bits: &[bool]) -> RankBits {
+        let mut words = vec![0u64; bits.len() / 64 + 1];
+        for (i, _) in bits.iter().enumerate().filter(|(_, &b)| b) {
+            words[i / 64] |= 1 << (i % 64);
+        }
+
+        let mut ranks = Vec::with_capacity(words.len());
+        let mut total = 0;
+        for word in &words {
+            ranks.push(total);
+            total += word.count_ones();
+        }
+
+        RankBits { words, ranks }
+    }
+
+    /// Returns the number of set bits before `index`.
+    #[inline]
+    fn rank1(&self, index: usize) -> usize {
+        let word = self.words[index / 64] & ((1 << (index % 64)) - 1);
+        self.ranks[index / 64] as usize + word.count_ones() as usize
+    }
+}
+
+/// An immutable sequence of values, supporting range successor queries.
+pub struct RangeSuccessor {
+    /// One level per bit of the values, from the highest bit down.
+    levels: Vec<RankBits>,
+    /// The number of zero bits in each level.
+    zeros: Vec<usize>,
+}
+
+impl RangeSuccessor {
+    pub fn new(values: &[u32]) -> RangeSuccessor {
+        let max = values.iter().cloned().max().unwrap_or(0);
+        let num_bits = 32 - max.leading_zeros() as usize;
+
+        let mut levels = Vec::with_capacity(num_bits);
+        let mut zeros = Vec::with_capacity(num_bits);
+        let mut current: Vec<u32> = values.to_vec();
+        for level in 0..num_bits {
+            let bit = num_bits - 1 - level;
+            let bits: Vec<bool> = current.iter().map(|v| (v >> bit) & 1 == 1).collect();
+            levels.push(RankBits::new(&bits));
+            zeros.push(bits.iter().filter(|&&b| !b).count());
+
+            // Stably move the values with a zero bit to the front.
+            let (mut next, ones): (Vec<u32>, Vec<u32>) =
+                current.iter().partition(|&&v| (v >> bit) & 1 == 0);
+            next.extend(ones);
+            current = next;
+        }
+
+        RangeSuccessor { levels, zeros }
+    }
+
+    /// Returns the smallest value at least `bound` in `values[start..end]`.
+    pub fn successor(&self, start: usize, end: usize, bound: u32) -> Option<u32> {
+        // Bounds above every value, including past the highest bit, find nothing.
+        let num_bits = self.levels.len();
+        if num_bits < 32 && bound >> num_bits != 0 {
+            return None;
+        }
+        self.descend(0, start, end, bound, 0, true)
+    }
+
+    /// Finds the smallest value in `[start, end)` at the given level,
+    /// whose higher bits are `prefix`.
+    ///
+    /// If `tight`, the higher bits equal those of the `bound`, so the value
+    /// must also be at least the `bound` in the remaining bits.
+    fn descend(
+        &self,
+        level: usize,
+        start: usize,
+        end: usize,
+        bound: u32,
+        prefix: u32,
+        tight: bool,
+    ) -> Option<u32> {
+        if start >= end {
+            return None;
+        }
+        if level == self.levels.len() {
+            return Some(prefix);
+        }
+
+        let bits = &self.levels[level];
+        let (ones_start, ones_end) = (bits.rank1(start), bits.rank1(end));
+        let zero_range = (start - ones_start, end - ones_end);
+        let one_range = (self.zeros[level] + ones_start, self.zeros[level] + ones_end);
+        let bit = 1 << (self.levels.len() - 1 - level);
+
+        if tight && bound & bit != 0 {
+            // Values with a zero here are below the bound.
+            return self.descend(
+                level + 1,
+                one_range.0,
+                one_range.1,
+                bound,
+                prefix | bit,
+                true,
+            );
+        }
+
+        // Prefer values with a zero here, since they're smaller.
+        self.descend(level + 1, zero_range.0, zero_range.1, bound, prefix, tight)
+            .or_else(|| {
+                self.descend(
+                    level + 1,
+                    one_range.0,
+                    one_range.1,
+                    bound,
+                    prefix | bit,
+                    false,
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_linear_search() {
+        let values: Vec<u32> = vec![7, 3, 12, 0, 9, 3, 15, 1, 8, 4, 11, 2];
+        let successor = RangeSuccessor::new(&values);
+
+        for start in 0..=values.len() {
+            for end in start..=values.len() {
+                for bound in 0..18 {
+                    let expected = values[start..end]
+                        .iter()
+                        .cloned()
+                        .filter(|&v| v >= bound)
+                        .min();
+                    assert_eq!(successor.successor(start, end, bound), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn handles_empty_and_zero() {
+        assert_eq!(RangeSuccessor::new(&[]).successor(0, 0, 0), None);
+
+        let zeros = RangeSuccessor::new(&[0, 0]);
+        assert_eq!(zeros.successor(0, 2, 0), Some(0));
+        assert_eq!(zeros.successor(0, 2, 1), None);
+    }
+}
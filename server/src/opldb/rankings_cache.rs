@@ -0,0 +1,135 @@
+//! Bounded cache of computed rankings, for selections that aren't
+//! precomputed in the StaticCache.
+
+use std::cmp::Ordering;
+use std::sync::{Arc, Mutex};
+
+use crate::opldb::algorithms;
+use crate::opldb::range_successor::RangeSuccessor;
+use crate::opldb::static_cache::RankedEntries;
+use crate::opldb::OplDb;
+use crate::pages::selection::Selection;
+
+/// The maximum number of rankings kept in the cache.
+///
+/// Each cached list costs about 12 bytes per row, so even the largest
+/// rankings only cost a few megabytes each.
+const CAPACITY: usize = 32;
+
+/// A computed rankings list, with an index for searching by username.
+pub struct CachedRankings {
+    /// The rankings themselves.
+    pub list: RankedEntries,
+    /// Positions in `list`, sorted by the username of the lifter at that position.
+    ///
+    /// Lifters appear more than once when ranking all performances,
+    /// so ties are broken by position.
+    by_username: Vec<u32>,
+    /// Finds the earliest position at or after a row within a range
+    /// of `by_username`.
+    successor: RangeSuccessor,
+}
+
+impl CachedRankings {
+    pub fn new(opldb: &OplDb, list: RankedEntries) -> CachedRankings {
+        let mut by_username: Vec<u32> = (0..list.indices().len() as u32).collect();
+        by_username.sort_unstable_by_key(|&i| {
+            let entry = opldb.get_entry(list.indices()[i as usize]);
+            (&opldb.get_lifter(entry.lifter_id).username, i)
+        });
+
+        let successor = RangeSuccessor::new(&by_username);
+        CachedRankings {
+            list,
+            by_username,
+            successor,
+        }
+    }
+
+    /// Returns the username of the lifter at a position in the rankings.
+    #[inline]
+    fn username_at<'db>(&self, opldb: &'db OplDb, position: u32) -> &'db str {
+        let entry = opldb.get_entry(self.list.indices()[position as usize]);
+        &opldb.get_lifter(entry.lifter_id).username
+    }
+
+    /// Returns the first position at or after `start` of a lifter whose
+    /// username begins with `prefix`.
+    pub fn first_with_prefix(
+        &self,
+        opldb: &OplDb,
+        prefix: &str,
+        start: usize,
+    ) -> Option<usize> {
+        if start >= self.by_username.len() {
+            return None;
+        }
+
+        // All usernames with the prefix are contiguous in `by_username`.
+        let first = self
+            .by_username
+            .binary_search_by(|&p| {
+                self.username_at(opldb, p)
+                    .cmp(prefix)
+                    .then(Ordering::Greater)
+            })
+            .unwrap_err();
+        let end = self
+            .by_username
+            .binary_search_by(|&p| {
+                let username = self.username_at(opldb, p);
+                if username.starts_with(prefix) {
+                    Ordering::Less
+                } else {
+                    username.cmp(prefix).then(Ordering::Greater)
+                }
+            })
+            .unwrap_err();
+
+        self.successor
+            .successor(first, end, start as u32)
+            .map(|p| p as usize)
+    }
+}
+
+/// Least-recently-used cache of rankings, keyed by Selection.
+///
+/// This lives in the OplDb so that it is discarded along with the data
+/// it was computed from.
+#[derive(Default)]
+pub struct RankingsCache {
+    /// Cached rankings, from least to most recently used.
+    ///
+    /// The cache is small enough that a linear search is fast.
+    entries: Mutex<Vec<(Selection, Arc<CachedRankings>)>>,
+}
+
+impl RankingsCache {
+    /// Returns the cached rankings for the selection, computing them if needed.
+    pub fn get(&self, opldb: &OplDb, selection: &Selection) -> Arc<CachedRankings> {
+        {
+            let mut entries = self.entries.lock().unwrap();
+            if let Some(i) = entries.iter().position(|(s, _)| s == selection) {
+                let hit = entries.remove(i);
+                let rankings = Arc::clone(&hit.1);
+                entries.push(hit);
+                return rankings;
+            }
+        }
+
+        // Compute without holding the lock, so other requests aren't blocked.
+        // Two requests for the same selection may both compute it, which is harmless.
+        let list = algorithms::get_full_sorted_uniqued(selection, opldb).into_owned();
+        let rankings = Arc::new(CachedRankings::new(opldb, list));
+
+        let mut entries = self.entries.lock().unwrap();
+        if let Some((_, existing)) = entries.iter().find(|(s, _)| s == selection) {
+            return Arc::clone(existing);
+        }
+        if entries.len() >= CAPACITY {
+            entries.remove(0);
+        }
+        entries.push((*selection, Arc::clone(&rankings)));
+        rankings
+    }
+}
@@ -0,0 +1,96 @@
+//! A handle to the current OplDb that can be replaced while the server runs.
+
+use std::error::Error;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::thread;
+
+use crate::opldb::OplDb;
+
+/// Shared ownership of the current OplDb, allowing a new database
+/// to be swapped in without restarting the server.
+///
+/// Readers take an `Arc` to the database at the start of a request,
+/// so a request that is in progress during a swap continues to use
+/// the old database, which is freed once the last such request finishes.
+///
+/// Clones refer to the same underlying database.
+#[derive(Clone)]
+pub struct SharedOplDb {
+    current: Arc<RwLock<Arc<OplDb>>>,
+    reloading: Arc<AtomicBool>,
+}
+
+impl SharedOplDb {
+    pub fn new(opldb: OplDb) -> SharedOplDb {
+        SharedOplDb::from_arc(Arc::new(opldb))
+    }
+
+    pub fn from_arc(opldb: Arc<OplDb>) -> SharedOplDb {
+        SharedOplDb {
+            current: Arc::new(RwLock::new(opldb)),
+            reloading: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Returns the current database.
+    pub fn get(&self) -> Arc<OplDb> {
+        // The lock is only ever held to clone or replace the Arc,
+        // neither of which can panic, so it is never poisoned.
+        Arc::clone(&self.current.read().unwrap())
+    }
+
+    /// Replaces the current database.
+    pub fn swap(&self, opldb: OplDb) {
+        let new = Arc::new(opldb);
+        let old = {
+            let mut current = self.current.write().unwrap();
+            std::mem::replace(&mut *current, new)
+        };
+        // Release the lock before possibly freeing the old database.
+        drop(old);
+    }
+
+    /// Whether a background reload is in progress.
+    pub fn is_reloading(&self) -> bool {
+        self.reloading.load(Ordering::SeqCst)
+    }
+
+    /// Builds a new database on a background thread using `load`,
+    /// swapping it in on success.
+    ///
+    /// Returns `false` without doing anything if a reload is already
+    /// in progress. A failed load leaves the current database in place.
+    pub fn reload_in_background<F>(&self, load: F) -> bool
+    where
+        F: FnOnce() -> Result<OplDb, Box<dyn Error>> + Send + 'static,
+    {
+        if self.reloading.swap(true, Ordering::SeqCst) {
+            return false;
+        }
+
+        let shared = self.clone();
+        thread::spawn(move || {
+            // Clears the flag even if loading panics.
+            let _flag = ReloadingFlag(Arc::clone(&shared.reloading));
+
+            match load() {
+                Ok(opldb) => {
+                    shared.swap(opldb);
+                    println!("OplDb reloaded.");
+                }
+                Err(e) => println!("OplDb reload failed: {}", e),
+            }
+        });
+        true
+    }
+}
+
+/// Clears the `reloading` flag when dropped.
+struct ReloadingFlag(Arc<AtomicBool>);
+
+impl Drop for ReloadingFlag {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::SeqCst);
+    }
+}
@@ -0,0 +1,133 @@
+//! Binary snapshots of a fully-constructed OplDb, for fast startup.
+//!
+//! Building the OplDb from CSV requires parsing every row and then
+//! recomputing all of the sorts in the StaticCache. A snapshot stores
+//! the finished structure, so loading is a single read of the file
+//! followed by deserialization.
+//!
+//! The file is a fixed-size header followed by a bincode payload:
+//!
+//! | Offset | Size | Contents                                   |
+//! | ------ | ---- | ------------------------------------------ |
+//! | 0      | 8    | The magic bytes `OPLDBSNP`                 |
+//! | 8      | 4    | The format version, as a little-endian u32 |
+//! | 12     | 4    | The CRC-32 of the payload, little-endian   |
+//! | 16     | ...  | The bincode-encoded OplDb                  |
+
+use bincode;
+use crc32fast;
+
+use std::convert::TryInto;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use crate::opldb::OplDb;
+
+/// Identifies the file as an OplDb snapshot.
+const MAGIC: &[u8; 8] = b"OPLDBSNP";
+
+/// Version of the snapshot format.
+///
+/// This must be incremented whenever the serialized layout of the OplDb
+/// changes, including any field added to an Entry, Meet, Lifter, or cache.
+/// Snapshots with a different version are rejected.
+pub const SNAPSHOT_VERSION: u32 = 15;
+
+/// Length of the header preceding the payload.
+const HEADER_LEN: usize = 16;
+
+/// Reads a little-endian u32 from the header at the given offset.
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    // The caller has already checked that the header is long enough.
+    u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+}
+
+/// Whether the snapshot exists and is at least as new as all its sources.
+fn is_fresh(snapshot: &Path, sources: &[&str]) -> bool {
+    let modified = |path: &Path| fs::metadata(path).and_then(|m| m.modified()).ok();
+
+    match modified(snapshot) {
+        Some(snapshot_time) => sources.iter().all(|source| {
+            modified(Path::new(source)).map_or(false, |t| t <= snapshot_time)
+        }),
+        None => false,
+    }
+}
+
+impl OplDb {
+    /// Writes the OplDb to a snapshot file.
+    ///
+    /// The file is written under a temporary name and then renamed,
+    /// so a reader never sees a partial snapshot.
+    pub fn write_snapshot(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let payload = bincode::serialize(self)?;
+
+        let mut bytes = Vec::with_capacity(HEADER_LEN + payload.len());
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&SNAPSHOT_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&crc32fast::hash(&payload).to_le_bytes());
+        bytes.extend_from_slice(&payload);
+
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, &bytes)?;
+        fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+
+    /// Constructs the OplDb from a snapshot file.
+    ///
+    /// Fails if the file isn't a snapshot, was written by a different
+    /// format version, or is corrupt.
+    pub fn from_snapshot(path: &Path) -> Result<OplDb, Box<dyn Error>> {
+        let bytes = fs::read(path)?;
+
+        if bytes.len() < HEADER_LEN || &bytes[0..MAGIC.len()] != MAGIC {
+            return Err("not an OplDb snapshot".into());
+        }
+
+        let version = read_u32(&bytes, 8);
+        if version != SNAPSHOT_VERSION {
+            return Err(format!(
+                "snapshot version {} is not the expected version {}",
+                version, SNAPSHOT_VERSION
+            )
+            .into());
+        }
+
+        let payload = &bytes[HEADER_LEN..];
+        if crc32fast::hash(payload) != read_u32(&bytes, 12) {
+            return Err("snapshot checksum mismatch".into());
+        }
+
+        Ok(bincode::deserialize(payload)?)
+    }
+
+    /// Constructs the OplDb from a snapshot, if a valid one exists that is
+    /// at least as new as the CSV files. Otherwise, constructs the OplDb
+    /// from the CSV files and tries to replace the snapshot.
+    ///
+    /// Failing to write the snapshot is not an error: it only means that
+    /// the next startup will also read the CSV files.
+    pub fn from_snapshot_or_csv(
+        snapshot: &str,
+        lifters_csv: &str,
+        meets_csv: &str,
+        entries_csv: &str,
+        weightclasses_csv: &str,
+    ) -> Result<OplDb, Box<dyn Error>> {
+        let snapshot = Path::new(snapshot);
+        let sources = [lifters_csv, meets_csv, entries_csv, weightclasses_csv];
+
+        if is_fresh(snapshot, &sources) {
+            if let Ok(opldb) = OplDb::from_snapshot(snapshot) {
+                return Ok(opldb);
+            }
+        }
+
+        let opldb =
+            OplDb::from_csv(lifters_csv, meets_csv, entries_csv, weightclasses_csv)?;
+        let _ = opldb.write_snapshot(snapshot);
+        Ok(opldb)
+    }
+}
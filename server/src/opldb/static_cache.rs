@@ -1,13 +1,16 @@
 //! Precalculated cache of data, such as sorts.
 
+use coefficients;
 use itertools::Itertools;
 use opltypes::*;
+use strum::IntoEnumIterator;
 
 use std::cmp::Ordering;
 use std::ops::Deref;
 
 use crate::opldb::algorithms::*;
-use crate::opldb::{Entry, Meet};
+use crate::opldb::column_index::{Bitset, ColumnIndex};
+use crate::opldb::{Entry, Meet, MetaFederation, MetaFederationCache, PointsSource};
 
 /// List of indices into the opldb.entries vector,
 /// in no particular order, but such that entries from the same
@@ -20,7 +23,7 @@ use crate::opldb::{Entry, Meet};
 /// Because it's non-sorted, that also means that there doesn't
 /// need to be a version of the data stored for each way in
 /// which the data can be sorted, so there's memory savings.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct NonSortedNonUnique(pub Vec<u32>);
 
 /// List of indices into the opldb.entries vector,
@@ -38,6 +41,7 @@ pub struct SortedNonUnique(pub Vec<u32>);
 ///
 /// This is useful to get `O(1)` lookup, since it stores
 /// the filter/sort/unique algorithm in its final output.
+#[derive(Serialize, Deserialize)]
 pub struct SortedUnique(pub Vec<u32>);
 
 // TODO: Can we templatize these PossiblyOwned types?
@@ -59,20 +63,52 @@ impl<'db> Deref for PossiblyOwnedNonSortedNonUnique<'db> {
     }
 }
 
-/// Allows remembering whether or not a returned SortedUnique is to be
+/// The rankings for a selection, as indices into the opldb.entries vector
+/// in sorted order.
+///
+/// Whether each lifter may occur more than once depends on the selection.
+pub enum RankedEntries {
+    /// Only the best entry of each lifter.
+    BestPerLifter(SortedUnique),
+    /// Every entry, so lifters may occur multiple times.
+    AllPerformances(SortedNonUnique),
+}
+
+impl RankedEntries {
+    /// Returns the ranked entry indices, best first.
+    pub fn indices(&self) -> &[u32] {
+        match self {
+            RankedEntries::BestPerLifter(x) => &x.0,
+            RankedEntries::AllPerformances(x) => &x.0,
+        }
+    }
+}
+
+/// Allows remembering whether or not returned RankedEntries are to be
 /// deallocated.
-pub enum PossiblyOwnedSortedUnique<'db> {
+///
+/// Only best-per-lifter rankings are precomputed, so only those are borrowed.
+pub enum PossiblyOwnedRankedEntries<'db> {
     Borrowed(&'db SortedUnique),
-    Owned(SortedUnique),
+    Owned(RankedEntries),
 }
 
-impl<'db> Deref for PossiblyOwnedSortedUnique<'db> {
-    type Target = SortedUnique;
+impl<'db> PossiblyOwnedRankedEntries<'db> {
+    /// Returns the ranked entry indices, best first.
+    pub fn indices(&self) -> &[u32] {
+        match self {
+            PossiblyOwnedRankedEntries::Borrowed(x) => &x.0,
+            PossiblyOwnedRankedEntries::Owned(x) => x.indices(),
+        }
+    }
 
-    fn deref(&self) -> &SortedUnique {
-        match &self {
-            PossiblyOwnedSortedUnique::Borrowed(x) => x,
-            PossiblyOwnedSortedUnique::Owned(x) => &x,
+    /// Converts to owned RankedEntries, copying if borrowed.
+    pub fn into_owned(self) -> RankedEntries {
+        match self {
+            PossiblyOwnedRankedEntries::Borrowed(x) => {
+                RankedEntries::BestPerLifter(SortedUnique(x.0.clone()))
+            }
+            PossiblyOwnedRankedEntries::Owned(x) => x,
         }
     }
 }
@@ -207,6 +243,30 @@ impl NonSortedNonUnique {
         SortedUnique(vec)
     }
 
+    /// Sorts the data with reference to a comparator, keeping every entry.
+    pub fn sort_by<F, G>(
+        &self,
+        entries: &[Entry],
+        meets: &[Meet],
+        compare: F,
+        belongs: G,
+    ) -> SortedNonUnique
+    where
+        F: Fn(&[Meet], &Entry, &Entry) -> Ordering,
+        G: Fn(&Entry) -> bool,
+    {
+        let mut vec: Vec<u32> = self
+            .0
+            .iter()
+            .filter(|&&idx| belongs(&entries[idx as usize]))
+            .cloned()
+            .collect();
+
+        vec.sort_by(|&a, &b| compare(meets, &entries[a as usize], &entries[b as usize]));
+        vec.shrink_to_fit();
+        SortedNonUnique(vec)
+    }
+
     /// Tests that the list is monotonically increasing.
     pub fn maintains_invariants(&self) -> bool {
         if self.0.is_empty() {
@@ -225,6 +285,7 @@ impl NonSortedNonUnique {
 }
 
 /// Owning structure of all precomputed data.
+#[derive(Serialize, Deserialize)]
 pub struct StaticCache {
     pub constant_time: ConstantTimeCache,
     pub linear_time: LinearTimeCache,
@@ -232,24 +293,32 @@ pub struct StaticCache {
 }
 
 impl StaticCache {
-    pub fn new(meets: &[Meet], entries: &[Entry]) -> StaticCache {
-        let loglin = LogLinearTimeCache::new(meets, entries);
+    pub fn new(
+        meets: &[Meet],
+        entries: &[Entry],
+        metafed_cache: &MetaFederationCache,
+    ) -> StaticCache {
+        let loglin = LogLinearTimeCache::new(entries);
 
         StaticCache {
             constant_time: ConstantTimeCache::new(&loglin, meets, entries),
-            linear_time: LinearTimeCache::new(),
+            linear_time: LinearTimeCache::new(meets, entries, metafed_cache),
             log_linear_time: loglin,
         }
     }
 }
 
 /// Stores all sorts for a given equipment type.
+#[derive(Serialize, Deserialize)]
 pub struct ConstantTimeBy {
     pub raw: SortedUnique,
     pub wraps: SortedUnique,
     pub raw_wraps: SortedUnique,
     pub single: SortedUnique,
     pub multi: SortedUnique,
+    pub straps: SortedUnique,
+    pub equipped: SortedUnique,
+    pub all: SortedUnique,
 }
 
 impl ConstantTimeBy {
@@ -272,11 +341,15 @@ impl ConstantTimeBy {
                 .sort_and_unique_by(ev, mv, compare, belongs),
             single: loglin.single.sort_and_unique_by(ev, mv, compare, belongs),
             multi: loglin.multi.sort_and_unique_by(ev, mv, compare, belongs),
+            straps: loglin.straps.sort_and_unique_by(ev, mv, compare, belongs),
+            equipped: loglin.equipped.sort_and_unique_by(ev, mv, compare, belongs),
+            all: loglin.all.sort_and_unique_by(ev, mv, compare, belongs),
         }
     }
 }
 
 /// Owning structure of all `O(1)` lookup data.
+#[derive(Serialize, Deserialize)]
 pub struct ConstantTimeCache {
     // Weight comparisons.
     pub squat: ConstantTimeBy,
@@ -284,11 +357,11 @@ pub struct ConstantTimeCache {
     pub deadlift: ConstantTimeBy,
     pub total: ConstantTimeBy,
 
-    // Points comparisons.
-    pub wilks: ConstantTimeBy,
-    pub mcculloch: ConstantTimeBy,
-    pub glossbrenner: ConstantTimeBy,
-    pub ipfpoints: ConstantTimeBy,
+    /// Points comparisons, in the order of `coefficients::stored_formulas()`.
+    ///
+    /// Only the stored systems are precomputed. Rankings by the others
+    /// are rare enough to be sorted when requested.
+    pub points: Vec<ConstantTimeBy>,
 }
 
 impl ConstantTimeCache {
@@ -308,42 +381,121 @@ impl ConstantTimeCache {
                 &filter_deadlift,
             ),
             total: ConstantTimeBy::new(loglin, mv, ev, &cmp_total, &filter_total),
-            wilks: ConstantTimeBy::new(loglin, mv, ev, &cmp_wilks, &filter_wilks),
-            mcculloch: ConstantTimeBy::new(
-                loglin,
-                mv,
-                ev,
-                &cmp_mcculloch,
-                &filter_mcculloch,
-            ),
-            glossbrenner: ConstantTimeBy::new(
-                loglin,
-                mv,
-                ev,
-                &cmp_glossbrenner,
-                &filter_glossbrenner,
-            ),
-            ipfpoints: ConstantTimeBy::new(
-                loglin,
-                mv,
-                ev,
-                &cmp_ipfpoints,
-                &filter_ipfpoints,
-            ),
+            points: coefficients::stored_formulas()
+                .map(|f| {
+                    let source = PointsSource::of(f.system());
+                    ConstantTimeBy::new(
+                        loglin,
+                        mv,
+                        ev,
+                        &|m: &[Meet], a: &Entry, b: &Entry| cmp_points(source, m, a, b),
+                        &|e: &Entry| filter_points(source, e),
+                    )
+                })
+                .collect(),
         }
     }
+
+    /// Gets the precomputed sorts for a points system, if it's stored.
+    pub fn by_points(&self, system: PointsSystem) -> Option<&ConstantTimeBy> {
+        self.points.get(coefficients::stored_index(system)?)
+    }
 }
 
 /// Owning structure of all `O(n)` lookup data.
-pub struct LinearTimeCache {}
+///
+/// Each column is indexed by value, so that any combination of values
+/// can be selected by intersecting bitsets, without examining entries.
+#[derive(Serialize, Deserialize)]
+pub struct LinearTimeCache {
+    pub equipment: ColumnIndex<Equipment>,
+    pub sex: ColumnIndex<Sex>,
+    pub federation: ColumnIndex<Federation>,
+    pub metafederation: ColumnIndex<MetaFederation>,
+    pub year: ColumnIndex<u32>,
+    pub ageclass: ColumnIndex<AgeClass>,
+    pub birthyearclass: ColumnIndex<BirthYearClass>,
+    pub event: ColumnIndex<Event>,
+    pub lifter_country: ColumnIndex<Option<Country>>,
+    pub meet_country: ColumnIndex<Country>,
+    pub tested: ColumnIndex<bool>,
+
+    /// Sets of MeetIDs, for meets with any tested entries
+    /// and for meets with any untested entries.
+    pub tested_meets: Bitset,
+    pub untested_meets: Bitset,
+}
 
 impl LinearTimeCache {
-    pub fn new() -> LinearTimeCache {
-        LinearTimeCache {}
+    pub fn new(
+        meets: &[Meet],
+        entries: &[Entry],
+        metafed_cache: &MetaFederationCache,
+    ) -> LinearTimeCache {
+        let meet = |e: &Entry| &meets[e.meet_id as usize];
+
+        let mut tested_meets = Bitset::empty(meets.len());
+        let mut untested_meets = Bitset::empty(meets.len());
+        for entry in entries {
+            if entry.tested {
+                tested_meets.insert(entry.meet_id);
+            } else {
+                untested_meets.insert(entry.meet_id);
+            }
+        }
+
+        LinearTimeCache {
+            equipment: ColumnIndex::new(entries, |e| e.equipment),
+            sex: ColumnIndex::new(entries, |e| e.sex),
+            federation: ColumnIndex::new(entries, |e| meet(e).federation),
+            metafederation: Self::index_metafederations(meets, entries, metafed_cache),
+            year: ColumnIndex::new(entries, |e| meet(e).date.year()),
+            ageclass: ColumnIndex::new(entries, |e| e.ageclass),
+            birthyearclass: ColumnIndex::new(entries, |e| e.birthyearclass),
+            event: ColumnIndex::new(entries, |e| e.event),
+            lifter_country: ColumnIndex::new(entries, |e| e.lifter_country),
+            meet_country: ColumnIndex::new(entries, |e| meet(e).country),
+            tested: ColumnIndex::new(entries, |e| e.tested),
+            tested_meets,
+            untested_meets,
+        }
+    }
+
+    /// Indexes the entries in each MetaFederation.
+    ///
+    /// Only entries from meets known to be in the MetaFederation are
+    /// considered, and they are only checked individually if membership
+    /// depends on the entry.
+    fn index_metafederations(
+        meets: &[Meet],
+        entries: &[Entry],
+        metafed_cache: &MetaFederationCache,
+    ) -> ColumnIndex<MetaFederation> {
+        let mut by_meet: Vec<Vec<u32>> = vec![vec![]; meets.len()];
+        for (i, entry) in entries.iter().enumerate() {
+            by_meet[entry.meet_id as usize].push(i as u32);
+        }
+
+        let lists = MetaFederation::iter()
+            .map(|meta| {
+                let list = metafed_cache
+                    .get_meet_ids_for(meta)
+                    .iter()
+                    .flat_map(|&meet_id| by_meet[meet_id as usize].iter().cloned())
+                    .filter(|&i| {
+                        meta.is_meet_level() || meta.contains(&entries[i as usize], meets)
+                    })
+                    .collect();
+                (meta, list)
+            })
+            .collect();
+
+        ColumnIndex::new_multi(entries.len(), lists)
     }
 }
 
 /// Owning structure of all `O(n log n)` lookup data.
+#[derive(Serialize, Deserialize)]
 pub struct LogLinearTimeCache {
     /// List of all non-DQ Raw entry indices by LifterID.
     pub raw: NonSortedNonUnique,
@@ -355,18 +507,12 @@ pub struct LogLinearTimeCache {
     pub single: NonSortedNonUnique,
     /// List of all non-DQ Multi-ply entry indices by LifterID.
     pub multi: NonSortedNonUnique,
-
-    /// List of all non-DQ Male entry indices by LifterID.
-    pub male: NonSortedNonUnique,
-    /// List of all non-DQ Female entry indices by LifterID.
-    pub female: NonSortedNonUnique,
-
-    pub year2019: NonSortedNonUnique,
-    pub year2018: NonSortedNonUnique,
-    pub year2017: NonSortedNonUnique,
-    pub year2016: NonSortedNonUnique,
-    pub year2015: NonSortedNonUnique,
-    pub year2014: NonSortedNonUnique,
+    /// List of all non-DQ Straps entry indices by LifterID.
+    pub straps: NonSortedNonUnique,
+    /// List of all non-DQ Single-ply+Multi-ply entry indices by LifterID.
+    pub equipped: NonSortedNonUnique,
+    /// List of all non-DQ entry indices by LifterID, in any equipment.
+    pub all: NonSortedNonUnique,
 }
 
 impl LogLinearTimeCache {
@@ -384,7 +530,7 @@ impl LogLinearTimeCache {
         NonSortedNonUnique(vec)
     }
 
-    pub fn new(meets: &[Meet], entries: &[Entry]) -> LogLinearTimeCache {
+    pub fn new(entries: &[Entry]) -> LogLinearTimeCache {
         LogLinearTimeCache {
             raw: Self::filter_entries(entries, |e| e.equipment == Equipment::Raw),
             wraps: Self::filter_entries(entries, |e| e.equipment == Equipment::Wraps),
@@ -393,28 +539,11 @@ impl LogLinearTimeCache {
             }),
             single: Self::filter_entries(entries, |e| e.equipment == Equipment::Single),
             multi: Self::filter_entries(entries, |e| e.equipment == Equipment::Multi),
-
-            male: Self::filter_entries(entries, |e| e.sex == Sex::M),
-            female: Self::filter_entries(entries, |e| e.sex == Sex::F),
-
-            year2019: Self::filter_entries(entries, |e| {
-                meets[e.meet_id as usize].date.year() == 2019
-            }),
-            year2018: Self::filter_entries(entries, |e| {
-                meets[e.meet_id as usize].date.year() == 2018
-            }),
-            year2017: Self::filter_entries(entries, |e| {
-                meets[e.meet_id as usize].date.year() == 2017
-            }),
-            year2016: Self::filter_entries(entries, |e| {
-                meets[e.meet_id as usize].date.year() == 2016
-            }),
-            year2015: Self::filter_entries(entries, |e| {
-                meets[e.meet_id as usize].date.year() == 2015
-            }),
-            year2014: Self::filter_entries(entries, |e| {
-                meets[e.meet_id as usize].date.year() == 2014
+            straps: Self::filter_entries(entries, |e| e.equipment == Equipment::Straps),
+            equipped: Self::filter_entries(entries, |e| {
+                e.equipment == Equipment::Single || e.equipment == Equipment::Multi
             }),
+            all: Self::filter_entries(entries, |_| true),
         }
     }
 }
@@ -0,0 +1,144 @@
+//! Weightclass schemes used by each federation.
+//!
+//! These come from the `[weightclasses]` tables in each federation's
+//! CONFIG.toml, which the checker writes out to `weightclasses.csv`.
+
+use csv;
+use opltypes::*;
+use serde::de::{self, Deserialize, Visitor};
+
+use std::error::Error;
+use std::fmt;
+
+/// A list of weightclasses used by a federation for a period of time.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WeightClassScheme {
+    #[serde(rename(deserialize = "Federation"))]
+    pub federation: Federation,
+    /// The name of the table in the CONFIG.toml, like "default_M".
+    #[serde(rename(deserialize = "Name"))]
+    pub name: String,
+    #[serde(rename(deserialize = "Sex"))]
+    pub sex: Sex,
+    /// The earliest date at which these weightclasses existed.
+    #[serde(rename(deserialize = "DateMin"))]
+    pub date_min: Date,
+    /// The last date at which these weightclasses existed.
+    #[serde(rename(deserialize = "DateMax"))]
+    pub date_max: Date,
+    #[serde(
+        rename(deserialize = "Classes"),
+        deserialize_with = "deserialize_classes"
+    )]
+    pub classes: Vec<WeightClassKg>,
+}
+
+impl WeightClassScheme {
+    /// Returns each class with its range of bodyweight, lightest first.
+    ///
+    /// The lower bound is exclusive, and the upper bound is inclusive,
+    /// as in `WeightClassSelection::to_bounds()`.
+    pub fn bounds(&self) -> Vec<(WeightClassKg, WeightKg, WeightKg)> {
+        let mut classes = self.classes.clone();
+        classes.sort();
+
+        let mut lower = WeightKg::from_i32(0);
+        let mut acc = Vec::with_capacity(classes.len());
+        for class in classes {
+            match class {
+                WeightClassKg::UnderOrEqual(upper) => {
+                    acc.push((class, lower, upper));
+                    lower = upper;
+                }
+                WeightClassKg::Over(over) => {
+                    acc.push((class, over, WeightKg::max_value()));
+                }
+                WeightClassKg::None => (),
+            }
+        }
+        acc
+    }
+}
+
+struct ClassesVisitor;
+
+impl<'de> Visitor<'de> for ClassesVisitor {
+    type Value = Vec<WeightClassKg>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a space-separated list of weightclasses")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Vec<WeightClassKg>, E>
+    where
+        E: de::Error,
+    {
+        value
+            .split_whitespace()
+            .map(|class| class.parse::<WeightClassKg>().map_err(E::custom))
+            .collect()
+    }
+}
+
+/// The CSV stores the classes as a single space-separated column.
+fn deserialize_classes<'de, D>(de: D) -> Result<Vec<WeightClassKg>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    if de.is_human_readable() {
+        de.deserialize_str(ClassesVisitor)
+    } else {
+        Vec::<WeightClassKg>::deserialize(de)
+    }
+}
+
+/// Reads the `weightclasses.csv` file into a Vec<WeightClassScheme>.
+pub fn import_weightclasses_csv(
+    file: &str,
+) -> Result<Vec<WeightClassScheme>, Box<dyn Error>> {
+    let mut vec = Vec::new();
+
+    let mut rdr = csv::Reader::from_path(file)?;
+    for scheme in rdr.deserialize() {
+        let scheme: WeightClassScheme = scheme?;
+        vec.push(scheme);
+    }
+
+    vec.shrink_to_fit();
+    Ok(vec)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scheme_bounds() {
+        let kg = WeightKg::from_f32;
+        let scheme = WeightClassScheme {
+            federation: Federation::USPA,
+            name: "default_F".to_string(),
+            sex: Sex::F,
+            date_min: "1900-01-01".parse::<Date>().unwrap(),
+            date_max: "9999-01-01".parse::<Date>().unwrap(),
+            classes: vec![
+                WeightClassKg::UnderOrEqual(kg(52.0)),
+                WeightClassKg::Over(kg(90.0)),
+                WeightClassKg::UnderOrEqual(kg(44.0)),
+                WeightClassKg::UnderOrEqual(kg(90.0)),
+            ],
+        };
+
+        let bounds: Vec<(WeightKg, WeightKg)> =
+            scheme.bounds().iter().map(|b| (b.1, b.2)).collect();
+        assert_eq!(
+            bounds,
+            vec![
+                (kg(0.0), kg(44.0)),
+                (kg(44.0), kg(52.0)),
+                (kg(52.0), kg(90.0)),
+                (kg(90.0), WeightKg::max_value()),
+            ]
+        );
+    }
+}
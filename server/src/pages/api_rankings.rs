@@ -24,9 +24,9 @@ pub fn get_slice<'db>(
     const ROW_LIMIT: usize = 100;
     let mut end_row = end_row;
 
-    // TODO: Use a better algorithm, don't generate everything.
-    let list = algorithms::get_full_sorted_uniqued(selection, opldb);
-    let total_length = list.0.len();
+    let rankings = algorithms::get_cached_sorted_uniqued(selection, opldb);
+    let list = rankings.list.indices();
+    let total_length = list.len();
 
     // The request must be in-bounds.
     if end_row >= total_length {
@@ -49,7 +49,7 @@ pub fn get_slice<'db>(
         end_row = start_row + (ROW_LIMIT - 1);
     }
 
-    let rows: Vec<JsEntryRow> = list.0[start_row..(end_row + 1)]
+    let rows: Vec<JsEntryRow> = list[start_row..(end_row + 1)]
         .iter()
         .zip(start_row..)
         .map(|(&n, i)| {
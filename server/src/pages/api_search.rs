@@ -40,17 +40,28 @@ pub fn search_rankings<'db>(
         return SearchRankingsResult { next_index: None };
     }
 
-    // TODO: Use a better algorithm, don't generate everything.
-    let list = algorithms::get_full_sorted_uniqued(selection, opldb);
+    let rankings = algorithms::get_cached_sorted_uniqued(selection, opldb);
+    let list = rankings.list.indices();
 
     // Handle out-of-bounds requests.
-    if start_row >= list.0.len() {
+    if start_row >= list.len() {
         return SearchRankingsResult { next_index: None };
     }
 
-    // TODO: Use a better algorithm; this is really a MVP.
-    for i in start_row..list.0.len() {
-        let entry = opldb.get_entry(list.0[i]);
+    // Usernames that begin with the query, including exact matches,
+    // are found using the index.
+    //
+    // Matching the query elsewhere in a username or in an Instagram handle
+    // isn't indexed, so that remains a linear scan. It only needs to cover
+    // the rows before the indexed match, which is usually close by.
+    let prefix_match: Option<usize> = [&normalized, &backwards]
+        .iter()
+        .filter(|q| !q.is_empty())
+        .filter_map(|q| rankings.first_with_prefix(opldb, q, start_row))
+        .min();
+
+    for i in start_row..prefix_match.unwrap_or(list.len()) {
+        let entry = opldb.get_entry(list[i]);
         let lifter = opldb.get_lifter(entry.lifter_id);
 
         if lifter.username.contains(&normalized)
@@ -66,5 +77,46 @@ pub fn search_rankings<'db>(
         }
     }
 
-    SearchRankingsResult { next_index: None }
+    SearchRankingsResult {
+        next_index: prefix_match,
+    }
+}
+
+/// The maximum number of lifters returned by the /api/search/lifters endpoint.
+pub const SEARCH_LIFTERS_LIMIT: usize = 20;
+
+/// A single lifter in the /api/search/lifters results.
+#[derive(Serialize)]
+pub struct SearchLifterResult<'db> {
+    pub username: &'db str,
+    pub name: &'db str,
+    pub cyrillic_name: &'db Option<String>,
+    pub instagram: &'db Option<String>,
+}
+
+/// JSON return from the /api/search/lifters endpoint.
+#[derive(Serialize)]
+pub struct SearchLiftersResult<'db> {
+    /// Matching lifters, best match first.
+    pub lifters: Vec<SearchLifterResult<'db>>,
+}
+
+/// Searches all lifters by name, Cyrillic name, and Instagram handle.
+pub fn search_lifters<'db>(opldb: &'db OplDb, query: &str) -> SearchLiftersResult<'db> {
+    let lifter_ids = opldb.get_lifter_index().search(query, SEARCH_LIFTERS_LIMIT);
+
+    let lifters = lifter_ids
+        .into_iter()
+        .map(|id| {
+            let lifter = opldb.get_lifter(id);
+            SearchLifterResult {
+                username: &lifter.username,
+                name: &lifter.name,
+                cyrillic_name: &lifter.cyrillic_name,
+                instagram: &lifter.instagram,
+            }
+        })
+        .collect();
+
+    SearchLiftersResult { lifters }
 }
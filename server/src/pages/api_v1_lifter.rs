@@ -0,0 +1,91 @@
+//! Implements the public /api/v1/lifters endpoint and the lifter CSV export.
+//!
+//! The output format is stable: see `pages::apidata`.
+
+use csv;
+
+use std::error::Error;
+
+use crate::opldb::OplDb;
+use crate::pages::apidata::{ApiEntry, ApiPersonalBests};
+use crate::pages::lifter::calculate_personal_bests;
+
+/// A single lifter with their full competition history.
+#[derive(Serialize)]
+pub struct ApiLifter<'db> {
+    pub lifter_id: u32,
+    pub name: &'db str,
+    pub cyrillic_name: &'db Option<String>,
+    pub username: &'db str,
+    pub instagram: &'db Option<String>,
+    pub vkontakte: &'db Option<String>,
+
+    pub personal_bests: Vec<ApiPersonalBests>,
+
+    /// Every entry for the lifter, oldest first.
+    pub entries: Vec<ApiEntry<'db>>,
+}
+
+impl<'db> ApiLifter<'db> {
+    pub fn from(opldb: &'db OplDb, lifter_id: u32) -> ApiLifter<'db> {
+        let lifter = opldb.get_lifter(lifter_id);
+
+        let mut entries = opldb.get_entries_for_lifter(lifter_id);
+        entries.sort_unstable_by_key(|e| &opldb.get_meet(e.meet_id).date);
+
+        ApiLifter {
+            lifter_id,
+            name: &lifter.name,
+            cyrillic_name: &lifter.cyrillic_name,
+            username: &lifter.username,
+            instagram: &lifter.instagram,
+            vkontakte: &lifter.vkontakte,
+
+            personal_bests: calculate_personal_bests(&entries)
+                .into_iter()
+                .map(ApiPersonalBests::from)
+                .collect(),
+
+            entries: entries
+                .into_iter()
+                .map(|e| ApiEntry::from(opldb, e))
+                .collect(),
+        }
+    }
+}
+
+/// JSON return from the /api/v1/lifters/ endpoint.
+#[derive(Serialize)]
+pub struct ApiLifters<'db> {
+    /// True if the username referred to more than one lifter,
+    /// each of which is then listed in `lifters`.
+    pub disambiguation: bool,
+    pub lifters: Vec<ApiLifter<'db>>,
+}
+
+/// Looks up the lifters referred to by the `lifter_ids`,
+/// as returned by `OplDb::get_lifter_ids_for_username()`.
+pub fn get_lifters<'db>(opldb: &'db OplDb, lifter_ids: &[u32]) -> ApiLifters<'db> {
+    ApiLifters {
+        disambiguation: lifter_ids.len() > 1,
+        lifters: lifter_ids
+            .iter()
+            .map(|&id| ApiLifter::from(opldb, id))
+            .collect(),
+    }
+}
+
+/// Exports every entry for the given lifters as CSV, with one row per entry.
+///
+/// Ambiguous lifters are distinguished by the "username" column.
+pub fn export_csv(opldb: &OplDb, lifter_ids: &[u32]) -> Result<String, Box<dyn Error>> {
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    for &lifter_id in lifter_ids {
+        let mut entries = opldb.get_entries_for_lifter(lifter_id);
+        entries.sort_unstable_by_key(|e| &opldb.get_meet(e.meet_id).date);
+        for entry in entries {
+            wtr.serialize(ApiEntry::from(opldb, entry))?;
+        }
+    }
+    Ok(String::from_utf8(wtr.into_inner()?)?)
+}
@@ -0,0 +1,94 @@
+//! Implements the public /api/v1/meets endpoint and the meet CSV export.
+//!
+//! The output format is stable: see `pages::apidata`.
+
+use csv;
+
+use std::error::Error;
+
+use crate::opldb::{Entry, OplDb};
+use crate::pages::apidata::{ApiEntry, ApiMeetInfo};
+use crate::pages::meet::{self, MeetSortSelection};
+
+/// A single row in the meet results.
+#[derive(Serialize)]
+pub struct ApiMeetRow<'db> {
+    /// The 1-based rank by points, or `None` if grouped by division.
+    pub rank: Option<usize>,
+    #[serde(flatten)]
+    pub entry: ApiEntry<'db>,
+}
+
+/// A group of rows, like a single division.
+#[derive(Serialize)]
+pub struct ApiMeetGroup<'db> {
+    pub rows: Vec<ApiMeetRow<'db>>,
+}
+
+/// JSON return from the /api/v1/meets/ endpoint.
+#[derive(Serialize)]
+pub struct ApiMeet<'db> {
+    pub meet: ApiMeetInfo<'db>,
+    pub groups: Vec<ApiMeetGroup<'db>>,
+}
+
+/// Groups the entries the same way as the meet page, for the given sort.
+///
+/// Also returns whether the groups are ranked by points.
+fn group_entries<'db>(
+    opldb: &'db OplDb,
+    meet_id: u32,
+    sort: MeetSortSelection,
+) -> (Vec<Vec<&'db Entry>>, bool) {
+    let meet = opldb.get_meet(meet_id);
+    let default_points = meet.federation.default_points(meet.date);
+
+    match sort.points_system(default_points) {
+        Some(system) => (vec![meet::sort_by_points(opldb, system, meet_id)], true),
+        None => (meet::group_by_division(opldb, meet_id, meet.ruleset), false),
+    }
+}
+
+pub fn get_meet<'db>(
+    opldb: &'db OplDb,
+    meet_id: u32,
+    sort: MeetSortSelection,
+) -> ApiMeet<'db> {
+    let (groups, ranked) = group_entries(opldb, meet_id, sort);
+
+    let groups = groups
+        .into_iter()
+        .map(|group| ApiMeetGroup {
+            rows: group
+                .into_iter()
+                .zip(1..)
+                .map(|(e, i)| ApiMeetRow {
+                    rank: if ranked { Some(i) } else { None },
+                    entry: ApiEntry::from(opldb, e),
+                })
+                .collect(),
+        })
+        .collect();
+
+    ApiMeet {
+        meet: ApiMeetInfo::from(opldb.get_meet(meet_id)),
+        groups,
+    }
+}
+
+/// Exports the meet results as CSV, with one row per entry.
+///
+/// Rows are in the same order as in the JSON groups.
+pub fn export_csv(
+    opldb: &OplDb,
+    meet_id: u32,
+    sort: MeetSortSelection,
+) -> Result<String, Box<dyn Error>> {
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    for group in group_entries(opldb, meet_id, sort).0 {
+        for entry in group {
+            wtr.serialize(ApiEntry::from(opldb, entry))?;
+        }
+    }
+    Ok(String::from_utf8(wtr.into_inner()?)?)
+}
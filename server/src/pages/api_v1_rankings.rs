@@ -0,0 +1,98 @@
+//! Implements the public /api/v1/rankings endpoint.
+//!
+//! The output format is stable: see `pages::apidata`.
+
+use opltypes::*;
+
+use crate::opldb::{algorithms, Entry, OplDb};
+use crate::pages::apidata::ApiEntry;
+use crate::pages::selection::{Selection, SortSelection};
+
+/// The number of rows returned if the request doesn't specify a limit.
+pub const DEFAULT_LIMIT: usize = 100;
+
+/// The maximum number of rows that may be requested at once.
+pub const MAX_LIMIT: usize = 1000;
+
+/// A single row in the rankings.
+#[derive(Serialize)]
+pub struct ApiRankingsRow<'db> {
+    /// The 1-based position of this row in the full rankings.
+    pub rank: usize,
+    /// The points the rankings are sorted by, if sorted by points.
+    pub points: Option<Points>,
+    /// The lift divided by bodyweight, if sorted by a ratio.
+    pub bodyweight_ratio: Option<Points>,
+    #[serde(flatten)]
+    pub entry: ApiEntry<'db>,
+}
+
+impl<'db> ApiRankingsRow<'db> {
+    pub fn new(
+        opldb: &'db OplDb,
+        entry: &'db Entry,
+        rank: usize,
+        sort: SortSelection,
+    ) -> ApiRankingsRow<'db> {
+        let ratio = |weight: WeightKg| Some(entry.bodyweight_ratio(weight));
+        let (points, bodyweight_ratio) = match sort {
+            SortSelection::BySquat
+            | SortSelection::ByBench
+            | SortSelection::ByDeadlift
+            | SortSelection::ByTotal => (None, None),
+            SortSelection::ByPoints(system) => (Some(entry.points(system)), None),
+            SortSelection::BySquatRatio => (None, ratio(entry.highest_squatkg())),
+            SortSelection::ByBenchRatio => (None, ratio(entry.highest_benchkg())),
+            SortSelection::ByDeadliftRatio => (None, ratio(entry.highest_deadliftkg())),
+            SortSelection::ByTotalRatio => (None, ratio(entry.totalkg)),
+        };
+
+        ApiRankingsRow {
+            rank,
+            points,
+            bodyweight_ratio,
+            entry: ApiEntry::from(opldb, entry),
+        }
+    }
+}
+
+/// JSON return from the /api/v1/rankings/ endpoint.
+#[derive(Serialize)]
+pub struct ApiRankings<'db> {
+    /// The total length of the full ranking (not the length of this page).
+    pub total_length: usize,
+    /// The 0-based index of the first row in this page.
+    ///
+    /// Past-the-end requests are clamped to the total length.
+    pub start: usize,
+    pub rows: Vec<ApiRankingsRow<'db>>,
+}
+
+pub fn get_page<'db>(
+    opldb: &'db OplDb,
+    selection: &Selection,
+    start: usize,
+    limit: usize,
+) -> ApiRankings<'db> {
+    let rankings = algorithms::get_cached_sorted_uniqued(selection, opldb);
+    let list = rankings.list.indices();
+    let total_length = list.len();
+
+    // Out-of-bounds requests are empty, rather than errors.
+    let start_row = start.min(total_length);
+    let end_row = start_row + limit.min(MAX_LIMIT).min(total_length - start_row);
+
+    let rows: Vec<ApiRankingsRow> = list[start_row..end_row]
+        .iter()
+        .zip(start_row..)
+        .map(|(&n, i)| {
+            ApiRankingsRow::new(opldb, opldb.get_entry(n), i + 1, selection.sort)
+        })
+        .collect();
+
+    ApiRankings {
+        total_length,
+        start: start_row,
+        rows,
+    }
+}
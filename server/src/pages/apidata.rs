@@ -0,0 +1,225 @@
+//! Types for the public, versioned JSON API under `/api/v1/`.
+//!
+//! Unlike the types in `jsdata`, which are a compact positional format
+//! for our own JS, these types are serialized with named fields and are
+//! never localized. Weights are always in kilograms, and numeric values
+//! are written as exact decimal strings, the same as in the CSV data files.
+//! Missing values are `null`.
+//!
+//! Fields may be added within a version, but existing fields must not be
+//! removed, renamed, or change meaning.
+
+use opltypes::*;
+
+use crate::opldb::{Entry, Meet, OplDb};
+use crate::pages::lifter::PersonalBests;
+
+/// Converts a zero weight, meaning "missing", to `None`.
+fn nonzero_kg(weight: WeightKg) -> Option<WeightKg> {
+    if weight.is_zero() {
+        None
+    } else {
+        Some(weight)
+    }
+}
+
+/// Converts zero points, meaning "missing", to `None`.
+fn nonzero_points(points: Points) -> Option<Points> {
+    if points == Points::default() {
+        None
+    } else {
+        Some(points)
+    }
+}
+
+/// Information about a single Meet.
+#[derive(Serialize)]
+pub struct ApiMeetInfo<'db> {
+    pub meet_path: &'db str,
+    pub federation: Federation,
+    pub date: Date,
+    pub meet_country: Country,
+    pub meet_state: &'db Option<String>,
+    pub meet_town: &'db Option<String>,
+    pub meet_name: &'db str,
+    pub num_unique_lifters: u32,
+}
+
+impl<'db> ApiMeetInfo<'db> {
+    pub fn from(meet: &'db Meet) -> ApiMeetInfo<'db> {
+        ApiMeetInfo {
+            meet_path: &meet.path,
+            federation: meet.federation,
+            date: meet.date,
+            meet_country: meet.country,
+            meet_state: &meet.state,
+            meet_town: &meet.town,
+            meet_name: &meet.name,
+            num_unique_lifters: meet.num_unique_lifters,
+        }
+    }
+}
+
+/// A single Entry with its Lifter and Meet information.
+#[derive(Serialize)]
+pub struct ApiEntry<'db> {
+    pub lifter_id: u32,
+    pub name: &'db str,
+    pub username: &'db str,
+
+    pub meet_id: u32,
+    pub meet_path: &'db str,
+    pub federation: Federation,
+    pub date: Date,
+    pub meet_country: Country,
+    pub meet_state: &'db Option<String>,
+    pub meet_name: &'db str,
+
+    pub sex: Sex,
+    pub event: Event,
+    pub equipment: Equipment,
+    pub age: Option<Age>,
+    pub ageclass: Option<AgeClass>,
+    pub birthyearclass: Option<BirthYearClass>,
+    pub division: &'db Option<String>,
+    pub bodyweightkg: Option<WeightKg>,
+    pub weightclasskg: Option<WeightClassKg>,
+
+    // Attempts, with failed attempts as negative numbers.
+    // Remember that federations might only report bests!
+    pub squat1kg: Option<WeightKg>,
+    pub squat2kg: Option<WeightKg>,
+    pub squat3kg: Option<WeightKg>,
+    pub squat4kg: Option<WeightKg>,
+    pub best3squatkg: Option<WeightKg>,
+    pub bench1kg: Option<WeightKg>,
+    pub bench2kg: Option<WeightKg>,
+    pub bench3kg: Option<WeightKg>,
+    pub bench4kg: Option<WeightKg>,
+    pub best3benchkg: Option<WeightKg>,
+    pub deadlift1kg: Option<WeightKg>,
+    pub deadlift2kg: Option<WeightKg>,
+    pub deadlift3kg: Option<WeightKg>,
+    pub deadlift4kg: Option<WeightKg>,
+    pub best3deadliftkg: Option<WeightKg>,
+
+    /// The highest successful squat, including fourth attempts.
+    pub squatkg: Option<WeightKg>,
+    /// The highest successful bench, including fourth attempts.
+    pub benchkg: Option<WeightKg>,
+    /// The highest successful deadlift, including fourth attempts.
+    pub deadliftkg: Option<WeightKg>,
+    pub totalkg: Option<WeightKg>,
+    pub place: String,
+
+    pub wilks: Option<Points>,
+    pub mcculloch: Option<Points>,
+    pub glossbrenner: Option<Points>,
+    pub ipfpoints: Option<Points>,
+    pub dots: Option<Points>,
+    pub goodlift: Option<Points>,
+
+    pub tested: bool,
+    pub lifter_country: Option<Country>,
+}
+
+impl<'db> ApiEntry<'db> {
+    pub fn from(opldb: &'db OplDb, entry: &'db Entry) -> ApiEntry<'db> {
+        let meet = opldb.get_meet(entry.meet_id);
+        let lifter = opldb.get_lifter(entry.lifter_id);
+
+        ApiEntry {
+            lifter_id: entry.lifter_id,
+            name: &lifter.name,
+            username: &lifter.username,
+
+            meet_id: entry.meet_id,
+            meet_path: &meet.path,
+            federation: meet.federation,
+            date: meet.date,
+            meet_country: meet.country,
+            meet_state: &meet.state,
+            meet_name: &meet.name,
+
+            sex: entry.sex,
+            event: entry.event,
+            equipment: entry.equipment,
+            age: match entry.age {
+                Age::None => None,
+                age => Some(age),
+            },
+            ageclass: match entry.ageclass {
+                AgeClass::None => None,
+                class => Some(class),
+            },
+            birthyearclass: match entry.birthyearclass {
+                BirthYearClass::None => None,
+                class => Some(class),
+            },
+            division: &entry.division,
+            bodyweightkg: nonzero_kg(entry.bodyweightkg),
+            weightclasskg: match entry.weightclasskg {
+                WeightClassKg::None => None,
+                class => Some(class),
+            },
+
+            squat1kg: nonzero_kg(entry.squat1kg),
+            squat2kg: nonzero_kg(entry.squat2kg),
+            squat3kg: nonzero_kg(entry.squat3kg),
+            squat4kg: nonzero_kg(entry.squat4kg),
+            best3squatkg: nonzero_kg(entry.best3squatkg),
+            bench1kg: nonzero_kg(entry.bench1kg),
+            bench2kg: nonzero_kg(entry.bench2kg),
+            bench3kg: nonzero_kg(entry.bench3kg),
+            bench4kg: nonzero_kg(entry.bench4kg),
+            best3benchkg: nonzero_kg(entry.best3benchkg),
+            deadlift1kg: nonzero_kg(entry.deadlift1kg),
+            deadlift2kg: nonzero_kg(entry.deadlift2kg),
+            deadlift3kg: nonzero_kg(entry.deadlift3kg),
+            deadlift4kg: nonzero_kg(entry.deadlift4kg),
+            best3deadliftkg: nonzero_kg(entry.best3deadliftkg),
+
+            squatkg: nonzero_kg(entry.highest_squatkg()),
+            benchkg: nonzero_kg(entry.highest_benchkg()),
+            deadliftkg: nonzero_kg(entry.highest_deadliftkg()),
+            totalkg: nonzero_kg(entry.totalkg),
+            place: format!("{}", entry.place),
+
+            wilks: nonzero_points(entry.points(PointsSystem::Wilks)),
+            mcculloch: nonzero_points(entry.points(PointsSystem::McCulloch)),
+            glossbrenner: nonzero_points(entry.points(PointsSystem::Glossbrenner)),
+            ipfpoints: nonzero_points(entry.points(PointsSystem::IPFPoints)),
+            dots: nonzero_points(entry.points(PointsSystem::Dots)),
+            goodlift: nonzero_points(entry.points(PointsSystem::Goodlift)),
+
+            tested: entry.tested,
+            lifter_country: entry.lifter_country,
+        }
+    }
+}
+
+/// A lifter's personal bests in a single kind of equipment.
+#[derive(Serialize)]
+pub struct ApiPersonalBests {
+    pub equipment: Equipment,
+    pub squatkg: Option<WeightKg>,
+    pub benchkg: Option<WeightKg>,
+    pub deadliftkg: Option<WeightKg>,
+    pub totalkg: Option<WeightKg>,
+    pub wilks: Option<Points>,
+    pub ipfpoints: Option<Points>,
+}
+
+impl From<PersonalBests> for ApiPersonalBests {
+    fn from(bests: PersonalBests) -> ApiPersonalBests {
+        ApiPersonalBests {
+            equipment: bests.equipment,
+            squatkg: bests.squat.and_then(nonzero_kg),
+            benchkg: bests.bench.and_then(nonzero_kg),
+            deadliftkg: bests.deadlift.and_then(nonzero_kg),
+            totalkg: bests.total.and_then(nonzero_kg),
+            wilks: bests.wilks.and_then(nonzero_points),
+            ipfpoints: bests.ipfpoints.and_then(nonzero_points),
+        }
+    }
+}
@@ -0,0 +1,149 @@
+//! Logic for the points calculator page and its API.
+
+use coefficients::{self, PointsInput};
+use opltypes::*;
+
+use crate::langpack;
+
+/// The context object passed to `templates/calculator.html.tera`
+#[derive(Serialize)]
+pub struct Context<'a> {
+    pub urlprefix: &'static str,
+    pub page_title: &'a str,
+    pub language: langpack::Language,
+    pub strings: &'a langpack::Translations,
+    pub units: opltypes::WeightUnits,
+
+    /// Every points system that can be chosen as a target.
+    pub systems: Vec<SystemOption>,
+}
+
+/// A points system in the target selector.
+#[derive(Serialize)]
+pub struct SystemOption {
+    pub slug: &'static str,
+    pub name: &'static str,
+}
+
+impl<'a> Context<'a> {
+    pub fn new(locale: &'a langpack::Locale) -> Context<'a> {
+        Context {
+            urlprefix: "/",
+            page_title: &locale.strings.calculator_page.title,
+            strings: locale.strings,
+            language: locale.language,
+            units: locale.units,
+            systems: coefficients::FORMULAS
+                .iter()
+                .map(|f| SystemOption {
+                    slug: f.slug(),
+                    name: f.name(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The unvalidated inputs to the calculator, as given in the query string.
+///
+/// Weights are always in kilograms.
+pub struct Query<'a> {
+    pub sex: &'a str,
+    pub bodyweight: &'a str,
+    pub total: &'a str,
+
+    /// Defaults to Raw.
+    pub equipment: Option<&'a str>,
+    /// Defaults to SBD.
+    pub event: Option<&'a str>,
+    pub age: Option<&'a str>,
+
+    /// A score to reach, like "450".
+    pub target: Option<&'a str>,
+    /// The slug of the system the target is in, like "dots".
+    pub system: Option<&'a str>,
+}
+
+/// The points for the inputs in a single system.
+#[derive(Serialize)]
+pub struct SystemPoints {
+    pub system: &'static str,
+    pub slug: &'static str,
+    /// Zero if the system doesn't apply to the inputs.
+    pub points: Points,
+}
+
+/// How the lifter could reach the target score.
+#[derive(Serialize)]
+pub struct TargetResult {
+    pub system: &'static str,
+    pub slug: &'static str,
+    pub target: Points,
+    /// The lightest total reaching the target at the current bodyweight.
+    pub totalkg: Option<WeightKg>,
+    /// The heaviest bodyweight at which the current total reaches the target.
+    pub max_bodyweightkg: Option<WeightKg>,
+}
+
+/// The response of the calculator API.
+#[derive(Serialize)]
+pub struct Calculation {
+    pub points: Vec<SystemPoints>,
+    pub target: Option<TargetResult>,
+}
+
+/// Parses an optional query parameter, treating absence as the default.
+fn parse_or<T: std::str::FromStr>(s: Option<&str>, default: T) -> Option<T> {
+    match s {
+        Some(s) => s.parse::<T>().ok(),
+        None => Some(default),
+    }
+}
+
+/// Calculates every points system for the inputs.
+///
+/// Returns `None` if the inputs don't parse.
+pub fn calculate(query: &Query) -> Option<Calculation> {
+    let input = PointsInput {
+        sex: query.sex.parse::<Sex>().ok()?,
+        equipment: parse_or(query.equipment, Equipment::Raw)?,
+        event: parse_or(query.event, Event::sbd())?,
+        bodyweightkg: query.bodyweight.parse::<WeightKg>().ok()?,
+        totalkg: query.total.parse::<WeightKg>().ok()?,
+        age: parse_or(query.age, Age::None)?,
+    };
+    if input.bodyweightkg <= WeightKg::from_i32(0)
+        || input.totalkg < WeightKg::from_i32(0)
+    {
+        return None;
+    }
+
+    let points = coefficients::FORMULAS
+        .iter()
+        .map(|f| SystemPoints {
+            system: f.name(),
+            slug: f.slug(),
+            points: coefficients::points(f.system(), &input),
+        })
+        .collect();
+
+    let target = match (query.target, query.system) {
+        (Some(target), Some(slug)) => {
+            let formula = coefficients::formula_by_slug(slug)?;
+            let target = target.parse::<Points>().ok()?;
+            Some(TargetResult {
+                system: formula.name(),
+                slug: formula.slug(),
+                target,
+                totalkg: coefficients::total_for_points(formula, &input, target),
+                max_bodyweightkg: coefficients::max_bodyweight_for_points(
+                    formula, &input, target,
+                ),
+            })
+        }
+        (None, None) => None,
+        _ => return None,
+    };
+
+    Some(Calculation { points, target })
+}
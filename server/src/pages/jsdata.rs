@@ -135,13 +135,24 @@ impl<'db> JsEntryRow<'db> {
                 SortSelection::BySquat
                 | SortSelection::ByBench
                 | SortSelection::ByDeadlift
-                | SortSelection::ByTotal
-                | SortSelection::ByWilks => entry.wilks.in_format(number_format),
-                SortSelection::ByMcCulloch => entry.mcculloch.in_format(number_format),
-                SortSelection::ByGlossbrenner => {
-                    entry.glossbrenner.in_format(number_format)
+                | SortSelection::ByTotal => {
+                    entry.points(PointsSystem::Wilks).in_format(number_format)
                 }
-                SortSelection::ByIPFPoints => entry.ipfpoints.in_format(number_format),
+                SortSelection::ByPoints(system) => {
+                    entry.points(system).in_format(number_format)
+                }
+                SortSelection::BySquatRatio => entry
+                    .bodyweight_ratio(entry.highest_squatkg())
+                    .in_format(number_format),
+                SortSelection::ByBenchRatio => entry
+                    .bodyweight_ratio(entry.highest_benchkg())
+                    .in_format(number_format),
+                SortSelection::ByDeadliftRatio => entry
+                    .bodyweight_ratio(entry.highest_deadliftkg())
+                    .in_format(number_format),
+                SortSelection::ByTotalRatio => entry
+                    .bodyweight_ratio(entry.totalkg)
+                    .in_format(number_format),
             },
         }
     }
@@ -181,17 +181,50 @@ impl<'a> MeetResultsRow<'a> {
             deadlift3: entry.deadlift3kg.as_type(units).in_format(number_format),
             deadlift4: entry.deadlift4kg.as_type(units).in_format(number_format),
 
-            wilks: entry.wilks.in_format(number_format),
-            ipfpoints: entry.ipfpoints.in_format(number_format),
+            wilks: entry.points(PointsSystem::Wilks).in_format(number_format),
+            ipfpoints: entry
+                .points(PointsSystem::IPFPoints)
+                .in_format(number_format),
         }
     }
 }
 
-/// Helper function to isolate all the best-calculation logic.
+/// A lifter's personal bests in a single kind of equipment, in kilograms.
+#[derive(Debug, PartialEq)]
+pub struct PersonalBests {
+    pub equipment: Equipment,
+    pub squat: Option<WeightKg>,
+    pub bench: Option<WeightKg>,
+    pub deadlift: Option<WeightKg>,
+    pub total: Option<WeightKg>,
+    pub wilks: Option<Points>,
+    pub ipfpoints: Option<Points>,
+}
+
+/// Helper function to localize the personal bests for display.
 fn calculate_bests<'db>(
     locale: &'db Locale,
     entries: &[&Entry],
 ) -> Vec<PersonalBestsRow<'db>> {
+    calculate_personal_bests(entries)
+        .into_iter()
+        .map(|b| {
+            PersonalBestsRow::new(
+                &locale,
+                locale.strings.translate_equipment(b.equipment),
+                b.squat,
+                b.bench,
+                b.deadlift,
+                b.total,
+                b.wilks,
+                b.ipfpoints,
+            )
+        })
+        .collect()
+}
+
+/// Helper function to isolate all the best-calculation logic.
+pub fn calculate_personal_bests(entries: &[&Entry]) -> Vec<PersonalBests> {
     // Best lifts must ignore disqualified entries.
     let non_dq: Vec<&&Entry> = entries.iter().filter(|e| !e.place.is_dq()).collect();
 
@@ -223,13 +256,13 @@ fn calculate_bests<'db>(
     let raw_wilks: Option<Points> = non_dq
         .iter()
         .filter(|e| e.event.is_full_power() && e.equipment == Equipment::Raw)
-        .map(|e| e.wilks)
+        .map(|e| e.points(PointsSystem::Wilks))
         .max();
 
     let raw_ipfpoints: Option<Points> = non_dq
         .iter()
         .filter(|e| e.event.is_full_power() && e.equipment == Equipment::Raw)
-        .map(|e| e.ipfpoints)
+        .map(|e| e.points(PointsSystem::IPFPoints))
         .max();
 
     let wraps_squat: Option<WeightKg> = non_dq
@@ -247,13 +280,49 @@ fn calculate_bests<'db>(
     let wraps_wilks: Option<Points> = non_dq
         .iter()
         .filter(|e| e.event.is_full_power() && e.equipment == Equipment::Wraps)
-        .map(|e| e.wilks)
+        .map(|e| e.points(PointsSystem::Wilks))
         .max();
 
     let wraps_ipfpoints: Option<Points> = non_dq
         .iter()
         .filter(|e| e.event.is_full_power() && e.equipment == Equipment::Wraps)
-        .map(|e| e.ipfpoints)
+        .map(|e| e.points(PointsSystem::IPFPoints))
+        .max();
+
+    let straps_squat: Option<WeightKg> = non_dq
+        .iter()
+        .filter(|e| e.equipment == Equipment::Straps)
+        .map(|e| e.highest_squatkg())
+        .max();
+
+    let straps_bench: Option<WeightKg> = non_dq
+        .iter()
+        .filter(|e| e.equipment == Equipment::Straps)
+        .map(|e| e.highest_benchkg())
+        .max();
+
+    let straps_deadlift: Option<WeightKg> = non_dq
+        .iter()
+        .filter(|e| e.equipment == Equipment::Straps)
+        .map(|e| e.highest_deadliftkg())
+        .max();
+
+    let straps_total: Option<WeightKg> = non_dq
+        .iter()
+        .filter(|e| e.event.is_full_power() && e.equipment == Equipment::Straps)
+        .map(|e| e.totalkg)
+        .max();
+
+    let straps_wilks: Option<Points> = non_dq
+        .iter()
+        .filter(|e| e.event.is_full_power() && e.equipment == Equipment::Straps)
+        .map(|e| e.points(PointsSystem::Wilks))
+        .max();
+
+    let straps_ipfpoints: Option<Points> = non_dq
+        .iter()
+        .filter(|e| e.event.is_full_power() && e.equipment == Equipment::Straps)
+        .map(|e| e.points(PointsSystem::IPFPoints))
         .max();
 
     let single_squat: Option<WeightKg> = non_dq
@@ -283,13 +352,13 @@ fn calculate_bests<'db>(
     let single_wilks: Option<Points> = non_dq
         .iter()
         .filter(|e| e.event.is_full_power() && e.equipment == Equipment::Single)
-        .map(|e| e.wilks)
+        .map(|e| e.points(PointsSystem::Wilks))
         .max();
 
     let single_ipfpoints: Option<Points> = non_dq
         .iter()
         .filter(|e| e.event.is_full_power() && e.equipment == Equipment::Single)
-        .map(|e| e.ipfpoints)
+        .map(|e| e.points(PointsSystem::IPFPoints))
         .max();
 
     let multi_squat: Option<WeightKg> = non_dq
@@ -319,45 +388,59 @@ fn calculate_bests<'db>(
     let multi_wilks: Option<Points> = non_dq
         .iter()
         .filter(|e| e.event.is_full_power() && e.equipment == Equipment::Multi)
-        .map(|e| e.wilks)
+        .map(|e| e.points(PointsSystem::Wilks))
         .max();
 
     let multi_ipfpoints: Option<Points> = non_dq
         .iter()
         .filter(|e| e.event.is_full_power() && e.equipment == Equipment::Multi)
-        .map(|e| e.ipfpoints)
+        .map(|e| e.points(PointsSystem::IPFPoints))
         .max();
 
-    let mut rows = Vec::with_capacity(4);
+    let mut rows = Vec::with_capacity(5);
 
     if raw_squat.is_some()
         || raw_bench.is_some()
         || raw_deadlift.is_some()
         || raw_total.is_some()
     {
-        rows.push(PersonalBestsRow::new(
-            &locale,
-            &locale.strings.equipment.raw,
-            raw_squat,
-            raw_bench,
-            raw_deadlift,
-            raw_total,
-            raw_wilks,
-            raw_ipfpoints,
-        ));
+        rows.push(PersonalBests {
+            equipment: Equipment::Raw,
+            squat: raw_squat,
+            bench: raw_bench,
+            deadlift: raw_deadlift,
+            total: raw_total,
+            wilks: raw_wilks,
+            ipfpoints: raw_ipfpoints,
+        });
     }
 
     if wraps_squat.is_some() || wraps_total.is_some() {
-        rows.push(PersonalBestsRow::new(
-            &locale,
-            &locale.strings.equipment.wraps,
-            wraps_squat,
-            None,
-            None,
-            wraps_total,
-            wraps_wilks,
-            wraps_ipfpoints,
-        ));
+        rows.push(PersonalBests {
+            equipment: Equipment::Wraps,
+            squat: wraps_squat,
+            bench: None,
+            deadlift: None,
+            total: wraps_total,
+            wilks: wraps_wilks,
+            ipfpoints: wraps_ipfpoints,
+        });
+    }
+
+    if straps_squat.is_some()
+        || straps_bench.is_some()
+        || straps_deadlift.is_some()
+        || straps_total.is_some()
+    {
+        rows.push(PersonalBests {
+            equipment: Equipment::Straps,
+            squat: straps_squat,
+            bench: straps_bench,
+            deadlift: straps_deadlift,
+            total: straps_total,
+            wilks: straps_wilks,
+            ipfpoints: straps_ipfpoints,
+        });
     }
 
     if single_squat.is_some()
@@ -365,16 +448,15 @@ fn calculate_bests<'db>(
         || single_deadlift.is_some()
         || single_total.is_some()
     {
-        rows.push(PersonalBestsRow::new(
-            &locale,
-            &locale.strings.equipment.single,
-            single_squat,
-            single_bench,
-            single_deadlift,
-            single_total,
-            single_wilks,
-            single_ipfpoints,
-        ));
+        rows.push(PersonalBests {
+            equipment: Equipment::Single,
+            squat: single_squat,
+            bench: single_bench,
+            deadlift: single_deadlift,
+            total: single_total,
+            wilks: single_wilks,
+            ipfpoints: single_ipfpoints,
+        });
     }
 
     if multi_squat.is_some()
@@ -382,16 +464,15 @@ fn calculate_bests<'db>(
         || multi_deadlift.is_some()
         || multi_total.is_some()
     {
-        rows.push(PersonalBestsRow::new(
-            &locale,
-            &locale.strings.equipment.multi,
-            multi_squat,
-            multi_bench,
-            multi_deadlift,
-            multi_total,
-            multi_wilks,
-            multi_ipfpoints,
-        ));
+        rows.push(PersonalBests {
+            equipment: Equipment::Multi,
+            squat: multi_squat,
+            bench: multi_bench,
+            deadlift: multi_deadlift,
+            total: multi_total,
+            wilks: multi_wilks,
+            ipfpoints: multi_ipfpoints,
+        });
     }
 
     rows
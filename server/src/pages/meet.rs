@@ -3,6 +3,7 @@
 use coefficients;
 use itertools::Itertools;
 use opltypes::*;
+use serde::{self, Serialize};
 
 use std::cmp;
 use std::str::FromStr;
@@ -29,11 +30,14 @@ pub struct Context<'db> {
     pub path_if_by_ah: String,
     pub path_if_by_division: String,
     pub path_if_by_glossbrenner: String,
+    pub path_if_by_goodlift: String,
     pub path_if_by_ipfpoints: String,
     pub path_if_by_nasa: String,
     pub path_if_by_reshel: String,
+    pub path_if_by_schwartzmalone: String,
     pub path_if_by_total: String,
     pub path_if_by_wilks: String,
+    pub path_if_by_wilks2020: String,
 
     /// True iff the meet reported any age data.
     pub has_age_data: bool,
@@ -51,16 +55,12 @@ pub struct Table<'db> {
 }
 
 /// A sort selection widget just for the meet page.
-#[derive(Copy, Clone, Debug, PartialEq, Serialize)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum MeetSortSelection {
-    ByAH,
     ByDivision,
-    ByGlossbrenner,
-    ByIPFPoints,
-    ByReshel,
-    ByNASA,
     ByTotal,
-    ByWilks,
+    /// Any points system known to the `coefficients` registry.
+    ByPoints(PointsSystem),
 
     /// Special value that resolves to one of the others after lookup.
     ByFederationDefault,
@@ -71,19 +71,58 @@ impl FromStr for MeetSortSelection {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
-            "by-ah" => Ok(MeetSortSelection::ByAH),
             "by-division" => Ok(MeetSortSelection::ByDivision),
-            "by-glossbrenner" => Ok(MeetSortSelection::ByGlossbrenner),
-            "by-ipf-points" => Ok(MeetSortSelection::ByIPFPoints),
-            "by-nasa" => Ok(MeetSortSelection::ByNASA),
-            "by-reshel" => Ok(MeetSortSelection::ByReshel),
             "by-total" => Ok(MeetSortSelection::ByTotal),
-            "by-wilks" => Ok(MeetSortSelection::ByWilks),
-            _ => Err(()),
+            // Anything else must be a points system, like "by-wilks".
+            _ => {
+                if !s.starts_with("by-") {
+                    return Err(());
+                }
+                coefficients::formula_by_slug(&s["by-".len()..])
+                    .map(|f| MeetSortSelection::ByPoints(f.system()))
+                    .ok_or(())
+            }
         }
     }
 }
 
+impl Serialize for MeetSortSelection {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        // Templates compare against the variant name, like "ByWilks",
+        // so points sorts are named after their PointsSystem.
+        match self {
+            MeetSortSelection::ByPoints(system) => {
+                serializer.collect_str(&format_args!("By{:?}", system))
+            }
+            _ => serializer.collect_str(&format_args!("{:?}", self)),
+        }
+    }
+}
+
+impl MeetSortSelection {
+    /// Returns the points system used to rank lifters, or `None` if the
+    /// results are grouped by division instead.
+    ///
+    /// The `default_points` is the federation's default for the meet.
+    pub fn points_system(self, default_points: PointsSystem) -> Option<PointsSystem> {
+        match self {
+            MeetSortSelection::ByDivision => None,
+            MeetSortSelection::ByTotal => Some(PointsSystem::Total),
+            MeetSortSelection::ByPoints(system) => Some(system),
+            MeetSortSelection::ByFederationDefault => Some(default_points),
+        }
+    }
+}
+
+/// Gets the title of the points column, localized where possible.
+fn points_column_title(strings: &langpack::Translations, system: PointsSystem) -> &str {
+    // FIXME: Total actually uses the meet default.
+    coefficients::formula(system).map_or("Points", |f| strings.columns.points_title(f))
+}
+
 #[derive(Serialize)]
 pub struct MeetInfo<'a> {
     pub path: &'a str,
@@ -179,31 +218,12 @@ impl<'a> ResultsRow<'a> {
                 .in_format(number_format),
             total: entry.totalkg.as_type(units).in_format(number_format),
             points: match points_system {
-                PointsSystem::AH => {
-                    let points =
-                        coefficients::ah(entry.sex, entry.bodyweightkg, entry.totalkg);
-                    points.in_format(number_format)
-                }
-                PointsSystem::Glossbrenner => entry.glossbrenner.in_format(number_format),
-                PointsSystem::IPFPoints => entry.ipfpoints.in_format(number_format),
-                PointsSystem::Reshel => {
-                    let points = coefficients::reshel(
-                        entry.sex,
-                        entry.bodyweightkg,
-                        entry.totalkg,
-                    );
-                    points.in_format(number_format)
-                }
-                PointsSystem::NASA => {
-                    let points = coefficients::nasa(entry.bodyweightkg, entry.totalkg);
-                    points.in_format(number_format)
-                }
                 PointsSystem::Total => entry
                     .totalkg
                     .as_type(units)
                     .as_points()
                     .in_format(number_format),
-                PointsSystem::Wilks => entry.wilks.in_format(number_format),
+                _ => entry.points(points_system).in_format(number_format),
             },
         }
     }
@@ -342,10 +362,8 @@ fn finish_table<'db>(
     locale: &'db Locale,
     points_system: PointsSystem,
     ruleset: RuleSet,
-    entries: &mut Vec<&'db Entry>,
+    entries: &[&'db Entry],
 ) -> Table<'db> {
-    entries.sort_unstable_by(|a, b| a.place.cmp(&b.place));
-
     let units = locale.units;
     let format = locale.number_format;
 
@@ -400,70 +418,76 @@ fn finish_table<'db>(
     Table { title, rows }
 }
 
-fn make_tables_by_division<'db>(
+/// Groups the entries of a meet into divisions, for the `ByDivision` display.
+///
+/// Within each division, entries are ordered by Place.
+pub fn group_by_division<'db>(
     opldb: &'db opldb::OplDb,
-    locale: &'db Locale,
-    points_system: PointsSystem,
     meet_id: u32,
     ruleset: RuleSet,
-) -> Vec<Table<'db>> {
+) -> Vec<Vec<&'db Entry>> {
     let mut entries = opldb.get_entries_for_meet(meet_id);
     if entries.is_empty() {
-        return vec![Table {
-            title: None,
-            rows: vec![],
-        }];
+        return vec![];
     }
 
-    // Sort each entry so that entries that should be in the same table
+    // Sort each entry so that entries that should be in the same group
     // appear next to each other in the vector.
     entries.sort_unstable_by(|a, b| cmp_by_group(ruleset, a, b));
 
     // Iterate over each entry, constructing a group.
-    let mut key_entry = &entries[0];
-    let mut group: Vec<&Entry> = Vec::new();
-    let mut tables: Vec<Table> = Vec::new();
+    let mut groups: Vec<Vec<&Entry>> = Vec::new();
+    let mut group: Vec<&Entry> = vec![entries[0]];
 
-    for entry in &entries {
+    for entry in entries.into_iter().skip(1) {
         // Keep batching entries that are in the same group.
-        if cmp_by_group(ruleset, entry, key_entry) == cmp::Ordering::Equal {
+        if cmp_by_group(ruleset, entry, group[0]) == cmp::Ordering::Equal {
             group.push(entry);
             continue;
         }
 
         // This entry isn't part of the old group.
-        // Finish the old group.
-        tables.push(finish_table(
-            &opldb,
-            &locale,
-            points_system,
-            ruleset,
-            &mut group,
-        ));
-
-        // Start a new group.
-        key_entry = &entry;
-        group.clear();
-        group.push(key_entry);
+        // Finish the old group and start a new one.
+        group.sort_unstable_by(|a, b| a.place.cmp(&b.place));
+        groups.push(group);
+        group = vec![entry];
     }
 
     // Wrap up the last batch.
-    tables.push(finish_table(
-        &opldb,
-        &locale,
-        points_system,
-        ruleset,
-        &mut group,
-    ));
-    tables
+    group.sort_unstable_by(|a, b| a.place.cmp(&b.place));
+    groups.push(group);
+    groups
 }
 
-fn make_tables_by_points<'db>(
+fn make_tables_by_division<'db>(
     opldb: &'db opldb::OplDb,
     locale: &'db Locale,
     points_system: PointsSystem,
     meet_id: u32,
+    ruleset: RuleSet,
 ) -> Vec<Table<'db>> {
+    let groups = group_by_division(opldb, meet_id, ruleset);
+    if groups.is_empty() {
+        return vec![Table {
+            title: None,
+            rows: vec![],
+        }];
+    }
+
+    groups
+        .iter()
+        .map(|group| finish_table(&opldb, &locale, points_system, ruleset, group))
+        .collect()
+}
+
+/// Orders the lifters of a meet by points, for the points-based displays.
+///
+/// Each lifter appears at most once.
+pub fn sort_by_points<'db>(
+    opldb: &'db opldb::OplDb,
+    points_system: PointsSystem,
+    meet_id: u32,
+) -> Vec<&'db Entry> {
     let meets = opldb.get_meets();
 
     // Display at most one entry for each lifter.
@@ -472,39 +496,41 @@ fn make_tables_by_points<'db>(
         .into_iter()
         .group_by(|e| e.lifter_id);
 
+    let wilks = opldb::PointsSource::of(PointsSystem::Wilks);
     let mut entries: Vec<&opldb::Entry> = groups
         .into_iter()
-        .map(|(_key, group)| group.max_by_key(|x| x.wilks).unwrap())
+        .map(|(_key, group)| group.max_by_key(|x| x.points_from(wilks)).unwrap())
         .collect();
 
-    // The points system to be used for display.
-    // The "Total" logic below changes this to select the federation default.
-    let mut display_points_system = points_system;
-
     match points_system {
-        PointsSystem::AH => {
-            entries.sort_unstable_by(|a, b| algorithms::cmp_ah(&meets, a, b));
-        }
-        PointsSystem::Glossbrenner => {
-            entries.sort_unstable_by(|a, b| algorithms::cmp_glossbrenner(&meets, a, b));
-        }
-        PointsSystem::IPFPoints => {
-            entries.sort_unstable_by(|a, b| algorithms::cmp_ipfpoints(&meets, a, b));
-        }
-        PointsSystem::Reshel => {
-            entries.sort_unstable_by(|a, b| algorithms::cmp_reshel(&meets, a, b));
+        PointsSystem::Total => {
+            entries.sort_unstable_by(|a, b| algorithms::cmp_total(&meets, a, b));
         }
-        PointsSystem::NASA => {
-            entries.sort_unstable_by(|a, b| algorithms::cmp_nasa(&meets, a, b));
+        _ => {
+            let source = opldb::PointsSource::of(points_system);
+            entries.sort_unstable_by(|a, b| algorithms::cmp_points(source, &meets, a, b));
         }
+    };
+
+    entries
+}
+
+fn make_tables_by_points<'db>(
+    opldb: &'db opldb::OplDb,
+    locale: &'db Locale,
+    points_system: PointsSystem,
+    meet_id: u32,
+) -> Vec<Table<'db>> {
+    let entries = sort_by_points(opldb, points_system, meet_id);
+
+    // The points system to be used for display.
+    // Sorting by Total displays the federation default instead.
+    let display_points_system = match points_system {
         PointsSystem::Total => {
-            entries.sort_unstable_by(|a, b| algorithms::cmp_total(&meets, a, b));
             let meet = opldb.get_meet(meet_id);
-            display_points_system = meet.federation.default_points(meet.date);
-        }
-        PointsSystem::Wilks => {
-            entries.sort_unstable_by(|a, b| algorithms::cmp_wilks(&meets, a, b));
+            meet.federation.default_points(meet.date)
         }
+        _ => points_system,
     };
 
     let rows: Vec<ResultsRow> = entries
@@ -526,96 +552,43 @@ impl<'db> Context<'db> {
         let meet = opldb.get_meet(meet_id);
         let default_points: PointsSystem = meet.federation.default_points(meet.date);
 
-        let tables: Vec<Table> = match sort {
-            MeetSortSelection::ByAH => {
-                make_tables_by_points(&opldb, &locale, PointsSystem::AH, meet_id)
+        let tables: Vec<Table> = match sort.points_system(default_points) {
+            Some(points_system) => {
+                make_tables_by_points(&opldb, &locale, points_system, meet_id)
             }
-            MeetSortSelection::ByDivision => make_tables_by_division(
+            None => make_tables_by_division(
                 &opldb,
                 &locale,
                 default_points,
                 meet_id,
                 meet.ruleset,
             ),
-            MeetSortSelection::ByGlossbrenner => make_tables_by_points(
-                &opldb,
-                &locale,
-                PointsSystem::Glossbrenner,
-                meet_id,
-            ),
-            MeetSortSelection::ByIPFPoints => {
-                make_tables_by_points(&opldb, &locale, PointsSystem::IPFPoints, meet_id)
-            }
-            MeetSortSelection::ByNASA => {
-                make_tables_by_points(&opldb, &locale, PointsSystem::NASA, meet_id)
-            }
-            MeetSortSelection::ByReshel => {
-                make_tables_by_points(&opldb, &locale, PointsSystem::Reshel, meet_id)
-            }
-            MeetSortSelection::ByTotal => {
-                make_tables_by_points(&opldb, &locale, PointsSystem::Total, meet_id)
-            }
-            MeetSortSelection::ByWilks => {
-                make_tables_by_points(&opldb, &locale, PointsSystem::Wilks, meet_id)
-            }
-            MeetSortSelection::ByFederationDefault => {
-                make_tables_by_points(&opldb, &locale, default_points, meet_id)
-            }
         };
 
-        let points_column_title = match sort {
-            MeetSortSelection::ByDivision | MeetSortSelection::ByFederationDefault => {
-                match default_points {
-                    PointsSystem::AH => "AH",
-                    PointsSystem::Glossbrenner => &locale.strings.columns.glossbrenner,
-                    PointsSystem::IPFPoints => &locale.strings.columns.ipfpoints,
-                    PointsSystem::NASA => "NASA",
-                    PointsSystem::Reshel => "Reshel",
-                    // FIXME: Total actually uses the meet default.
-                    PointsSystem::Total => "Points",
-                    PointsSystem::Wilks => &locale.strings.columns.wilks,
-                }
-            }
-            MeetSortSelection::ByAH => "AH",
-            MeetSortSelection::ByGlossbrenner => &locale.strings.columns.glossbrenner,
-            MeetSortSelection::ByIPFPoints => &locale.strings.columns.ipfpoints,
-            MeetSortSelection::ByNASA => "NASA",
-            MeetSortSelection::ByReshel => "Reshel",
-            // FIXME: Total actually uses the meet default.
-            MeetSortSelection::ByTotal => "Points",
-            MeetSortSelection::ByWilks => &locale.strings.columns.wilks,
-        };
+        let points_column_title = points_column_title(
+            locale.strings,
+            sort.points_system(default_points).unwrap_or(default_points),
+        );
 
         // Paths do not include the urlprefix, which defaults to "/".
-        let path_if_by_ah = match default_points {
-            PointsSystem::AH => format!("m/{}", meet.path),
-            _ => format!("m/{}/by-ah", meet.path),
+        let path_if_by = |system: PointsSystem| -> String {
+            match coefficients::formula(system) {
+                _ if system == default_points => format!("m/{}", meet.path),
+                Some(f) => format!("m/{}/by-{}", meet.path, f.slug()),
+                None => format!("m/{}/by-total", meet.path),
+            }
         };
+        let path_if_by_ah = path_if_by(PointsSystem::AH);
         let path_if_by_division = format!("m/{}/by-division", meet.path);
-        let path_if_by_glossbrenner = match default_points {
-            PointsSystem::Glossbrenner => format!("m/{}", meet.path),
-            _ => format!("m/{}/by-glossbrenner", meet.path),
-        };
-        let path_if_by_ipfpoints = match default_points {
-            PointsSystem::IPFPoints => format!("m/{}", meet.path),
-            _ => format!("m/{}/by-ipf-points", meet.path),
-        };
-        let path_if_by_nasa = match default_points {
-            PointsSystem::NASA => format!("m/{}", meet.path),
-            _ => format!("m/{}/by-nasa", meet.path),
-        };
-        let path_if_by_reshel = match default_points {
-            PointsSystem::Reshel => format!("m/{}", meet.path),
-            _ => format!("m/{}/by-reshel", meet.path),
-        };
-        let path_if_by_total = match default_points {
-            PointsSystem::NASA => format!("m/{}", meet.path),
-            _ => format!("m/{}/by-total", meet.path),
-        };
-        let path_if_by_wilks = match default_points {
-            PointsSystem::Wilks => format!("m/{}", meet.path),
-            _ => format!("m/{}/by-wilks", meet.path),
-        };
+        let path_if_by_glossbrenner = path_if_by(PointsSystem::Glossbrenner);
+        let path_if_by_goodlift = path_if_by(PointsSystem::Goodlift);
+        let path_if_by_ipfpoints = path_if_by(PointsSystem::IPFPoints);
+        let path_if_by_nasa = path_if_by(PointsSystem::NASA);
+        let path_if_by_reshel = path_if_by(PointsSystem::Reshel);
+        let path_if_by_schwartzmalone = path_if_by(PointsSystem::SchwartzMalone);
+        let path_if_by_total = path_if_by(PointsSystem::Total);
+        let path_if_by_wilks = path_if_by(PointsSystem::Wilks);
+        let path_if_by_wilks2020 = path_if_by(PointsSystem::Wilks2020);
 
         Context {
             urlprefix: "/",
@@ -625,23 +598,11 @@ impl<'db> Context<'db> {
             units: locale.units,
             points_column_title,
             sortselection: match sort {
-                MeetSortSelection::ByAH => MeetSortSelection::ByAH,
-                MeetSortSelection::ByDivision => MeetSortSelection::ByDivision,
-                MeetSortSelection::ByGlossbrenner => MeetSortSelection::ByGlossbrenner,
-                MeetSortSelection::ByIPFPoints => MeetSortSelection::ByIPFPoints,
-                MeetSortSelection::ByReshel => MeetSortSelection::ByReshel,
-                MeetSortSelection::ByNASA => MeetSortSelection::ByNASA,
-                MeetSortSelection::ByTotal => MeetSortSelection::ByTotal,
-                MeetSortSelection::ByWilks => MeetSortSelection::ByWilks,
                 MeetSortSelection::ByFederationDefault => match default_points {
-                    PointsSystem::AH => MeetSortSelection::ByAH,
-                    PointsSystem::Glossbrenner => MeetSortSelection::ByGlossbrenner,
-                    PointsSystem::IPFPoints => MeetSortSelection::ByIPFPoints,
-                    PointsSystem::Reshel => MeetSortSelection::ByReshel,
-                    PointsSystem::NASA => MeetSortSelection::ByNASA,
                     PointsSystem::Total => MeetSortSelection::ByTotal,
-                    PointsSystem::Wilks => MeetSortSelection::ByWilks,
+                    system => MeetSortSelection::ByPoints(system),
                 },
+                _ => sort,
             },
             meet: MeetInfo::from(&meet, locale.strings),
             has_age_data: true, // TODO: Maybe use again?
@@ -650,11 +611,14 @@ impl<'db> Context<'db> {
             path_if_by_ah,
             path_if_by_division,
             path_if_by_glossbrenner,
+            path_if_by_goodlift,
             path_if_by_ipfpoints,
             path_if_by_nasa,
             path_if_by_reshel,
+            path_if_by_schwartzmalone,
             path_if_by_total,
             path_if_by_wilks,
+            path_if_by_wilks2020,
         }
     }
 }
@@ -6,8 +6,8 @@ use std::ffi::OsStr;
 use std::path;
 
 use crate::langpack::{self, Language, Locale};
-use crate::opldb::{self, Meet};
-use crate::pages::selection::{FederationSelection, YearSelection};
+use crate::opldb::{self, Bitset, Meet};
+use crate::pages::selection::{FederationSelection, TestedSelection, YearSelection};
 
 /// Query selection descriptor, corresponding to HTML widgets.
 ///
@@ -18,6 +18,7 @@ use crate::pages::selection::{FederationSelection, YearSelection};
 pub struct MeetListSelection {
     pub federation: FederationSelection,
     pub year: YearSelection,
+    pub tested: TestedSelection,
 }
 
 impl Default for MeetListSelection {
@@ -25,6 +26,7 @@ impl Default for MeetListSelection {
         MeetListSelection {
             federation: FederationSelection::AllFederations,
             year: YearSelection::AllYears,
+            tested: TestedSelection::AllEntries,
         }
     }
 }
@@ -46,6 +48,7 @@ impl MeetListSelection {
         // Prevent fields from being overwritten or redundant.
         let mut parsed_federation: bool = false;
         let mut parsed_year: bool = false;
+        let mut parsed_tested: bool = false;
 
         // Iterate over each path component, attempting to determine
         // what kind of data it is.
@@ -67,6 +70,13 @@ impl MeetListSelection {
                 }
                 ret.year = y;
                 parsed_year = true;
+            // Check whether this is drug-testing information.
+            } else if let Ok(t) = segment.parse::<TestedSelection>() {
+                if parsed_tested {
+                    return Err(());
+                }
+                ret.tested = t;
+                parsed_tested = true;
             // Unknown string, therefore malformed URL.
             } else {
                 return Err(());
@@ -124,6 +134,7 @@ pub struct Context<'db> {
     pub units: WeightUnits,
 
     pub selection: &'db MeetListSelection,
+    pub years: &'db [u32],
     pub meets: Vec<MeetInfo<'db>>,
 
     /// Temporary crutch until we figure out how to show
@@ -142,42 +153,70 @@ impl<'db> Context<'db> {
 
         let year: Option<u32> = mselection.year.as_u32();
 
+        let linear_time = &opldb.get_static_cache().linear_time;
+        let tested_meets = match mselection.tested {
+            TestedSelection::AllEntries => None,
+            TestedSelection::Tested => Some(&linear_time.tested_meets),
+            TestedSelection::Untested => Some(&linear_time.untested_meets),
+        };
+
+        // Filters by everything except the federation.
+        let filter = |&(meet_id, m): &(u32, &Meet)| {
+            if let Some(year) = year {
+                if m.date.year() != year {
+                    return false;
+                }
+            }
+            tested_meets.map_or(true, |set| set.contains(meet_id))
+        };
+
         // TODO: Move this selection logic into the opldb.
         let mut meets: Vec<&Meet> = match mselection.federation {
             FederationSelection::AllFederations => opldb
                 .get_meets()
                 .iter()
-                .filter(|m| match year {
-                    Some(year) => m.date.year() == year,
-                    None => true,
-                })
+                .enumerate()
+                .map(|(i, m)| (i as u32, m))
+                .filter(filter)
+                .map(|(_, m)| m)
+                .collect(),
+            FederationSelection::One(fed) => opldb
+                .get_meets()
+                .iter()
+                .enumerate()
+                .map(|(i, m)| (i as u32, m))
+                .filter(|&(_, m)| m.federation == fed)
+                .filter(filter)
+                .map(|(_, m)| m)
                 .collect(),
-            FederationSelection::One(fed) => {
-                opldb
-                    .get_meets()
-                    .iter()
-                    .filter(|m| {
-                        // Filter by year.
-                        if let Some(year) = year {
-                            if m.date.year() != year {
-                                return false;
-                            }
-                        }
-
-                        m.federation == fed
-                    })
-                    .collect()
-            }
             FederationSelection::Meta(meta) => opldb
                 .get_metafed_cache()
                 .get_meet_ids_for(meta)
                 .iter()
-                .map(|&i| opldb.get_meet(i))
-                .filter(|m| match year {
-                    Some(year) => m.date.year() == year,
-                    None => true,
-                })
+                .map(|&i| (i, opldb.get_meet(i)))
+                .filter(filter)
+                .map(|(_, m)| m)
                 .collect(),
+            FederationSelection::Union(union) => {
+                // A meet may be in several metafederations, so collect into a set.
+                let mut meet_ids = Bitset::empty(opldb.get_meets().len());
+                for (i, m) in opldb.get_meets().iter().enumerate() {
+                    if union.contains_federation(m.federation) {
+                        meet_ids.insert(i as u32);
+                    }
+                }
+                for meta in union.metafederations() {
+                    for &i in opldb.get_metafed_cache().get_meet_ids_for(meta) {
+                        meet_ids.insert(i);
+                    }
+                }
+                meet_ids
+                    .iter()
+                    .map(|i| (i, opldb.get_meet(i)))
+                    .filter(filter)
+                    .map(|(_, m)| m)
+                    .collect()
+            }
         };
 
         meets.sort_unstable_by(|a, b|
@@ -195,6 +234,7 @@ impl<'db> Context<'db> {
             strings: locale.strings,
             units: locale.units,
             selection: mselection,
+            years: opldb.get_years(),
             meets: meets
                 .into_iter()
                 .take(PAGE_SIZE)
@@ -1,10 +1,12 @@
 //! The backend logic for each HTML page.
 
 // Common objects.
+pub mod apidata;
 pub mod jsdata;
 pub mod selection;
 
 // Template context providers.
+pub mod calculator;
 pub mod contact;
 pub mod data;
 pub mod disambiguation;
@@ -19,3 +21,6 @@ pub mod status;
 // API providers.
 pub mod api_rankings;
 pub mod api_search;
+pub mod api_v1_lifter;
+pub mod api_v1_meet;
+pub mod api_v1_rankings;
@@ -6,7 +6,179 @@ use serde_json;
 use crate::langpack::{self, Language, Locale};
 use crate::opldb;
 use crate::pages::api_rankings::get_slice;
-use crate::pages::selection::Selection;
+use crate::pages::selection::{
+    bodyweight_range_to_url, country_to_url, FederationSelection, Selection,
+    WeightClassSelection,
+};
+
+/// An option in a selector widget whose options depend on the data.
+#[derive(Serialize)]
+pub struct WidgetOption {
+    /// The URL path segment selected by the option.
+    pub value: String,
+    pub label: String,
+}
+
+/// Lists the lifter countries that occur in the data, by translated name.
+fn lifter_country_options(
+    opldb: &opldb::OplDb,
+    strings: &langpack::Translations,
+) -> Vec<WidgetOption> {
+    let column = &opldb.get_static_cache().linear_time.lifter_country;
+    let mut options: Vec<WidgetOption> = column
+        .values()
+        .flatten()
+        .map(|country| WidgetOption {
+            value: format!("from-{}", country_to_url(country)),
+            label: strings.translate_country(country).to_string(),
+        })
+        .collect();
+    options.sort_by(|a, b| a.label.cmp(&b.label));
+    options
+}
+
+/// Lists the meet countries that occur in the data, by translated name,
+/// each followed by the states within it.
+fn meet_location_options(
+    opldb: &opldb::OplDb,
+    strings: &langpack::Translations,
+) -> Vec<WidgetOption> {
+    let column = &opldb.get_static_cache().linear_time.meet_country;
+    let mut countries: Vec<(opltypes::Country, &str)> = column
+        .values()
+        .map(|country| (country, strings.translate_country(country)))
+        .collect();
+    countries.sort_by(|a, b| a.1.cmp(b.1));
+
+    let mut options = Vec::with_capacity(countries.len());
+    for (country, name) in countries {
+        let url = country_to_url(country);
+        options.push(WidgetOption {
+            value: format!("in-{}", url),
+            label: name.to_string(),
+        });
+
+        // Each country is followed by its states.
+        for (_, state) in opldb.get_meet_states().iter().filter(|s| s.0 == country) {
+            options.push(WidgetOption {
+                value: format!("in-{}-{}", url, state.to_lowercase()),
+                label: format!("{} - {}", name, state),
+            });
+        }
+    }
+    options
+}
+
+/// A group of options in the weightclass selector, from a federation's CONFIG.toml.
+#[derive(Serialize)]
+pub struct WeightClassGroup {
+    pub label: String,
+    /// The sex the classes are for, which the selector also selects.
+    pub sex: &'static str,
+    pub options: Vec<WidgetOption>,
+}
+
+/// Lists the federations whose weightclasses are relevant to the selection.
+fn selected_federations(
+    opldb: &opldb::OplDb,
+    selection: &FederationSelection,
+) -> Vec<opltypes::Federation> {
+    let metas: Vec<opldb::MetaFederation> = match selection {
+        // Every federation is too many to be useful.
+        FederationSelection::AllFederations => return vec![],
+        FederationSelection::One(fed) => return vec![*fed],
+        FederationSelection::Meta(meta) => vec![*meta],
+        FederationSelection::Union(union) => union.metafederations().collect(),
+    };
+
+    let mut feds: Vec<opltypes::Federation> = match selection {
+        FederationSelection::Union(union) => union.federations().collect(),
+        _ => vec![],
+    };
+    for meta in metas {
+        for &meet_id in opldb.get_metafed_cache().get_meet_ids_for(meta) {
+            let fed = opldb.get_meet(meet_id).federation;
+            if !feds.contains(&fed) {
+                feds.push(fed);
+            }
+        }
+    }
+    feds
+}
+
+/// Lists the weightclass schemes used by the selected federations,
+/// omitting schemes with the same classes as one already listed.
+fn weightclass_groups(
+    opldb: &opldb::OplDb,
+    selection: &Selection,
+    units: opltypes::WeightUnits,
+) -> Vec<WeightClassGroup> {
+    let feds = selected_federations(opldb, &selection.federation);
+
+    let mut seen: Vec<(opltypes::Sex, &[opltypes::WeightClassKg])> = vec![];
+    let mut groups = vec![];
+    for scheme in opldb.get_weightclass_schemes() {
+        if !feds.contains(&scheme.federation) {
+            continue;
+        }
+
+        let sex = match scheme.sex {
+            opltypes::Sex::M => "men",
+            opltypes::Sex::F => "women",
+        };
+        if seen.contains(&(scheme.sex, scheme.classes.as_slice())) {
+            continue;
+        }
+        seen.push((scheme.sex, scheme.classes.as_slice()));
+
+        let options = scheme
+            .bounds()
+            .into_iter()
+            .map(|(class, lower, upper)| WidgetOption {
+                value: bodyweight_range_to_url(lower, upper),
+                label: match class {
+                    opltypes::WeightClassKg::Over(_) => class.as_type(units).to_string(),
+                    _ => format!("-{}", class.as_type(units)),
+                },
+            })
+            .collect();
+
+        groups.push(WeightClassGroup {
+            label: format!("{} {}", scheme.federation, scheme.name.replace('_', " ")),
+            sex,
+            options,
+        });
+    }
+    groups
+}
+
+/// Returns an option for a selected bodyweight range that isn't otherwise
+/// in the weightclass selector, so that the selection is shown.
+fn custom_weightclass_option(
+    selection: &Selection,
+    groups: &[WeightClassGroup],
+    units: opltypes::WeightUnits,
+) -> Option<WidgetOption> {
+    let (lower, upper) = match selection.weightclasses {
+        WeightClassSelection::Range(lower, upper) => (lower, upper),
+        _ => return None,
+    };
+
+    let value = bodyweight_range_to_url(lower, upper);
+    if groups
+        .iter()
+        .any(|g| g.options.iter().any(|o| o.value == value))
+    {
+        return None;
+    }
+
+    let label = if upper == opltypes::WeightKg::max_value() {
+        format!("{}+", lower.as_type(units))
+    } else {
+        format!("{}-{}", lower.as_type(units), upper.as_type(units))
+    };
+    Some(WidgetOption { value, label })
+}
 
 /// The context object passed to `templates/rankings.html.tera`.
 #[derive(Serialize)]
@@ -27,6 +199,11 @@ pub struct Context<'db, 'a> {
     pub strings: &'db langpack::Translations,
     pub units: opltypes::WeightUnits,
     pub selection: &'a Selection,
+    pub years: &'db [u32],
+    pub lifter_countries: Vec<WidgetOption>,
+    pub meet_locations: Vec<WidgetOption>,
+    pub weightclass_groups: Vec<WeightClassGroup>,
+    pub weightclass_custom: Option<WidgetOption>,
     pub data: String,
 }
 
@@ -39,6 +216,10 @@ impl<'db, 'a> Context<'db, 'a> {
         // Inline the top 100 to avoid another round-trip.
         let slice = get_slice(&opldb, &locale, &selection, 0, 99);
 
+        let weightclass_groups = weightclass_groups(opldb, selection, locale.units);
+        let weightclass_custom =
+            custom_weightclass_option(selection, &weightclass_groups, locale.units);
+
         Some(Context {
             urlprefix: "/",
             page_title: "Rankings".to_string(),
@@ -46,6 +227,11 @@ impl<'db, 'a> Context<'db, 'a> {
             strings: locale.strings,
             units: locale.units,
             selection,
+            years: opldb.get_years(),
+            lifter_countries: lifter_country_options(opldb, locale.strings),
+            meet_locations: meet_location_options(opldb, locale.strings),
+            weightclass_groups,
+            weightclass_custom,
             data: serde_json::to_string(&slice).ok()?,
         })
     }
@@ -20,6 +20,7 @@ pub struct RecordsSelection {
     pub classkind: ClassKindSelection,
     pub ageclass: AgeClassSelection,
     pub year: YearSelection,
+    pub tested: TestedSelection,
 }
 
 impl Default for RecordsSelection {
@@ -31,6 +32,7 @@ impl Default for RecordsSelection {
             classkind: ClassKindSelection::Traditional,
             ageclass: AgeClassSelection::AllAges,
             year: YearSelection::AllYears,
+            tested: TestedSelection::AllEntries,
         }
     }
 }
@@ -44,6 +46,7 @@ impl RecordsSelection {
             sex: self.sex,
             ageclass: self.ageclass,
             year: self.year,
+            tested: self.tested,
             ..*default
         }
     }
@@ -69,6 +72,7 @@ impl RecordsSelection {
         let mut parsed_classkind: bool = false;
         let mut parsed_ageclass: bool = false;
         let mut parsed_year: bool = false;
+        let mut parsed_tested: bool = false;
 
         // Iterate over each path component, attempting to determine
         // what kind of data it is.
@@ -118,6 +122,13 @@ impl RecordsSelection {
                 }
                 ret.year = y;
                 parsed_year = true;
+            // Check whether this is drug-testing information.
+            } else if let Ok(t) = segment.parse::<TestedSelection>() {
+                if parsed_tested {
+                    return Err(());
+                }
+                ret.tested = t;
+                parsed_tested = true;
             // Unknown string, therefore malformed URL.
             } else {
                 return Err(());
@@ -161,6 +172,7 @@ pub struct Context<'db> {
     pub strings: &'db langpack::Translations,
     pub units: WeightUnits,
     pub selection: RecordsSelection,
+    pub years: &'db [u32],
     pub tables: Vec<Table<'db>>,
 }
 
@@ -697,6 +709,7 @@ impl<'db> Context<'db> {
             strings: locale.strings,
             units: locale.units,
             selection: *selection,
+            years: opldb.get_years(),
             tables,
         }
     }
@@ -1,11 +1,15 @@
 //! Logic for efficiently selecting a subset of the database.
 
+use chrono::{self, Datelike};
+use coefficients;
 use opltypes::*;
 use serde::{self, Serialize};
 
 use std::ffi::OsStr;
+use std::fmt;
 use std::path;
 use std::str::FromStr;
+use strum::IntoEnumIterator;
 
 use crate::opldb::MetaFederation;
 
@@ -18,8 +22,14 @@ pub struct Selection {
     pub sex: SexSelection,
     pub ageclass: AgeClassSelection,
     pub year: YearSelection,
+    pub daterange: DateRangeSelection,
+    pub country: LifterCountrySelection,
+    pub meetcountry: MeetCountrySelection,
+    pub meetstate: MeetStateSelection,
+    pub tested: TestedSelection,
     pub event: EventSelection,
     pub sort: SortSelection,
+    pub performances: PerformancesSelection,
 }
 
 impl Default for Selection {
@@ -31,8 +41,14 @@ impl Default for Selection {
             sex: SexSelection::AllSexes,
             ageclass: AgeClassSelection::AllAges,
             year: YearSelection::AllYears,
+            daterange: DateRangeSelection::AllDates,
+            country: LifterCountrySelection::AllCountries,
+            meetcountry: MeetCountrySelection::AllCountries,
+            meetstate: MeetStateSelection::AllStates,
+            tested: TestedSelection::AllEntries,
             event: EventSelection::AllEvents,
-            sort: SortSelection::ByWilks,
+            sort: SortSelection::ByPoints(PointsSystem::Wilks),
+            performances: PerformancesSelection::BestPerLifter,
         }
     }
 }
@@ -58,8 +74,14 @@ impl Selection {
         let mut parsed_sex: bool = false;
         let mut parsed_ageclass: bool = false;
         let mut parsed_year: bool = false;
+        let mut parsed_daterange: bool = false;
+        let mut parsed_country: bool = false;
+        let mut parsed_meetcountry: bool = false;
+        let mut parsed_meetstate: bool = false;
+        let mut parsed_tested: bool = false;
         let mut parsed_sort: bool = false;
         let mut parsed_event: bool = false;
+        let mut parsed_performances: bool = false;
 
         // Iterate over each path component, attempting to determine
         // what kind of data it is.
@@ -109,6 +131,41 @@ impl Selection {
                 }
                 ret.year = y;
                 parsed_year = true;
+            // Check whether this is date range information.
+            } else if let Ok(d) = segment.parse::<DateRangeSelection>() {
+                if parsed_daterange {
+                    return Err(());
+                }
+                ret.daterange = d;
+                parsed_daterange = true;
+            // Check whether this is lifter country information.
+            } else if let Ok(c) = segment.parse::<LifterCountrySelection>() {
+                if parsed_country {
+                    return Err(());
+                }
+                ret.country = c;
+                parsed_country = true;
+            // Check whether this is meet country information.
+            } else if let Ok(c) = segment.parse::<MeetCountrySelection>() {
+                if parsed_meetcountry {
+                    return Err(());
+                }
+                ret.meetcountry = c;
+                parsed_meetcountry = true;
+            // Check whether this is meet state information.
+            } else if let Ok(s) = segment.parse::<MeetStateSelection>() {
+                if parsed_meetstate {
+                    return Err(());
+                }
+                ret.meetstate = s;
+                parsed_meetstate = true;
+            // Check whether this is drug-testing information.
+            } else if let Ok(t) = segment.parse::<TestedSelection>() {
+                if parsed_tested {
+                    return Err(());
+                }
+                ret.tested = t;
+                parsed_tested = true;
             // Check whether this is sort information.
             } else if let Ok(s) = segment.parse::<SortSelection>() {
                 if parsed_sort {
@@ -123,6 +180,13 @@ impl Selection {
                 }
                 ret.event = e;
                 parsed_event = true;
+            // Check whether this is information about repeated lifters.
+            } else if let Ok(p) = segment.parse::<PerformancesSelection>() {
+                if parsed_performances {
+                    return Err(());
+                }
+                ret.performances = p;
+                parsed_performances = true;
             // Unknown string, therefore malformed URL.
             } else {
                 return Err(());
@@ -142,6 +206,30 @@ pub enum EquipmentSelection {
     RawAndWraps,
     Single,
     Multi,
+    Straps,
+    /// Single-ply and Multi-ply.
+    Equipped,
+    AllEquipment,
+}
+
+impl EquipmentSelection {
+    /// Whether entries with the given Equipment are part of this selection.
+    pub fn contains(self, equipment: Equipment) -> bool {
+        match self {
+            EquipmentSelection::Raw => equipment == Equipment::Raw,
+            EquipmentSelection::Wraps => equipment == Equipment::Wraps,
+            EquipmentSelection::RawAndWraps => {
+                equipment == Equipment::Raw || equipment == Equipment::Wraps
+            }
+            EquipmentSelection::Single => equipment == Equipment::Single,
+            EquipmentSelection::Multi => equipment == Equipment::Multi,
+            EquipmentSelection::Straps => equipment == Equipment::Straps,
+            EquipmentSelection::Equipped => {
+                equipment == Equipment::Single || equipment == Equipment::Multi
+            }
+            EquipmentSelection::AllEquipment => true,
+        }
+    }
 }
 
 impl FromStr for EquipmentSelection {
@@ -154,6 +242,9 @@ impl FromStr for EquipmentSelection {
             // No entry for RawAndWraps, since it's default.
             "single" => Ok(EquipmentSelection::Single),
             "multi" => Ok(EquipmentSelection::Multi),
+            "straps" => Ok(EquipmentSelection::Straps),
+            "equipped" => Ok(EquipmentSelection::Equipped),
+            "all-equipment" => Ok(EquipmentSelection::AllEquipment),
             _ => Err(()),
         }
     }
@@ -164,12 +255,114 @@ pub enum FederationSelection {
     AllFederations,
     One(Federation),
     Meta(MetaFederation),
+    /// Any of several federations or metafederations, like "uspa+usapl+rps".
+    Union(FederationUnion),
+}
+
+/// The maximum number of federations and metafederations in a union.
+pub const MAX_FEDERATION_UNION: usize = 8;
+
+/// A set of federations and metafederations, selected together.
+///
+/// This is stored inline, instead of in a `Vec`, so that selections
+/// can be copied cheaply.
+///
+/// Each union has only one URL, with its parts in alphabetical order,
+/// so that equal unions are also equal selections.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FederationUnion {
+    federations: [Option<Federation>; MAX_FEDERATION_UNION],
+    metafederations: [Option<MetaFederation>; MAX_FEDERATION_UNION],
+}
+
+impl FederationUnion {
+    /// Iterates over the federations in the union.
+    pub fn federations<'a>(&'a self) -> impl Iterator<Item = Federation> + 'a {
+        self.federations.iter().filter_map(|&f| f)
+    }
+
+    /// Iterates over the metafederations in the union.
+    pub fn metafederations<'a>(&'a self) -> impl Iterator<Item = MetaFederation> + 'a {
+        self.metafederations.iter().filter_map(|&m| m)
+    }
+
+    /// Whether the federation is directly part of the union.
+    pub fn contains_federation(&self, fed: Federation) -> bool {
+        self.federations().any(|f| f == fed)
+    }
+}
+
+impl FromStr for FederationUnion {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut union = FederationUnion {
+            federations: [None; MAX_FEDERATION_UNION],
+            metafederations: [None; MAX_FEDERATION_UNION],
+        };
+
+        let mut federations: Vec<Federation> = Vec::new();
+        let mut metafederations: Vec<MetaFederation> = Vec::new();
+        for part in s.split('+') {
+            if federations.len() + metafederations.len() >= MAX_FEDERATION_UNION {
+                return Err(());
+            }
+
+            // Each part is parsed just like a single federation selection.
+            match part.parse::<FederationSelection>()? {
+                FederationSelection::One(fed) => federations.push(fed),
+                FederationSelection::Meta(meta) => metafederations.push(meta),
+                _ => return Err(()),
+            }
+        }
+
+        // A union of a single federation should be written without a '+'.
+        if federations.len() + metafederations.len() < 2 {
+            return Err(());
+        }
+
+        // Store the parts in URL order, without duplicates.
+        federations.sort_by_key(|fed| fed.to_string().to_lowercase());
+        federations.dedup();
+        metafederations.sort_by_key(|meta| meta.to_string());
+        metafederations.dedup();
+        for (i, fed) in federations.into_iter().enumerate() {
+            union.federations[i] = Some(fed);
+        }
+        for (i, meta) in metafederations.into_iter().enumerate() {
+            union.metafederations[i] = Some(meta);
+        }
+
+        // Disallow URLs other than the canonical one, like those with
+        // duplicates or out of order.
+        if union.to_string() != s {
+            return Err(());
+        }
+        Ok(union)
+    }
+}
+
+impl fmt::Display for FederationUnion {
+    /// Formats the union as it would appear in a URL.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut parts: Vec<String> = self
+            .federations()
+            .map(|fed| fed.to_string().to_lowercase())
+            .chain(self.metafederations().map(|meta| meta.to_string()))
+            .collect();
+        parts.sort();
+        write!(f, "{}", parts.join("+"))
+    }
 }
 
 impl FromStr for FederationSelection {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.contains('+') {
+            return s.parse::<FederationUnion>().map(FederationSelection::Union);
+        }
+
         // Try to parse a MetaFederation first.
         // A MetaFederation with the same name as a Federation
         // may override the interpretation of that federation.
@@ -196,12 +389,13 @@ impl Serialize for FederationSelection {
             FederationSelection::AllFederations => serializer.serialize_str("All"),
             FederationSelection::One(fed) => fed.serialize(serializer),
             FederationSelection::Meta(meta) => meta.serialize(serializer),
+            FederationSelection::Union(union) => serializer.collect_str(union),
         }
     }
 }
 
 /// The weight class selector widget.
-#[derive(Copy, Clone, PartialEq, Serialize)]
+#[derive(Copy, Clone, Debug, PartialEq, Serialize)]
 pub enum WeightClassSelection {
     AllClasses,
 
@@ -286,6 +480,84 @@ pub enum WeightClassSelection {
     WpF84,
     WpF100,
     WpFOver100,
+
+    /// A custom range of bodyweight, like "bw-90-100" or "bw-over-140".
+    ///
+    /// The lower bound is exclusive, and the upper bound is inclusive.
+    #[serde(serialize_with = "serialize_bodyweight_range")]
+    Range(WeightKg, WeightKg),
+}
+
+/// Formats a bodyweight as it would appear in a URL, like "90" or "100.02".
+///
+/// The weight is written exactly, without trailing zeros in the decimal.
+fn bodyweight_to_url(w: WeightKg) -> String {
+    let raw = w.as_raw();
+    let (integer, hundredths) = (raw / 100, raw % 100);
+    if hundredths == 0 {
+        format!("{}", integer)
+    } else if hundredths % 10 == 0 {
+        format!("{}.{}", integer, hundredths / 10)
+    } else {
+        format!("{}.{:02}", integer, hundredths)
+    }
+}
+
+/// Formats a bodyweight range as it would appear in a URL.
+pub fn bodyweight_range_to_url(lower: WeightKg, upper: WeightKg) -> String {
+    if upper == WeightKg::max_value() {
+        format!("bw-over-{}", bodyweight_to_url(lower))
+    } else {
+        format!(
+            "bw-{}-{}",
+            bodyweight_to_url(lower),
+            bodyweight_to_url(upper)
+        )
+    }
+}
+
+/// Serializes the bodyweight range as its URL, for comparison in templates.
+fn serialize_bodyweight_range<S>(
+    lower: &WeightKg,
+    upper: &WeightKg,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_str(&bodyweight_range_to_url(*lower, *upper))
+}
+
+/// Parses a bodyweight in a URL, like "90", "67.5", or "100.02".
+///
+/// Only the form written by `bodyweight_to_url()` is accepted,
+/// so that each range has only one URL.
+fn bodyweight_from_url(s: &str) -> Result<WeightKg, ()> {
+    let mut parts = s.splitn(2, '.');
+    let integer = parts.next().unwrap_or("");
+    let decimal = parts.next();
+
+    let is_digits = |p: &str| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit());
+    if !is_digits(integer) || integer.len() > 4 {
+        return Err(());
+    }
+    if integer.len() > 1 && integer.starts_with('0') {
+        return Err(());
+    }
+
+    let mut raw = integer.parse::<i32>().map_err(|_| ())? * 100;
+    if let Some(decimal) = decimal {
+        if !is_digits(decimal) || decimal.len() > 2 || decimal.ends_with('0') {
+            return Err(());
+        }
+        let hundredths = decimal.parse::<i32>().map_err(|_| ())?;
+        raw += if decimal.len() == 1 {
+            hundredths * 10
+        } else {
+            hundredths
+        };
+    }
+    Ok(WeightKg::from_raw(raw))
 }
 
 /// Helper function to save repetition.
@@ -381,6 +653,8 @@ impl WeightClassSelection {
             WeightClassSelection::WpF84 => make_bounds(72.0, 84.0),
             WeightClassSelection::WpF100 => make_bounds(84.0, 100.0),
             WeightClassSelection::WpFOver100 => make_bound_over(100.0),
+
+            WeightClassSelection::Range(lower, upper) => (lower, upper),
         }
     }
 
@@ -599,6 +873,14 @@ impl WeightClassSelection {
             WeightClassSelection::WpFOver100 => {
                 WeightClassKg::Over(WeightKg::from_i32(100))
             }
+
+            WeightClassSelection::Range(lower, upper) => {
+                if upper == WeightKg::max_value() {
+                    WeightClassKg::Over(lower)
+                } else {
+                    WeightClassKg::UnderOrEqual(upper)
+                }
+            }
         }
     }
 }
@@ -683,7 +965,26 @@ impl FromStr for WeightClassSelection {
             "wp100" => Ok(WeightClassSelection::WpF100),
             "wpover100" => Ok(WeightClassSelection::WpFOver100),
 
-            _ => Err(()),
+            _ => {
+                // Try to parse a bodyweight range.
+                if !s.starts_with("bw-") {
+                    return Err(());
+                }
+                let range = &s["bw-".len()..];
+
+                if range.starts_with("over-") {
+                    let lower = bodyweight_from_url(&range["over-".len()..])?;
+                    return Ok(WeightClassSelection::Range(lower, WeightKg::max_value()));
+                }
+
+                let mut bounds = range.splitn(2, '-');
+                let lower = bodyweight_from_url(bounds.next().ok_or(())?)?;
+                let upper = bodyweight_from_url(bounds.next().ok_or(())?)?;
+                if lower >= upper {
+                    return Err(());
+                }
+                Ok(WeightClassSelection::Range(lower, upper))
+            }
         }
     }
 }
@@ -709,6 +1010,42 @@ impl FromStr for SexSelection {
     }
 }
 
+/// The drug-testing selector widget.
+///
+/// This is independent of the federation, so it can narrow down
+/// federations that offer both tested and untested divisions.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize)]
+pub enum TestedSelection {
+    AllEntries,
+    Tested,
+    Untested,
+}
+
+impl TestedSelection {
+    /// Whether an entry with the given `Entry.tested` value is selected.
+    #[inline]
+    pub fn contains(self, tested: bool) -> bool {
+        match self {
+            TestedSelection::AllEntries => true,
+            TestedSelection::Tested => tested,
+            TestedSelection::Untested => !tested,
+        }
+    }
+}
+
+impl FromStr for TestedSelection {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            // No entry for AllEntries, since it's default.
+            "tested" => Ok(TestedSelection::Tested),
+            "untested" => Ok(TestedSelection::Untested),
+            _ => Err(()),
+        }
+    }
+}
+
 /// The AgeClass selector widget.
 #[derive(Copy, Clone, Debug, PartialEq, Serialize)]
 pub enum AgeClassSelection {
@@ -738,6 +1075,73 @@ pub enum AgeClassSelection {
     Masters7579,
 
     MastersOver80,
+
+    // By year of birth, as used by the IPF.
+    SubJuniors,
+    Juniors,
+    Masters1,
+    Masters2,
+    Masters3,
+    Masters4,
+}
+
+impl AgeClassSelection {
+    /// Whether entries with the given AgeClass are part of this selection.
+    ///
+    /// Selections by year of birth don't use the AgeClass,
+    /// so they never contain one: see `birthyearclass()`.
+    pub fn contains(self, class: AgeClass) -> bool {
+        match self {
+            AgeClassSelection::AllAges => true,
+            AgeClassSelection::Youth512 => class == AgeClass::Class5_12,
+            AgeClassSelection::Teenage1315 => class == AgeClass::Class13_15,
+            AgeClassSelection::Teenage1617 => class == AgeClass::Class16_17,
+            AgeClassSelection::Teenage1819 => class == AgeClass::Class18_19,
+            AgeClassSelection::Juniors2023 => class == AgeClass::Class20_23,
+            AgeClassSelection::Seniors2434 => class == AgeClass::Class24_34,
+            AgeClassSelection::Submasters3539 => class == AgeClass::Class35_39,
+            AgeClassSelection::Masters4049 => {
+                class == AgeClass::Class40_44 || class == AgeClass::Class45_49
+            }
+            AgeClassSelection::Masters5059 => {
+                class == AgeClass::Class50_54 || class == AgeClass::Class55_59
+            }
+            AgeClassSelection::Masters6069 => {
+                class == AgeClass::Class60_64 || class == AgeClass::Class65_69
+            }
+            AgeClassSelection::Masters7079 => {
+                class == AgeClass::Class70_74 || class == AgeClass::Class75_79
+            }
+            AgeClassSelection::Masters4044 => class == AgeClass::Class40_44,
+            AgeClassSelection::Masters4549 => class == AgeClass::Class45_49,
+            AgeClassSelection::Masters5054 => class == AgeClass::Class50_54,
+            AgeClassSelection::Masters5559 => class == AgeClass::Class55_59,
+            AgeClassSelection::Masters6064 => class == AgeClass::Class60_64,
+            AgeClassSelection::Masters6569 => class == AgeClass::Class65_69,
+            AgeClassSelection::Masters7074 => class == AgeClass::Class70_74,
+            AgeClassSelection::Masters7579 => class == AgeClass::Class75_79,
+            AgeClassSelection::MastersOver80 => class == AgeClass::Class80_999,
+            AgeClassSelection::SubJuniors
+            | AgeClassSelection::Juniors
+            | AgeClassSelection::Masters1
+            | AgeClassSelection::Masters2
+            | AgeClassSelection::Masters3
+            | AgeClassSelection::Masters4 => false,
+        }
+    }
+
+    /// The BirthYearClass of a selection by year of birth, if it is one.
+    pub fn birthyearclass(self) -> Option<BirthYearClass> {
+        match self {
+            AgeClassSelection::SubJuniors => Some(BirthYearClass::ClassY14Y18),
+            AgeClassSelection::Juniors => Some(BirthYearClass::ClassY19Y23),
+            AgeClassSelection::Masters1 => Some(BirthYearClass::ClassY40Y49),
+            AgeClassSelection::Masters2 => Some(BirthYearClass::ClassY50Y59),
+            AgeClassSelection::Masters3 => Some(BirthYearClass::ClassY60Y69),
+            AgeClassSelection::Masters4 => Some(BirthYearClass::ClassY70Y999),
+            _ => None,
+        }
+    }
 }
 
 impl FromStr for AgeClassSelection {
@@ -766,136 +1170,194 @@ impl FromStr for AgeClassSelection {
             "65-69" => Ok(AgeClassSelection::Masters6569),
             "70-74" => Ok(AgeClassSelection::Masters7074),
             "75-79" => Ok(AgeClassSelection::Masters7579),
+            "sub-juniors" => Ok(AgeClassSelection::SubJuniors),
+            "juniors" => Ok(AgeClassSelection::Juniors),
+            "masters1" => Ok(AgeClassSelection::Masters1),
+            "masters2" => Ok(AgeClassSelection::Masters2),
+            "masters3" => Ok(AgeClassSelection::Masters3),
+            "masters4" => Ok(AgeClassSelection::Masters4),
             _ => Err(()),
         }
     }
 }
 
+/// Returns the name of a Country as used in URLs, like "newzealand".
+pub fn country_to_url(country: Country) -> String {
+    format!("{:?}", country).to_lowercase()
+}
+
+/// Parses the name of a Country as used in URLs.
+fn country_from_url(s: &str) -> Option<Country> {
+    Country::iter().find(|&c| country_to_url(c) == s)
+}
+
+/// Parses a State code as used in URLs, like "ny", for the given Country.
+fn state_from_url(s: &str, country: Country) -> Option<State> {
+    // Codes are uppercase, except for a few full names like "Guam".
+    let mut capitalized: String =
+        s.chars().take(1).flat_map(char::to_uppercase).collect();
+    capitalized.push_str(&s.chars().skip(1).collect::<String>());
+
+    State::from_str_and_country(&s.to_uppercase(), country)
+        .or_else(|_| State::from_str_and_country(&capitalized, country))
+        .ok()
+}
+
+/// The lifter country selector widget, for the nationality of the lifter.
+///
+/// Serializes to the URL path segment that selects it.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum LifterCountrySelection {
+    AllCountries,
+    One(Country),
+}
+
+impl FromStr for LifterCountrySelection {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // No entry for AllCountries, since it's default.
+        if !s.starts_with("from-") {
+            return Err(());
+        }
+        country_from_url(&s[5..])
+            .map(LifterCountrySelection::One)
+            .ok_or(())
+    }
+}
+
+impl Serialize for LifterCountrySelection {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            LifterCountrySelection::AllCountries => {
+                serializer.serialize_str("AllCountries")
+            }
+            LifterCountrySelection::One(country) => {
+                serializer.collect_str(&format_args!("from-{}", country_to_url(*country)))
+            }
+        }
+    }
+}
+
+/// The meet country selector widget, for where the meet took place.
+///
+/// Serializes to the URL path segment that selects it.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum MeetCountrySelection {
+    AllCountries,
+    One(Country),
+}
+
+impl FromStr for MeetCountrySelection {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // No entry for AllCountries, since it's default.
+        if !s.starts_with("in-") {
+            return Err(());
+        }
+        country_from_url(&s[3..])
+            .map(MeetCountrySelection::One)
+            .ok_or(())
+    }
+}
+
+impl Serialize for MeetCountrySelection {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            MeetCountrySelection::AllCountries => {
+                serializer.serialize_str("AllCountries")
+            }
+            MeetCountrySelection::One(country) => {
+                serializer.collect_str(&format_args!("in-{}", country_to_url(*country)))
+            }
+        }
+    }
+}
+
+/// The meet state selector widget, for the state or region within
+/// a country where the meet took place.
+///
+/// Serializes to the URL path segment that selects it, like "in-usa-ny".
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum MeetStateSelection {
+    AllStates,
+    One(State),
+}
+
+impl FromStr for MeetStateSelection {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // No entry for AllStates, since it's default.
+        if !s.starts_with("in-") {
+            return Err(());
+        }
+
+        // The state code follows the country, after the last dash.
+        let mut parts = s[3..].rsplitn(2, '-');
+        let code = parts.next().ok_or(())?;
+        let country = country_from_url(parts.next().ok_or(())?).ok_or(())?;
+        state_from_url(code, country)
+            .map(MeetStateSelection::One)
+            .ok_or(())
+    }
+}
+
+impl Serialize for MeetStateSelection {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            MeetStateSelection::AllStates => serializer.serialize_str("AllStates"),
+            MeetStateSelection::One(state) => serializer.collect_str(&format_args!(
+                "in-{}-{}",
+                country_to_url(state.to_country()),
+                state.to_string().to_lowercase()
+            )),
+        }
+    }
+}
+
 /// The year selector widget.
-#[derive(Copy, Clone, Debug, PartialEq, Serialize)]
+///
+/// Any year may be selected. The years offered by the widget
+/// are the years in which meets occurred, from `OplDb::get_years()`.
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum YearSelection {
     AllYears,
-    Year2019,
-    Year2018,
-    Year2017,
-    Year2016,
-    Year2015,
-    Year2014,
-    Year2013,
-    Year2012,
-    Year2011,
-    Year2010,
-    Year2009,
-    Year2008,
-    Year2007,
-    Year2006,
-    Year2005,
-    Year2004,
-    Year2003,
-    Year2002,
-    Year2001,
-    Year2000,
-    Year1999,
-    Year1998,
-    Year1997,
-    Year1996,
-    Year1995,
-    Year1994,
-    Year1993,
-    Year1992,
-    Year1991,
-    Year1990,
-    Year1989,
-    Year1988,
-    Year1987,
-    Year1986,
-    Year1985,
-    Year1984,
-    Year1983,
-    Year1982,
-    Year1981,
-    Year1980,
-    Year1979,
-    Year1978,
-    Year1977,
-    Year1976,
-    Year1975,
-    Year1974,
-    Year1973,
-    Year1972,
-    Year1971,
-    Year1970,
-    Year1969,
-    Year1968,
-    Year1967,
-    Year1966,
-    Year1965,
-    Year1964,
+    One(u32),
 }
 
 impl FromStr for YearSelection {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            // No entry for AllYears, since it's default.
-            "2019" => Ok(YearSelection::Year2019),
-            "2018" => Ok(YearSelection::Year2018),
-            "2017" => Ok(YearSelection::Year2017),
-            "2016" => Ok(YearSelection::Year2016),
-            "2015" => Ok(YearSelection::Year2015),
-            "2014" => Ok(YearSelection::Year2014),
-            "2013" => Ok(YearSelection::Year2013),
-            "2012" => Ok(YearSelection::Year2012),
-            "2011" => Ok(YearSelection::Year2011),
-            "2010" => Ok(YearSelection::Year2010),
-            "2009" => Ok(YearSelection::Year2009),
-            "2008" => Ok(YearSelection::Year2008),
-            "2007" => Ok(YearSelection::Year2007),
-            "2006" => Ok(YearSelection::Year2006),
-            "2005" => Ok(YearSelection::Year2005),
-            "2004" => Ok(YearSelection::Year2004),
-            "2003" => Ok(YearSelection::Year2003),
-            "2002" => Ok(YearSelection::Year2002),
-            "2001" => Ok(YearSelection::Year2001),
-            "2000" => Ok(YearSelection::Year2000),
-            "1999" => Ok(YearSelection::Year1999),
-            "1998" => Ok(YearSelection::Year1998),
-            "1997" => Ok(YearSelection::Year1997),
-            "1996" => Ok(YearSelection::Year1996),
-            "1995" => Ok(YearSelection::Year1995),
-            "1994" => Ok(YearSelection::Year1994),
-            "1993" => Ok(YearSelection::Year1993),
-            "1992" => Ok(YearSelection::Year1992),
-            "1991" => Ok(YearSelection::Year1991),
-            "1990" => Ok(YearSelection::Year1990),
-            "1989" => Ok(YearSelection::Year1989),
-            "1988" => Ok(YearSelection::Year1988),
-            "1987" => Ok(YearSelection::Year1987),
-            "1986" => Ok(YearSelection::Year1986),
-            "1985" => Ok(YearSelection::Year1985),
-            "1984" => Ok(YearSelection::Year1984),
-            "1983" => Ok(YearSelection::Year1983),
-            "1982" => Ok(YearSelection::Year1982),
-            "1981" => Ok(YearSelection::Year1981),
-            "1980" => Ok(YearSelection::Year1980),
-            "1979" => Ok(YearSelection::Year1979),
-            "1978" => Ok(YearSelection::Year1978),
-            "1977" => Ok(YearSelection::Year1977),
-            "1976" => Ok(YearSelection::Year1976),
-            "1975" => Ok(YearSelection::Year1975),
-            "1974" => Ok(YearSelection::Year1974),
-            "1973" => Ok(YearSelection::Year1973),
-            "1972" => Ok(YearSelection::Year1972),
-            "1971" => Ok(YearSelection::Year1971),
-            "1970" => Ok(YearSelection::Year1970),
-            "1969" => Ok(YearSelection::Year1969),
-            "1968" => Ok(YearSelection::Year1968),
-            "1967" => Ok(YearSelection::Year1967),
-            "1966" => Ok(YearSelection::Year1966),
-            "1965" => Ok(YearSelection::Year1965),
-            "1964" => Ok(YearSelection::Year1964),
-            _ => Err(()),
+        // No entry for AllYears, since it's default.
+        if s.len() != 4 || !s.chars().all(|c| c.is_ascii_digit()) {
+            return Err(());
+        }
+        s.parse::<u32>().map(YearSelection::One).map_err(|_| ())
+    }
+}
+
+impl Serialize for YearSelection {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        // A single year is serialized as a number, for comparison
+        // against the years offered by the widget.
+        match self {
+            YearSelection::AllYears => serializer.serialize_str("AllYears"),
+            YearSelection::One(year) => serializer.serialize_u32(*year),
         }
     }
 }
@@ -905,62 +1367,146 @@ impl YearSelection {
     pub fn as_u32(self) -> Option<u32> {
         match self {
             YearSelection::AllYears => None,
-            YearSelection::Year2019 => Some(2019),
-            YearSelection::Year2018 => Some(2018),
-            YearSelection::Year2017 => Some(2017),
-            YearSelection::Year2016 => Some(2016),
-            YearSelection::Year2015 => Some(2015),
-            YearSelection::Year2014 => Some(2014),
-            YearSelection::Year2013 => Some(2013),
-            YearSelection::Year2012 => Some(2012),
-            YearSelection::Year2011 => Some(2011),
-            YearSelection::Year2010 => Some(2010),
-            YearSelection::Year2009 => Some(2009),
-            YearSelection::Year2008 => Some(2008),
-            YearSelection::Year2007 => Some(2007),
-            YearSelection::Year2006 => Some(2006),
-            YearSelection::Year2005 => Some(2005),
-            YearSelection::Year2004 => Some(2004),
-            YearSelection::Year2003 => Some(2003),
-            YearSelection::Year2002 => Some(2002),
-            YearSelection::Year2001 => Some(2001),
-            YearSelection::Year2000 => Some(2000),
-            YearSelection::Year1999 => Some(1999),
-            YearSelection::Year1998 => Some(1998),
-            YearSelection::Year1997 => Some(1997),
-            YearSelection::Year1996 => Some(1996),
-            YearSelection::Year1995 => Some(1995),
-            YearSelection::Year1994 => Some(1994),
-            YearSelection::Year1993 => Some(1993),
-            YearSelection::Year1992 => Some(1992),
-            YearSelection::Year1991 => Some(1991),
-            YearSelection::Year1990 => Some(1990),
-            YearSelection::Year1989 => Some(1989),
-            YearSelection::Year1988 => Some(1988),
-            YearSelection::Year1987 => Some(1987),
-            YearSelection::Year1986 => Some(1986),
-            YearSelection::Year1985 => Some(1985),
-            YearSelection::Year1984 => Some(1984),
-            YearSelection::Year1983 => Some(1983),
-            YearSelection::Year1982 => Some(1982),
-            YearSelection::Year1981 => Some(1981),
-            YearSelection::Year1980 => Some(1980),
-            YearSelection::Year1979 => Some(1979),
-            YearSelection::Year1978 => Some(1978),
-            YearSelection::Year1977 => Some(1977),
-            YearSelection::Year1976 => Some(1976),
-            YearSelection::Year1975 => Some(1975),
-            YearSelection::Year1974 => Some(1974),
-            YearSelection::Year1973 => Some(1973),
-            YearSelection::Year1972 => Some(1972),
-            YearSelection::Year1971 => Some(1971),
-            YearSelection::Year1970 => Some(1970),
-            YearSelection::Year1969 => Some(1969),
-            YearSelection::Year1968 => Some(1968),
-            YearSelection::Year1967 => Some(1967),
-            YearSelection::Year1966 => Some(1966),
-            YearSelection::Year1965 => Some(1965),
-            YearSelection::Year1964 => Some(1964),
+            YearSelection::One(year) => Some(year),
+        }
+    }
+}
+
+/// Returns the current local date.
+fn today() -> Date {
+    let now = chrono::Local::today();
+    Date::from_u32(now.year() as u32 * 10_000 + now.month() * 100 + now.day())
+}
+
+/// Returns the same day of the month, a number of months earlier.
+///
+/// The result may not be a real date, like February 31st, but it
+/// still compares correctly against real dates.
+fn months_before(date: Date, months: u32) -> Date {
+    let total = (date.year() * 12 + date.month() - 1).saturating_sub(months);
+    Date::from_u32((total / 12) * 10_000 + (total % 12 + 1) * 100 + date.day())
+}
+
+/// Selects meets by date, in addition to the YearSelection.
+///
+/// Serializes to the URL path segment that selects it.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum DateRangeSelection {
+    AllDates,
+    /// Meets on or after the first date and on or before the second date.
+    /// At most one of the bounds may be missing.
+    Between(Option<Date>, Option<Date>),
+    /// Meets in the last number of months, on or after the date that
+    /// many months before the selection was parsed.
+    ///
+    /// The starting date is fixed at parse time, so that equal selections
+    /// always select the same meets.
+    LastMonths {
+        months: u32,
+        since: Date,
+    },
+}
+
+/// The maximum number of months in a rolling window.
+const MAX_MONTHS: u32 = 1200;
+
+impl FromStr for DateRangeSelection {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // No entry for AllDates, since it's default.
+
+        // Rolling windows, like "last-12-months".
+        if s.starts_with("last-") && s.ends_with("-months") && s.len() > 12 {
+            let number = &s[5..s.len() - 7];
+            if !number.chars().all(|c| c.is_ascii_digit()) {
+                return Err(());
+            }
+            let months = number.parse::<u32>().map_err(|_| ())?;
+            if months == 0 || months > MAX_MONTHS {
+                return Err(());
+            }
+            let since = months_before(today(), months);
+            return Ok(DateRangeSelection::LastMonths { months, since });
+        }
+
+        // Fixed ranges, like "since-2015-01-01-until-2017-12-31".
+        let parse_date = |d: &str| d.parse::<Date>().map_err(|_| ());
+        if s.starts_with("since-") {
+            let rest = &s[6..];
+            if rest.len() == 10 {
+                return Ok(DateRangeSelection::Between(Some(parse_date(rest)?), None));
+            }
+            if rest.len() == 27 && rest.get(10..17) == Some("-until-") {
+                let since = parse_date(&rest[..10])?;
+                let until = parse_date(&rest[17..])?;
+                if since > until {
+                    return Err(());
+                }
+                return Ok(DateRangeSelection::Between(Some(since), Some(until)));
+            }
+        } else if s.starts_with("until-") && s.len() == 16 {
+            return Ok(DateRangeSelection::Between(
+                None,
+                Some(parse_date(&s[6..])?),
+            ));
+        }
+
+        Err(())
+    }
+}
+
+impl fmt::Display for DateRangeSelection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DateRangeSelection::AllDates => write!(f, "AllDates"),
+            DateRangeSelection::Between(Some(since), Some(until)) => {
+                write!(f, "since-{}-until-{}", since, until)
+            }
+            DateRangeSelection::Between(Some(since), None) => {
+                write!(f, "since-{}", since)
+            }
+            DateRangeSelection::Between(None, Some(until)) => {
+                write!(f, "until-{}", until)
+            }
+            DateRangeSelection::Between(None, None) => write!(f, "AllDates"),
+            DateRangeSelection::LastMonths { months, .. } => {
+                write!(f, "last-{}-months", months)
+            }
+        }
+    }
+}
+
+impl Serialize for DateRangeSelection {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl DateRangeSelection {
+    /// Returns the first and last dates of the range, inclusive,
+    /// or `None` if all dates are selected.
+    pub fn bounds(self) -> Option<(Date, Date)> {
+        let (first, last) = match self {
+            DateRangeSelection::AllDates => return None,
+            DateRangeSelection::Between(first, last) => (first, last),
+            DateRangeSelection::LastMonths { since, .. } => (Some(since), None),
+        };
+        Some((
+            first.unwrap_or(Date::from_u32(0)),
+            last.unwrap_or(Date::from_u32(u32::max_value())),
+        ))
+    }
+
+    /// Whether the given meet date is in the range.
+    #[inline]
+    pub fn contains(self, date: Date) -> bool {
+        match self.bounds() {
+            Some((first, last)) => first <= date && date <= last,
+            None => true,
         }
     }
 }
@@ -981,6 +1527,20 @@ pub enum EventSelection {
     DeadliftOnly,
 }
 
+impl EventSelection {
+    /// Whether entries with the given Event are part of this selection.
+    pub fn contains(self, event: Event) -> bool {
+        match self {
+            EventSelection::AllEvents => true,
+            EventSelection::FullPower => event.is_full_power(),
+            EventSelection::PushPull => event.is_push_pull(),
+            EventSelection::SquatOnly => event.is_squat_only(),
+            EventSelection::BenchOnly => event.is_bench_only(),
+            EventSelection::DeadliftOnly => event.is_deadlift_only(),
+        }
+    }
+}
+
 impl FromStr for EventSelection {
     type Err = ();
 
@@ -997,16 +1557,32 @@ impl FromStr for EventSelection {
 }
 
 /// The sort selector widget.
-#[derive(Copy, Clone, Debug, PartialEq, Serialize)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum SortSelection {
     BySquat,
     ByBench,
     ByDeadlift,
     ByTotal,
-    ByGlossbrenner,
-    ByIPFPoints,
-    ByMcCulloch,
-    ByWilks,
+    /// Any points system known to the `coefficients` registry.
+    ByPoints(PointsSystem),
+    /// Squat divided by bodyweight.
+    BySquatRatio,
+    /// Bench divided by bodyweight.
+    ByBenchRatio,
+    /// Deadlift divided by bodyweight.
+    ByDeadliftRatio,
+    /// Total divided by bodyweight.
+    ByTotalRatio,
+}
+
+impl SortSelection {
+    /// Returns the points system used for sorting, if any.
+    pub fn points_system(self) -> Option<PointsSystem> {
+        match self {
+            SortSelection::ByPoints(system) => Some(system),
+            _ => None,
+        }
+    }
 }
 
 impl FromStr for SortSelection {
@@ -1018,10 +1594,54 @@ impl FromStr for SortSelection {
             "by-bench" => Ok(SortSelection::ByBench),
             "by-deadlift" => Ok(SortSelection::ByDeadlift),
             "by-total" => Ok(SortSelection::ByTotal),
-            "by-ipf-points" => Ok(SortSelection::ByIPFPoints),
-            "by-glossbrenner" => Ok(SortSelection::ByGlossbrenner),
-            "by-mcculloch" => Ok(SortSelection::ByMcCulloch),
-            "by-wilks" => Ok(SortSelection::ByWilks),
+            "by-squat-ratio" => Ok(SortSelection::BySquatRatio),
+            "by-bench-ratio" => Ok(SortSelection::ByBenchRatio),
+            "by-deadlift-ratio" => Ok(SortSelection::ByDeadliftRatio),
+            "by-total-ratio" => Ok(SortSelection::ByTotalRatio),
+            // Anything else must be a points system, like "by-wilks".
+            _ => {
+                if !s.starts_with("by-") {
+                    return Err(());
+                }
+                coefficients::formula_by_slug(&s["by-".len()..])
+                    .map(|f| SortSelection::ByPoints(f.system()))
+                    .ok_or(())
+            }
+        }
+    }
+}
+
+impl Serialize for SortSelection {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        // Templates compare against the variant name, like "ByWilks",
+        // so points sorts are named after their PointsSystem.
+        match self {
+            SortSelection::ByPoints(system) => {
+                serializer.collect_str(&format_args!("By{:?}", system))
+            }
+            _ => serializer.collect_str(&format_args!("{:?}", self)),
+        }
+    }
+}
+
+/// Whether lifters may appear in the rankings more than once.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize)]
+pub enum PerformancesSelection {
+    /// Default selection: only each lifter's best entry is ranked.
+    BestPerLifter,
+    /// Every entry is ranked, so lifters may appear many times.
+    AllPerformances,
+}
+
+impl FromStr for PerformancesSelection {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "all-performances" => Ok(PerformancesSelection::AllPerformances),
             _ => Err(()),
         }
     }
@@ -1050,6 +1670,259 @@ mod tests {
             FederationSelection::Meta(MetaFederation::USPA)
         );
         assert_eq!(s.equipment, EquipmentSelection::Raw);
+
+        let s = Selection::from_path(Path::new("/uspa/tested"), &d).unwrap();
+        assert_eq!(
+            s.federation,
+            FederationSelection::Meta(MetaFederation::USPA)
+        );
+        assert_eq!(s.tested, TestedSelection::Tested);
+
+        let s = Selection::from_path(Path::new("/raw/by-total-ratio"), &d).unwrap();
+        assert_eq!(s.sort, SortSelection::ByTotalRatio);
+
+        let s = Selection::from_path(Path::new("/by-schwartz-malone"), &d).unwrap();
+        assert_eq!(
+            s.sort,
+            SortSelection::ByPoints(PointsSystem::SchwartzMalone)
+        );
+    }
+
+    #[test]
+    fn test_equipment_selection() {
+        let d = Selection::default();
+
+        let s = Selection::from_path(Path::new("/straps/men"), &d).unwrap();
+        assert_eq!(s.equipment, EquipmentSelection::Straps);
+        assert!(s.equipment.contains(Equipment::Straps));
+        assert!(!s.equipment.contains(Equipment::Raw));
+
+        let s = Selection::from_path(Path::new("/equipped"), &d).unwrap();
+        assert!(s.equipment.contains(Equipment::Single));
+        assert!(s.equipment.contains(Equipment::Multi));
+        assert!(!s.equipment.contains(Equipment::Wraps));
+
+        let s = Selection::from_path(Path::new("/all-equipment/uspa"), &d).unwrap();
+        assert_eq!(s.equipment, EquipmentSelection::AllEquipment);
+        assert!(s.equipment.contains(Equipment::Straps));
+
+        assert!(Selection::from_path(Path::new("/straps/raw"), &d).is_err());
+    }
+
+    #[test]
+    fn test_federation_union_selection() {
+        let d = Selection::default();
+
+        let s = Selection::from_path(Path::new("/rps+usapl+uspa/raw"), &d).unwrap();
+        let union = match s.federation {
+            FederationSelection::Union(union) => union,
+            _ => panic!("expected a union"),
+        };
+        assert_eq!(
+            union.federations().collect::<Vec<Federation>>(),
+            vec![Federation::RPS, Federation::USAPL]
+        );
+        assert_eq!(
+            union.metafederations().collect::<Vec<MetaFederation>>(),
+            vec![MetaFederation::USPA]
+        );
+        assert_eq!(union.to_string(), "rps+usapl+uspa");
+        assert_eq!(s.equipment, EquipmentSelection::Raw);
+
+        // Each part must be valid, and appear only once.
+        assert!("uspa+".parse::<FederationSelection>().is_err());
+        assert!("uspa+all".parse::<FederationSelection>().is_err());
+        assert!("rps+rps".parse::<FederationSelection>().is_err());
+        assert!("rps+notafed".parse::<FederationSelection>().is_err());
+
+        // Each union has a single URL, with its parts in alphabetical order.
+        assert!("usapl+uspa".parse::<FederationSelection>().is_ok());
+        assert!("uspa+usapl".parse::<FederationSelection>().is_err());
+        assert!("RPS+usapl".parse::<FederationSelection>().is_err());
+
+        // Unions are limited in size, to keep selections small.
+        let many = "aapf+apf+gpc+ipf+rps+spf+usapl+wpc+wrpf";
+        assert!(many.parse::<FederationSelection>().is_err());
+    }
+
+    #[test]
+    fn test_bodyweight_range_selection() {
+        let d = Selection::default();
+        let kg = WeightKg::from_f32;
+
+        let s = Selection::from_path(Path::new("/raw/bw-90-100"), &d).unwrap();
+        assert_eq!(
+            s.weightclasses,
+            WeightClassSelection::Range(kg(90.0), kg(100.0))
+        );
+        assert_eq!(s.weightclasses.to_bounds(), (kg(90.0), kg(100.0)));
+
+        let s = Selection::from_path(Path::new("/bw-67.5-75/men"), &d).unwrap();
+        assert_eq!(s.weightclasses.to_bounds(), (kg(67.5), kg(75.0)));
+
+        let s = Selection::from_path(Path::new("/bw-over-140"), &d).unwrap();
+        assert_eq!(
+            s.weightclasses.to_bounds(),
+            (kg(140.0), WeightKg::max_value())
+        );
+
+        // The URL of a range parses back to the same range.
+        for url in &["bw-0-52", "bw-82.5-90", "bw-100.02-107.96", "bw-over-90"] {
+            let range = url.parse::<WeightClassSelection>().unwrap();
+            let (lower, upper) = range.to_bounds();
+            assert_eq!(bodyweight_range_to_url(lower, upper), *url);
+        }
+
+        // Classes with two decimal places keep them exactly.
+        let (lower, upper) = (WeightKg::from_raw(10002), WeightKg::from_raw(10977));
+        let url = bodyweight_range_to_url(lower, upper);
+        assert_eq!(url, "bw-100.02-109.77");
+        let range = url.parse::<WeightClassSelection>().unwrap();
+        assert_eq!(range.to_bounds(), (lower, upper));
+
+        // Ranges must be increasing, and written in canonical form.
+        assert!("bw-100-90".parse::<WeightClassSelection>().is_err());
+        assert!("bw-90-90".parse::<WeightClassSelection>().is_err());
+        assert!("bw-90".parse::<WeightClassSelection>().is_err());
+        assert!("bw-90.0-100".parse::<WeightClassSelection>().is_err());
+        assert!("bw-90-100.50".parse::<WeightClassSelection>().is_err());
+        assert!("bw-90.125-100".parse::<WeightClassSelection>().is_err());
+        assert!("bw-090-100".parse::<WeightClassSelection>().is_err());
+        assert!("bw-90.-100".parse::<WeightClassSelection>().is_err());
+        assert!("bw-inf-100".parse::<WeightClassSelection>().is_err());
+        assert!("bw-over-".parse::<WeightClassSelection>().is_err());
+    }
+
+    #[test]
+    fn test_year_selection() {
+        let d = Selection::default();
+
+        let s = Selection::from_path(Path::new("/raw/2024"), &d).unwrap();
+        assert_eq!(s.year, YearSelection::One(2024));
+        assert_eq!(s.year.as_u32(), Some(2024));
+
+        let s = Selection::from_path(Path::new("/1964/men"), &d).unwrap();
+        assert_eq!(s.year, YearSelection::One(1964));
+
+        // Years must be written with four digits.
+        assert!("+202".parse::<YearSelection>().is_err());
+        assert!("02024".parse::<YearSelection>().is_err());
+        assert!("all".parse::<YearSelection>().is_err());
+        assert!(Selection::from_path(Path::new("/2018/2019"), &d).is_err());
+    }
+
+    #[test]
+    fn test_daterange_selection() {
+        let d = Selection::default();
+        let date = |s: &str| s.parse::<Date>().unwrap();
+
+        let s = Selection::from_path(Path::new("/raw/since-2018-06-01"), &d).unwrap();
+        assert_eq!(
+            s.daterange,
+            DateRangeSelection::Between(Some(date("2018-06-01")), None)
+        );
+        assert!(s.daterange.contains(date("2018-06-01")));
+        assert!(!s.daterange.contains(date("2018-05-31")));
+
+        let s = "since-2015-01-01-until-2017-12-31"
+            .parse::<DateRangeSelection>()
+            .unwrap();
+        assert_eq!(s.bounds(), Some((date("2015-01-01"), date("2017-12-31"))));
+        assert_eq!(s.to_string(), "since-2015-01-01-until-2017-12-31");
+
+        let s = "until-2000-01-01".parse::<DateRangeSelection>().unwrap();
+        assert!(s.contains(date("1980-01-01")));
+        assert!(!s.contains(date("2000-01-02")));
+
+        let s = "last-12-months".parse::<DateRangeSelection>().unwrap();
+        assert_eq!(s.to_string(), "last-12-months");
+        assert!(s.contains(today()));
+
+        // Ranges must be in order and well-formed.
+        assert!("since-2018-01-01-until-2017-01-01"
+            .parse::<DateRangeSelection>()
+            .is_err());
+        assert!("since-2018-13-01".parse::<DateRangeSelection>().is_err());
+        assert!("last-0-months".parse::<DateRangeSelection>().is_err());
+        assert!("last--months".parse::<DateRangeSelection>().is_err());
+        assert!("last-+1-months".parse::<DateRangeSelection>().is_err());
+        assert!(
+            Selection::from_path(Path::new("/last-12-months/since-2018-01-01"), &d)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_location_selection() {
+        let d = Selection::default();
+
+        let s = Selection::from_path(Path::new("/raw/from-canada"), &d).unwrap();
+        assert_eq!(s.country, LifterCountrySelection::One(Country::Canada));
+        assert_eq!(s.meetcountry, MeetCountrySelection::AllCountries);
+
+        let s = Selection::from_path(Path::new("/in-newzealand"), &d).unwrap();
+        assert_eq!(
+            s.meetcountry,
+            MeetCountrySelection::One(Country::NewZealand)
+        );
+
+        let s = Selection::from_path(Path::new("/in-usa-ny/from-usa"), &d).unwrap();
+        assert_eq!(
+            s.meetstate,
+            MeetStateSelection::One(State::InUSA(USAState::NY))
+        );
+        assert_eq!(s.country, LifterCountrySelection::One(Country::USA));
+
+        // Codes that aren't all-caps are still matched.
+        let s = "in-usa-guam".parse::<MeetStateSelection>().unwrap();
+        assert_eq!(s, MeetStateSelection::One(State::InUSA(USAState::Guam)));
+
+        // Serialization produces the URL segment.
+        let json = serde_json::to_string(&s).unwrap();
+        assert_eq!(json, "\"in-usa-guam\"");
+
+        // States must belong to the country.
+        assert!("in-canada-ny".parse::<MeetStateSelection>().is_err());
+        assert!("in-atlantis".parse::<MeetCountrySelection>().is_err());
+        assert!("from-".parse::<LifterCountrySelection>().is_err());
+        assert!(Selection::from_path(Path::new("/from-usa/from-canada"), &d).is_err());
+    }
+
+    #[test]
+    fn test_months_before() {
+        let date = |s: &str| s.parse::<Date>().unwrap();
+        assert_eq!(months_before(date("2019-03-15"), 1), date("2019-02-15"));
+        assert_eq!(months_before(date("2019-03-15"), 12), date("2018-03-15"));
+        assert_eq!(months_before(date("2019-01-31"), 14), date("2017-11-31"));
+    }
+
+    #[test]
+    fn test_performances_selection() {
+        let d = Selection::default();
+        assert_eq!(d.performances, PerformancesSelection::BestPerLifter);
+
+        let s = Selection::from_path(Path::new("/raw/all-performances"), &d).unwrap();
+        assert_eq!(s.performances, PerformancesSelection::AllPerformances);
+        assert_eq!(s.equipment, EquipmentSelection::Raw);
+
+        let path = Path::new("/all-performances/all-performances");
+        assert!(Selection::from_path(path, &d).is_err());
+    }
+
+    #[test]
+    fn test_sort_selection() {
+        let s = "by-ipf-points".parse::<SortSelection>();
+        assert_eq!(s, Ok(SortSelection::ByPoints(PointsSystem::IPFPoints)));
+        let s = "by-total".parse::<SortSelection>();
+        assert_eq!(s, Ok(SortSelection::ByTotal));
+        assert!("by-nonsense".parse::<SortSelection>().is_err());
+        assert!("wilks".parse::<SortSelection>().is_err());
+
+        // The templates rely on the serialized names.
+        let s = SortSelection::ByPoints(PointsSystem::IPFPoints);
+        assert_eq!(serde_json::to_string(&s).unwrap(), "\"ByIPFPoints\"");
+        let s = SortSelection::ByTotalRatio;
+        assert_eq!(serde_json::to_string(&s).unwrap(), "\"ByTotalRatio\"");
     }
 
     #[test]
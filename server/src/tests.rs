@@ -1,31 +1,35 @@
 //! Tests for the Rocket code in main.rs.
 
+use super::common::AdminConfig;
 use super::dotenv;
 use super::rocket;
 
 use server::langpack::{LangInfo, Language};
-use server::opldb::OplDb;
+use server::opldb::{OplDb, SharedOplDb};
 
-use rocket::http::{Cookie, Header, Status};
+use rocket::http::{ContentType, Cookie, Header, Status};
 use rocket::local::Client;
 
 use std::sync::Once;
 
-static mut OPLDB_GLOBAL: Option<OplDb> = None;
+static mut OPLDB_GLOBAL: Option<SharedOplDb> = None;
 static OPLDB_INIT: Once = Once::new();
 
-fn db() -> &'static OplDb {
+fn db() -> &'static SharedOplDb {
     const LIFTERS_CSV: &str = "../build/lifters.csv";
     const MEETS_CSV: &str = "../build/meets.csv";
     const ENTRIES_CSV: &str = "../build/entries.csv";
+    const WEIGHTCLASSES_CSV: &str = "../build/weightclasses.csv";
 
     unsafe {
         OPLDB_INIT.call_once(|| {
             // This isn't really the place for it, but preload the environment.
             dotenv::from_filename("server.env").unwrap();
 
-            OPLDB_GLOBAL =
-                Some(OplDb::from_csv(LIFTERS_CSV, MEETS_CSV, ENTRIES_CSV).unwrap());
+            let opldb =
+                OplDb::from_csv(LIFTERS_CSV, MEETS_CSV, ENTRIES_CSV, WEIGHTCLASSES_CSV)
+                    .unwrap();
+            OPLDB_GLOBAL = Some(SharedOplDb::new(opldb));
         });
 
         OPLDB_GLOBAL.as_ref().unwrap()
@@ -47,7 +51,8 @@ fn langinfo() -> &'static LangInfo {
 /// Returns a client's view into the Rocket server, suitable for making
 /// requests.
 fn client() -> Client {
-    Client::new(rocket(db(), langinfo())).expect("valid rocket instance")
+    let admin = AdminConfig { token: None };
+    Client::new(rocket(db().clone(), langinfo(), admin)).expect("valid rocket instance")
 }
 
 #[test]
@@ -61,10 +66,101 @@ fn test_pages_load() {
     let client = client();
     assert_eq!(client.get("/").dispatch().status(), Status::Ok);
     assert_eq!(client.get("/rankings/uspa").dispatch().status(), Status::Ok);
+    assert_eq!(client.get("/rankings/by-dots").dispatch().status(), Status::Ok);
+    assert_eq!(
+        client
+            .get("/rankings/2018/since-2018-06-01")
+            .dispatch()
+            .status(),
+        Status::Ok
+    );
+    assert_eq!(
+        client
+            .get("/rankings/from-canada/in-usa-ny")
+            .dispatch()
+            .status(),
+        Status::Ok
+    );
+    assert_eq!(
+        client.get("/rankings/uspa/untested").dispatch().status(),
+        Status::Ok
+    );
+    assert_eq!(
+        client
+            .get("/rankings/rps+usapl+uspa/raw")
+            .dispatch()
+            .status(),
+        Status::Ok
+    );
+    assert_eq!(
+        client.get("/rankings/uspa/bw-90-100").dispatch().status(),
+        Status::Ok
+    );
+    assert_eq!(
+        client.get("/rankings/bw-over-140").dispatch().status(),
+        Status::Ok
+    );
+    assert_eq!(
+        client
+            .get("/rankings/straps/by-deadlift")
+            .dispatch()
+            .status(),
+        Status::Ok
+    );
+    assert_eq!(
+        client
+            .get("/rankings/uspa/all-performances")
+            .dispatch()
+            .status(),
+        Status::Ok
+    );
+    assert_eq!(
+        client.get("/rankings/by-total-ratio").dispatch().status(),
+        Status::Ok
+    );
+    assert_eq!(
+        client.get("/rankings/raw/by-goodlift").dispatch().status(),
+        Status::Ok
+    );
+    assert_eq!(
+        client.get("/rankings/by-wilks2020").dispatch().status(),
+        Status::Ok
+    );
+    assert_eq!(
+        client
+            .get("/rankings/by-schwartz-malone")
+            .dispatch()
+            .status(),
+        Status::Ok
+    );
+    assert_eq!(
+        client.get("/api/v1/rankings/raw/by-ah").dispatch().status(),
+        Status::Ok
+    );
     assert_eq!(client.get("/records").dispatch().status(), Status::Ok);
+    assert_eq!(
+        client.get("/records/all-equipment").dispatch().status(),
+        Status::Ok
+    );
+    assert_eq!(
+        client.get("/records/all-canada/tested").dispatch().status(),
+        Status::Ok
+    );
     assert_eq!(client.get("/records/uspa").dispatch().status(), Status::Ok);
+    assert_eq!(
+        client.get("/records/usapl+uspa").dispatch().status(),
+        Status::Ok
+    );
     assert_eq!(client.get("/u/seanstangl").dispatch().status(), Status::Ok);
     assert_eq!(client.get("/mlist").dispatch().status(), Status::Ok);
+    assert_eq!(
+        client.get("/mlist/uspa/tested").dispatch().status(),
+        Status::Ok
+    );
+    assert_eq!(
+        client.get("/mlist/usapl+uspa").dispatch().status(),
+        Status::Ok
+    );
     assert_eq!(client.get("/m/uspa/0485").dispatch().status(), Status::Ok);
     assert_eq!(
         client.get("/m/gpc-aus/1827").dispatch().status(),
@@ -73,12 +169,131 @@ fn test_pages_load() {
     assert_eq!(client.get("/status").dispatch().status(), Status::Ok);
     assert_eq!(client.get("/data").dispatch().status(), Status::Ok);
     assert_eq!(client.get("/faq").dispatch().status(), Status::Ok);
+    assert_eq!(
+        client.get("/tools/calculator").dispatch().status(),
+        Status::Ok
+    );
     assert_eq!(client.get("/contact").dispatch().status(), Status::Ok);
 
     // Test a disambiguation page.
     assert_eq!(client.get("/u/joshsmith").dispatch().status(), Status::Ok);
 }
 
+/// Tests that the public API loads, with and without paging parameters.
+#[test]
+fn test_api_v1_rankings() {
+    let client = client();
+    let mut res = client.get("/api/v1/rankings").dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    assert_eq!(res.content_type(), Some(ContentType::JSON));
+    assert!(res.body_string().unwrap().contains("\"rows\":"));
+    assert_eq!(
+        client
+            .get("/api/v1/rankings/raw/uspa/by-dots?start=100&limit=10")
+            .dispatch()
+            .status(),
+        Status::Ok
+    );
+    assert_eq!(
+        client
+            .get("/api/v1/rankings/last-12-months")
+            .dispatch()
+            .status(),
+        Status::Ok
+    );
+    assert_eq!(
+        client
+            .get("/api/v1/rankings/since-2015-01-01-until-2017-12-31")
+            .dispatch()
+            .status(),
+        Status::Ok
+    );
+    assert_eq!(
+        client
+            .get("/api/v1/rankings/usapl+uspa/raw")
+            .dispatch()
+            .status(),
+        Status::Ok
+    );
+    assert_eq!(
+        client
+            .get("/api/v1/rankings/all-performances/by-total")
+            .dispatch()
+            .status(),
+        Status::Ok
+    );
+    assert_eq!(
+        client
+            .get("/api/v1/rankings/raw/by-squat-ratio")
+            .dispatch()
+            .status(),
+        Status::Ok
+    );
+    assert_eq!(
+        client.get("/api/v1/rankings/uspa+uspa").dispatch().status(),
+        Status::NotFound
+    );
+    assert_eq!(
+        client.get("/api/v1/rankings/not-a-selection").dispatch().status(),
+        Status::NotFound
+    );
+}
+
+/// Tests the lifter exports, including for a disambiguation.
+#[test]
+fn test_lifter_exports() {
+    let client = client();
+    let res = client.get("/api/v1/lifters/seanstangl").dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    assert_eq!(res.content_type(), Some(ContentType::JSON));
+    let res = client.get("/u/seanstangl/csv").dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    assert_eq!(res.content_type(), Some(ContentType::CSV));
+
+    assert_eq!(
+        client.get("/api/v1/lifters/joshsmith").dispatch().status(),
+        Status::Ok
+    );
+    assert_eq!(
+        client.get("/api/v1/lifters/notarealusername").dispatch().status(),
+        Status::NotFound
+    );
+}
+
+/// Tests the points calculator API, which needs no database access.
+#[test]
+fn test_calculator_api() {
+    let client = client();
+    let mut res = client
+        .get("/api/v1/calculator?sex=M&bodyweight=82.5&total=600")
+        .dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    assert_eq!(res.content_type(), Some(ContentType::JSON));
+    assert!(res.body_string().unwrap().contains("\"slug\":\"wilks\""));
+
+    let mut res = client
+        .get("/api/v1/calculator?sex=F&bodyweight=60&total=400&target=500&system=dots")
+        .dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    assert!(res.body_string().unwrap().contains("\"target\":{"));
+
+    // Unparseable inputs and unknown systems are not found.
+    assert_eq!(
+        client
+            .get("/api/v1/calculator?sex=X&bodyweight=82.5&total=600")
+            .dispatch()
+            .status(),
+        Status::NotFound
+    );
+    assert_eq!(
+        client
+            .get("/api/v1/calculator?sex=M&bodyweight=82.5&total=600&target=1&system=foo")
+            .dispatch()
+            .status(),
+        Status::NotFound
+    );
+}
+
 /// Some rankings pages that contain only a few entries have
 /// historically produced crashes, when the context-generating
 /// code assumes a minimum entry count.
@@ -108,6 +323,21 @@ fn test_meet_pages_with_explicit_sorts() {
         client.get("/m/wrpf/bob4/by-ipf-points").dispatch().status(),
         Status::Ok
     );
+    assert_eq!(
+        client.get("/m/wrpf/bob4/by-goodlift").dispatch().status(),
+        Status::Ok
+    );
+    assert_eq!(
+        client.get("/m/wrpf/bob4/by-wilks2020").dispatch().status(),
+        Status::Ok
+    );
+    assert_eq!(
+        client
+            .get("/m/wrpf/bob4/by-schwartz-malone")
+            .dispatch()
+            .status(),
+        Status::Ok
+    );
     assert_eq!(
         client.get("/m/wrpf/bob4/by-division").dispatch().status(),
         Status::Ok
@@ -118,6 +348,34 @@ fn test_meet_pages_with_explicit_sorts() {
     );
 }
 
+/// Tests the meet exports, with and without explicit sorts.
+#[test]
+fn test_meet_exports() {
+    let client = client();
+    let res = client.get("/api/v1/meets/wrpf/bob4").dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    assert_eq!(res.content_type(), Some(ContentType::JSON));
+    assert_eq!(
+        client
+            .get("/api/v1/meets/wrpf/bob4/by-division")
+            .dispatch()
+            .status(),
+        Status::Ok
+    );
+
+    let res = client.get("/m/wrpf/bob4/csv").dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    assert_eq!(res.content_type(), Some(ContentType::CSV));
+    assert_eq!(
+        client.get("/m/wrpf/bob4/by-total/csv").dispatch().status(),
+        Status::Ok
+    );
+    assert_eq!(
+        client.get("/api/v1/meets/wrpf/notameet").dispatch().status(),
+        Status::NotFound
+    );
+}
+
 #[test]
 fn test_username_redirects() {
     let client = client();
@@ -286,3 +544,64 @@ fn test_rankings_nonsense() {
         Status::Ok
     );
 }
+
+/// The reload endpoint doesn't exist unless a token is configured,
+/// and then requires the token.
+#[test]
+fn test_admin_reload_requires_token() {
+    let client = client();
+    assert_eq!(
+        client.post("/admin/reload").dispatch().status(),
+        Status::NotFound
+    );
+
+    // Reloading replaces the database, so use a handle separate from other tests.
+    let opldb = SharedOplDb::from_arc(db().get());
+    let admin = AdminConfig {
+        token: Some("secret".to_string()),
+    };
+    let client =
+        Client::new(rocket(opldb, langinfo(), admin)).expect("valid rocket instance");
+    assert_eq!(
+        client.post("/admin/reload").dispatch().status(),
+        Status::Unauthorized
+    );
+    let wrong = Header::new("Authorization", "Bearer wrong");
+    assert_eq!(
+        client
+            .post("/admin/reload")
+            .header(wrong)
+            .dispatch()
+            .status(),
+        Status::Unauthorized
+    );
+    let right = Header::new("Authorization", "Bearer secret");
+    assert_eq!(
+        client
+            .post("/admin/reload")
+            .header(right)
+            .dispatch()
+            .status(),
+        Status::Accepted
+    );
+}
+
+#[test]
+fn test_search_lifters_api() {
+    let client = client();
+    let mut res = client.get("/api/search/lifters?q=sean%20stangl").dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    assert_eq!(res.content_type(), Some(ContentType::JSON));
+    assert!(res.body_string().unwrap().contains("\"seanstangl\""));
+}
+
+#[test]
+fn test_search_rankings_api_all_performances() {
+    let client = client();
+    let mut res = client
+        .get("/api/search/rankings/all-performances?q=sean%20stangl&start=0")
+        .dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    assert_eq!(res.content_type(), Some(ContentType::JSON));
+    assert!(res.body_string().unwrap().contains("\"next_index\":"));
+}
@@ -11,11 +11,14 @@ pub fn db() -> &'static OplDb {
     const LIFTERS_CSV: &str = "../build/lifters.csv";
     const MEETS_CSV: &str = "../build/meets.csv";
     const ENTRIES_CSV: &str = "../build/entries.csv";
+    const WEIGHTCLASSES_CSV: &str = "../build/weightclasses.csv";
 
     unsafe {
         OPLDB_INIT.call_once(|| {
-            OPLDB_GLOBAL =
-                Some(OplDb::from_csv(LIFTERS_CSV, MEETS_CSV, ENTRIES_CSV).unwrap());
+            OPLDB_GLOBAL = Some(
+                OplDb::from_csv(LIFTERS_CSV, MEETS_CSV, ENTRIES_CSV, WEIGHTCLASSES_CSV)
+                    .unwrap(),
+            );
         });
 
         OPLDB_GLOBAL.as_ref().unwrap()
@@ -3,13 +3,21 @@
 //! Not sure how to break this up across files for the moment,
 //! so just keeping with a super-generic name.
 
+extern crate coefficients;
 extern crate opltypes;
+extern crate serde_json;
 extern crate server;
+extern crate strum;
 
 use opltypes::*;
-use server::opldb::algorithms;
+use server::opldb::{algorithms, MetaFederation, OplDb, RankedEntries, SNAPSHOT_VERSION};
+use server::pages;
+use server::pages::meet::MeetSortSelection;
 use server::pages::selection::*;
 
+use std::sync::Arc;
+use strum::IntoEnumIterator;
+
 mod common;
 
 /// Checks that the sorting algorithm doesn't include any entries with
@@ -23,7 +31,7 @@ fn sorts_only_include_valid_entries() {
     selection.federation = FederationSelection::One(Federation::RPS);
     selection.sort = SortSelection::BySquat;
     let rankings = algorithms::get_full_sorted_uniqued(&selection, &db);
-    for idx in rankings.0.iter() {
+    for idx in rankings.indices().iter() {
         let entry = db.get_entry(*idx);
         assert!(entry.highest_squatkg() > WeightKg::from_i32(0));
         assert!(!entry.place.is_dq());
@@ -33,7 +41,7 @@ fn sorts_only_include_valid_entries() {
     selection.federation = FederationSelection::One(Federation::RPS);
     selection.sort = SortSelection::ByBench;
     let rankings = algorithms::get_full_sorted_uniqued(&selection, &db);
-    for idx in rankings.0.iter() {
+    for idx in rankings.indices().iter() {
         let entry = db.get_entry(*idx);
         assert!(entry.highest_benchkg() > WeightKg::from_i32(0));
         assert!(!entry.place.is_dq());
@@ -43,7 +51,7 @@ fn sorts_only_include_valid_entries() {
     selection.federation = FederationSelection::One(Federation::RPS);
     selection.sort = SortSelection::ByDeadlift;
     let rankings = algorithms::get_full_sorted_uniqued(&selection, &db);
-    for idx in rankings.0.iter() {
+    for idx in rankings.indices().iter() {
         let entry = db.get_entry(*idx);
         assert!(entry.highest_deadliftkg() > WeightKg::from_i32(0));
         assert!(!entry.place.is_dq());
@@ -53,7 +61,7 @@ fn sorts_only_include_valid_entries() {
     selection.federation = FederationSelection::One(Federation::RPS);
     selection.sort = SortSelection::ByTotal;
     let rankings = algorithms::get_full_sorted_uniqued(&selection, &db);
-    for idx in rankings.0.iter() {
+    for idx in rankings.indices().iter() {
         let entry = db.get_entry(*idx);
         assert!(entry.totalkg > WeightKg::from_i32(0));
         assert!(!entry.place.is_dq());
@@ -61,28 +69,91 @@ fn sorts_only_include_valid_entries() {
 
     selection = Selection::default();
     selection.federation = FederationSelection::One(Federation::RPS);
-    selection.sort = SortSelection::ByWilks;
+    selection.sort = SortSelection::ByPoints(PointsSystem::Wilks);
+    let rankings = algorithms::get_full_sorted_uniqued(&selection, &db);
+    for idx in rankings.indices().iter() {
+        let entry = db.get_entry(*idx);
+        assert!(entry.points(PointsSystem::Wilks) > Points::from_i32(0));
+        assert!(!entry.place.is_dq());
+    }
+
+    selection = Selection::default();
+    selection.federation = FederationSelection::One(Federation::RPS);
+    selection.sort = SortSelection::ByPoints(PointsSystem::Dots);
+    let rankings = algorithms::get_full_sorted_uniqued(&selection, &db);
+    for idx in rankings.indices().iter() {
+        let entry = db.get_entry(*idx);
+        assert!(entry.points(PointsSystem::Dots) > Points::from_i32(0));
+        assert!(!entry.place.is_dq());
+    }
+
+    selection = Selection::default();
+    selection.federation = FederationSelection::One(Federation::RPS);
+    selection.sort = SortSelection::ByPoints(PointsSystem::Goodlift);
+    let rankings = algorithms::get_full_sorted_uniqued(&selection, &db);
+    for idx in rankings.indices().iter() {
+        let entry = db.get_entry(*idx);
+        assert!(entry.points(PointsSystem::Goodlift) > Points::from_i32(0));
+        assert!(!entry.place.is_dq());
+    }
+
+    selection = Selection::default();
+    selection.federation = FederationSelection::One(Federation::RPS);
+    selection.sort = SortSelection::ByPoints(PointsSystem::Wilks2020);
+    let rankings = algorithms::get_full_sorted_uniqued(&selection, &db);
+    for idx in rankings.indices().iter() {
+        let entry = db.get_entry(*idx);
+        let points =
+            coefficients::wilks2020(entry.sex, entry.bodyweightkg, entry.totalkg);
+        assert!(points > Points::from_i32(0));
+        assert!(!entry.place.is_dq());
+    }
+
+    selection = Selection::default();
+    selection.federation = FederationSelection::One(Federation::RPS);
+    selection.sort = SortSelection::ByPoints(PointsSystem::SchwartzMalone);
     let rankings = algorithms::get_full_sorted_uniqued(&selection, &db);
-    for idx in rankings.0.iter() {
+    for idx in rankings.indices().iter() {
         let entry = db.get_entry(*idx);
-        assert!(entry.wilks > Points::from_i32(0));
+        let points =
+            coefficients::schwartzmalone(entry.sex, entry.bodyweightkg, entry.totalkg);
+        assert!(points > Points::from_i32(0));
         assert!(!entry.place.is_dq());
     }
 
     // Also test the fully-statically-cached variants.
     selection = Selection::default();
-    selection.sort = SortSelection::ByWilks;
+    selection.sort = SortSelection::ByPoints(PointsSystem::Wilks);
+    let rankings = algorithms::get_full_sorted_uniqued(&selection, &db);
+    for idx in rankings.indices().iter() {
+        let entry = db.get_entry(*idx);
+        assert!(entry.points(PointsSystem::Wilks) > Points::from_i32(0));
+        assert!(!entry.place.is_dq());
+    }
+
+    selection = Selection::default();
+    selection.sort = SortSelection::ByPoints(PointsSystem::AH);
+    let rankings = algorithms::get_full_sorted_uniqued(&selection, &db);
+    for idx in rankings.indices().iter() {
+        let entry = db.get_entry(*idx);
+        let points = coefficients::ah(entry.sex, entry.bodyweightkg, entry.totalkg);
+        assert!(points > Points::from_i32(0));
+        assert!(!entry.place.is_dq());
+    }
+
+    selection = Selection::default();
+    selection.sort = SortSelection::ByPoints(PointsSystem::Dots);
     let rankings = algorithms::get_full_sorted_uniqued(&selection, &db);
-    for idx in rankings.0.iter() {
+    for idx in rankings.indices().iter() {
         let entry = db.get_entry(*idx);
-        assert!(entry.wilks > Points::from_i32(0));
+        assert!(entry.points(PointsSystem::Dots) > Points::from_i32(0));
         assert!(!entry.place.is_dq());
     }
 
     selection = Selection::default();
     selection.sort = SortSelection::BySquat;
     let rankings = algorithms::get_full_sorted_uniqued(&selection, &db);
-    for idx in rankings.0.iter() {
+    for idx in rankings.indices().iter() {
         let entry = db.get_entry(*idx);
         assert!(entry.highest_squatkg() > WeightKg::from_i32(0));
         assert!(!entry.place.is_dq());
@@ -103,3 +174,533 @@ fn num_unique_lifters_is_valid() {
     let meet_id = db.get_meet_id("spf/1744").unwrap();
     assert_eq!(db.get_meet(meet_id).num_unique_lifters, 59);
 }
+
+/// Tests that the public rankings API pages through the full rankings.
+#[test]
+fn api_v1_rankings_paging() {
+    let db = common::db();
+
+    let mut selection = Selection::default();
+    selection.federation = FederationSelection::One(Federation::RPS);
+    let full = algorithms::get_full_sorted_uniqued(&selection, &db);
+
+    let page = pages::api_v1_rankings::get_page(&db, &selection, 10, 5);
+    assert_eq!(page.total_length, full.indices().len());
+    assert_eq!(page.rows.len(), 5);
+    assert_eq!(page.rows[0].rank, 11);
+    assert_eq!(
+        page.rows[0].entry.lifter_id,
+        db.get_entry(full.indices()[10]).lifter_id
+    );
+
+    // The limit is capped.
+    let page = pages::api_v1_rankings::get_page(&db, &selection, 0, usize::max_value());
+    assert_eq!(page.rows.len(), pages::api_v1_rankings::MAX_LIMIT);
+
+    // Out-of-bounds requests are empty.
+    let page =
+        pages::api_v1_rankings::get_page(&db, &selection, full.indices().len(), 10);
+    assert!(page.rows.is_empty());
+    let page = pages::api_v1_rankings::get_page(&db, &selection, usize::max_value(), 10);
+    assert!(page.rows.is_empty());
+    assert_eq!(page.start, page.total_length);
+
+    // The output uses named fields.
+    let page = pages::api_v1_rankings::get_page(&db, &selection, 0, 1);
+    let json = serde_json::to_string(&page).unwrap();
+    assert!(json.contains("\"rank\":1,"));
+    assert!(json.contains("\"totalkg\":"));
+
+    // Rows include the points they are ranked by.
+    selection.sort = SortSelection::ByPoints(PointsSystem::Goodlift);
+    let page = pages::api_v1_rankings::get_page(&db, &selection, 0, 1);
+    assert!(page.rows[0].entry.goodlift.is_some());
+    assert_eq!(page.rows[0].points, page.rows[0].entry.goodlift);
+    assert_eq!(page.rows[0].bodyweight_ratio, None);
+
+    selection.sort = SortSelection::ByTotalRatio;
+    let page = pages::api_v1_rankings::get_page(&db, &selection, 0, 1);
+    assert_eq!(page.rows[0].points, None);
+    assert!(page.rows[0].bodyweight_ratio > Some(Points::from_i32(0)));
+    assert!(page.rows[0].entry.bodyweightkg.is_some());
+}
+
+/// Tests the public lifter API, including disambiguation.
+#[test]
+fn api_v1_lifters() {
+    let db = common::db();
+
+    let lifter_ids = db.get_lifter_ids_for_username("seanstangl");
+    assert_eq!(lifter_ids.len(), 1);
+    let result = pages::api_v1_lifter::get_lifters(&db, &lifter_ids);
+    assert!(!result.disambiguation);
+    let lifter = &result.lifters[0];
+    assert_eq!(lifter.username, "seanstangl");
+    assert_eq!(
+        lifter.entries.len(),
+        db.get_entries_for_lifter(lifter_ids[0]).len()
+    );
+    assert!(!lifter.personal_bests.is_empty());
+
+    // Entries are sorted oldest first.
+    for pair in lifter.entries.windows(2) {
+        assert!(pair[0].date <= pair[1].date);
+    }
+
+    // Ambiguous usernames list every matching lifter.
+    let lifter_ids = db.get_lifter_ids_for_username("joshsmith");
+    assert!(lifter_ids.len() > 1);
+    let result = pages::api_v1_lifter::get_lifters(&db, &lifter_ids);
+    assert!(result.disambiguation);
+    assert_eq!(result.lifters.len(), lifter_ids.len());
+
+    // The CSV export has a header and one row per entry.
+    let csv = pages::api_v1_lifter::export_csv(&db, &lifter_ids).unwrap();
+    let num_entries: usize = lifter_ids
+        .iter()
+        .map(|&id| db.get_entries_for_lifter(id).len())
+        .sum();
+    assert_eq!(csv.lines().count(), num_entries + 1);
+    assert!(csv.starts_with("lifter_id,name,username,"));
+}
+
+/// Tests the public meet API, grouped by division and by points.
+#[test]
+fn api_v1_meets() {
+    let db = common::db();
+    let meet_id = db.get_meet_id("spf/1744").unwrap();
+    let num_entries = db.get_entries_for_meet(meet_id).len();
+
+    // Grouping by division includes every entry, without ranks.
+    let result =
+        pages::api_v1_meet::get_meet(&db, meet_id, MeetSortSelection::ByDivision);
+    assert_eq!(result.meet.meet_path, "spf/1744");
+    assert!(result.groups.len() > 1);
+    let num_rows: usize = result.groups.iter().map(|g| g.rows.len()).sum();
+    assert_eq!(num_rows, num_entries);
+    assert!(result.groups[0].rows[0].rank.is_none());
+
+    // Sorting by points lists each lifter once, ranked.
+    let result = pages::api_v1_meet::get_meet(
+        &db,
+        meet_id,
+        MeetSortSelection::ByPoints(PointsSystem::Wilks),
+    );
+    assert_eq!(result.groups.len(), 1);
+    let rows = &result.groups[0].rows;
+    assert_eq!(rows.len(), db.get_meet(meet_id).num_unique_lifters as usize);
+    assert_eq!(rows[0].rank, Some(1));
+
+    // The CSV export has a header and one row per entry.
+    let csv = pages::api_v1_meet::export_csv(&db, meet_id, MeetSortSelection::ByDivision)
+        .unwrap();
+    assert_eq!(csv.lines().count(), num_entries + 1);
+}
+
+/// Tests that a snapshot round-trips the OplDb, and that damaged
+/// snapshots are rejected.
+#[test]
+fn snapshot_roundtrip() {
+    let db = common::db();
+    let path = std::env::temp_dir().join("opldb-integration-test.snapshot");
+
+    db.write_snapshot(&path).unwrap();
+    let loaded = OplDb::from_snapshot(&path).unwrap();
+
+    assert_eq!(loaded.get_lifters().len(), db.get_lifters().len());
+    assert_eq!(loaded.get_meets().len(), db.get_meets().len());
+    assert_eq!(loaded.get_entries().len(), db.get_entries().len());
+
+    let entry = db.get_entry(1000);
+    let loaded_entry = loaded.get_entry(1000);
+    assert_eq!(loaded_entry.lifter_id, entry.lifter_id);
+    assert_eq!(loaded_entry.totalkg, entry.totalkg);
+    assert_eq!(loaded_entry.place, entry.place);
+    assert_eq!(loaded_entry.tested, entry.tested);
+
+    // Both cached and non-cached rankings should be identical.
+    let mut selection = Selection::default();
+    assert_eq!(
+        algorithms::get_full_sorted_uniqued(&selection, &loaded).indices(),
+        algorithms::get_full_sorted_uniqued(&selection, &db).indices()
+    );
+    selection.federation = FederationSelection::One(Federation::RPS);
+    selection.sort = SortSelection::BySquat;
+    assert_eq!(
+        algorithms::get_full_sorted_uniqued(&selection, &loaded).indices(),
+        algorithms::get_full_sorted_uniqued(&selection, &db).indices()
+    );
+
+    // Flipping a single payload byte fails the checksum.
+    let mut bytes = std::fs::read(&path).unwrap();
+    let last = bytes.len() - 1;
+    bytes[last] ^= 0xFF;
+    std::fs::write(&path, &bytes).unwrap();
+    assert!(OplDb::from_snapshot(&path).is_err());
+
+    // A snapshot from a different format version is rejected.
+    bytes[last] ^= 0xFF;
+    bytes[8..12].copy_from_slice(&(SNAPSHOT_VERSION + 1).to_le_bytes());
+    std::fs::write(&path, &bytes).unwrap();
+    assert!(OplDb::from_snapshot(&path).is_err());
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+/// Tests that cached rankings match freshly-computed rankings,
+/// and that repeated requests reuse the cached result.
+#[test]
+fn cached_rankings() {
+    let db = common::db();
+
+    let mut selection = Selection::default();
+    selection.federation = FederationSelection::One(Federation::USPA);
+    selection.sort = SortSelection::ByTotal;
+
+    let cached = algorithms::get_cached_sorted_uniqued(&selection, &db);
+    let full = algorithms::get_full_sorted_uniqued(&selection, &db);
+    assert_eq!(cached.list.indices(), full.indices());
+
+    let again = algorithms::get_cached_sorted_uniqued(&selection, &db);
+    assert!(Arc::ptr_eq(&cached, &again));
+
+    // The username index agrees with the list.
+    for position in &[0, full.indices().len() / 2, full.indices().len() - 1] {
+        let entry = db.get_entry(full.indices()[*position]);
+        let username = &db.get_lifter(entry.lifter_id).username;
+        let found = cached.first_with_prefix(&db, username, *position);
+        assert_eq!(found, Some(*position));
+    }
+    assert_eq!(cached.first_with_prefix(&db, "notarealusername", 0), None);
+}
+
+/// Tests that selecting entries using the column indexes gives the same
+/// result as checking each entry directly.
+#[test]
+fn entry_indices_match_scan() {
+    let db = common::db();
+    let meets = db.get_meets();
+
+    let mut selections = vec![];
+
+    let mut selection = Selection::default();
+    selection.sex = SexSelection::Women;
+    selection.year = YearSelection::One(2018);
+    selections.push(selection);
+
+    let mut selection = Selection::default();
+    selection.daterange = "since-2016-07-01-until-2017-06-30".parse().unwrap();
+    selections.push(selection);
+
+    let mut selection = Selection::default();
+    selection.country = LifterCountrySelection::One(Country::Canada);
+    selection.meetcountry = MeetCountrySelection::One(Country::USA);
+    selections.push(selection);
+
+    let mut selection = Selection::default();
+    selection.meetstate = MeetStateSelection::One(State::InUSA(USAState::NY));
+    selections.push(selection);
+
+    let mut selection = Selection::default();
+    selection.federation = FederationSelection::Meta(MetaFederation::USPA);
+    selection.tested = TestedSelection::Tested;
+    selections.push(selection);
+
+    let mut selection = Selection::default();
+    selection.country = LifterCountrySelection::One(Country::Canada);
+    selection.tested = TestedSelection::Untested;
+    selections.push(selection);
+
+    let mut selection = Selection::default();
+    selection.federation = FederationSelection::One(Federation::USPA);
+    selection.event = EventSelection::FullPower;
+    selection.ageclass = AgeClassSelection::Masters4049;
+    selections.push(selection);
+
+    let mut selection = Selection::default();
+    selection.ageclass = AgeClassSelection::Juniors;
+    selections.push(selection);
+
+    let mut selection = Selection::default();
+    selection.equipment = EquipmentSelection::Single;
+    selection.federation = FederationSelection::Meta(MetaFederation::FullyTested);
+    selections.push(selection);
+
+    let mut selection = Selection::default();
+    selection.federation = "rps+usapl+uspa".parse::<FederationSelection>().unwrap();
+    selection.equipment = EquipmentSelection::Raw;
+    selections.push(selection);
+
+    let mut selection = Selection::default();
+    selection.equipment = EquipmentSelection::Straps;
+    selections.push(selection);
+
+    let mut selection = Selection::default();
+    selection.equipment = EquipmentSelection::Equipped;
+    selection.sex = SexSelection::Women;
+    selections.push(selection);
+
+    let mut selection = Selection::default();
+    selection.equipment = EquipmentSelection::AllEquipment;
+    selection.year = YearSelection::One(2018);
+    selections.push(selection);
+
+    for selection in selections {
+        let indices = algorithms::get_entry_indices_for(&selection, &db);
+        assert!(indices.maintains_invariants());
+
+        let expected: Vec<u32> = db
+            .get_entries()
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| {
+                let meet = db.get_meet(e.meet_id);
+                selection.equipment.contains(e.equipment)
+                    && match selection.sex {
+                        SexSelection::AllSexes => true,
+                        SexSelection::Men => e.sex == Sex::M,
+                        SexSelection::Women => e.sex == Sex::F,
+                    }
+                    && selection
+                        .year
+                        .as_u32()
+                        .map_or(true, |y| meet.date.year() == y)
+                    && selection.daterange.contains(meet.date)
+                    && match selection.country {
+                        LifterCountrySelection::AllCountries => true,
+                        LifterCountrySelection::One(c) => e.lifter_country == Some(c),
+                    }
+                    && match selection.meetcountry {
+                        MeetCountrySelection::AllCountries => true,
+                        MeetCountrySelection::One(c) => meet.country == c,
+                    }
+                    && match selection.meetstate {
+                        MeetStateSelection::AllStates => true,
+                        MeetStateSelection::One(state) => {
+                            meet.country == state.to_country()
+                                && meet.state == Some(state.to_string())
+                        }
+                    }
+                    && match selection.federation {
+                        FederationSelection::AllFederations => true,
+                        FederationSelection::One(fed) => meet.federation == fed,
+                        FederationSelection::Meta(meta) => meta.contains(e, meets),
+                        FederationSelection::Union(union) => {
+                            union.contains_federation(meet.federation)
+                                || union.metafederations().any(|m| m.contains(e, meets))
+                        }
+                    }
+                    && selection.tested.contains(e.tested)
+                    && match selection.ageclass.birthyearclass() {
+                        Some(class) => e.birthyearclass == class,
+                        None => selection.ageclass.contains(e.ageclass),
+                    }
+                    && selection.event.contains(e.event)
+            })
+            .map(|(i, _)| i as u32)
+            .collect();
+
+        assert!(!expected.is_empty());
+        assert_eq!(indices.0, expected);
+    }
+}
+
+/// Tests that the index of each MetaFederation, which is built from the
+/// meets in the MetaFederationCache, agrees with checking each entry.
+#[test]
+fn metafederation_index_matches_contains() {
+    let db = common::db();
+    let meets = db.get_meets();
+    let index = &db.get_static_cache().linear_time.metafederation;
+
+    for meta in MetaFederation::iter() {
+        let indexed: Vec<u32> = index.select_one(meta).iter().collect();
+        let expected: Vec<u32> = db
+            .get_entries()
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| meta.contains(e, meets))
+            .map(|(i, _)| i as u32)
+            .collect();
+        assert_eq!(indexed, expected, "{}", meta);
+    }
+}
+
+/// Checks that the years offered for selection cover every meet.
+#[test]
+fn years_cover_all_meets() {
+    let db = common::db();
+    let years = db.get_years();
+
+    // The years are a continuous range, latest first.
+    assert!(!years.is_empty());
+    assert!(years.windows(2).all(|w| w[0] == w[1] + 1));
+
+    for meet in db.get_meets() {
+        assert!(years.contains(&meet.date.year()));
+    }
+    assert!(db.get_meets().iter().any(|m| m.date.year() == years[0]));
+    assert!(db
+        .get_meets()
+        .iter()
+        .any(|m| m.date.year() == years[years.len() - 1]));
+}
+
+/// Checks that the weightclasses from CONFIG.toml files are loaded,
+/// and that selecting one of their classes finds lifters in that class.
+#[test]
+fn weightclass_schemes_select_bodyweight_ranges() {
+    let db = common::db();
+    let scheme = db
+        .get_weightclass_schemes()
+        .iter()
+        .find(|s| s.federation == Federation::USPA && s.sex == Sex::M)
+        .expect("USPA has weightclasses for men");
+
+    let bounds = scheme.bounds();
+    assert!(bounds.len() > 2);
+    let (_, lower, upper) = bounds[bounds.len() / 2];
+
+    let mut selection = Selection::default();
+    selection.federation = FederationSelection::One(Federation::USPA);
+    selection.weightclasses = WeightClassSelection::Range(lower, upper);
+    let rankings = algorithms::get_full_sorted_uniqued(&selection, &db);
+
+    assert!(!rankings.indices().is_empty());
+    for &i in rankings.indices().iter() {
+        let entry = db.get_entry(i);
+        assert!(entry.bodyweightkg > lower && entry.bodyweightkg <= upper);
+    }
+}
+
+/// Tests that ranking all performances keeps every entry that the
+/// best-per-lifter rankings would choose between, in sorted order.
+#[test]
+fn all_performances_rankings() {
+    let db = common::db();
+
+    let mut selection = Selection::default();
+    selection.sort = SortSelection::ByTotal;
+    let best = algorithms::get_full_sorted_uniqued(&selection, &db);
+
+    selection.performances = PerformancesSelection::AllPerformances;
+    let all = algorithms::get_full_sorted_uniqued(&selection, &db);
+    assert!(all.indices().len() > best.indices().len());
+
+    // Every best performance is still ranked.
+    let mut sorted = all.indices().to_vec();
+    sorted.sort_unstable();
+    for idx in best.indices().iter() {
+        assert!(sorted.binary_search(idx).is_ok());
+    }
+
+    // The rankings are sorted, and lifters appear more than once.
+    let meets = db.get_meets();
+    let mut lifters = vec![];
+    for pair in all.indices().windows(2) {
+        let a = db.get_entry(pair[0]);
+        let b = db.get_entry(pair[1]);
+        assert_ne!(
+            algorithms::cmp_total(meets, a, b),
+            std::cmp::Ordering::Greater
+        );
+        lifters.push(a.lifter_id);
+    }
+    lifters.sort_unstable();
+    lifters.dedup();
+    assert!(lifters.len() < all.indices().len() - 1);
+
+    // Lookups by username find each of the lifter's positions.
+    let cached = algorithms::get_cached_sorted_uniqued(&selection, &db);
+    match cached.list {
+        RankedEntries::AllPerformances(_) => (),
+        RankedEntries::BestPerLifter(_) => panic!("expected all performances"),
+    }
+    let last = db.get_entry(*all.indices().last().unwrap()).lifter_id;
+    let username = &db.get_lifter(last).username;
+    for (position, _) in all
+        .indices()
+        .iter()
+        .enumerate()
+        .filter(|(_, &i)| db.get_entry(i).lifter_id == last)
+    {
+        let found = cached.first_with_prefix(&db, username, position);
+        assert_eq!(found, Some(position));
+    }
+}
+
+/// Tests that ratio sorts exclude entries without a bodyweight,
+/// and rank by the lift divided by bodyweight.
+#[test]
+fn ratio_sorts() {
+    let db = common::db();
+
+    let mut selection = Selection::default();
+    selection.sort = SortSelection::ByTotalRatio;
+    let rankings = algorithms::get_full_sorted_uniqued(&selection, &db);
+    assert!(!rankings.indices().is_empty());
+
+    let mut prev: Option<Points> = None;
+    for &i in rankings.indices().iter() {
+        let entry = db.get_entry(i);
+        assert!(entry.bodyweightkg > WeightKg::from_i32(0));
+        assert!(entry.totalkg > WeightKg::from_i32(0));
+
+        let ratio = entry.bodyweight_ratio(entry.totalkg);
+        if let Some(prev) = prev {
+            assert!(prev >= ratio);
+        }
+        prev = Some(ratio);
+    }
+
+    selection.federation = FederationSelection::One(Federation::USPA);
+    selection.sort = SortSelection::ByBenchRatio;
+    let rankings = algorithms::get_full_sorted_uniqued(&selection, &db);
+    for &i in rankings.indices().iter() {
+        let entry = db.get_entry(i);
+        assert!(entry.bodyweightkg > WeightKg::from_i32(0));
+        assert!(entry.highest_benchkg() > WeightKg::from_i32(0));
+        assert!(!entry.place.is_dq());
+    }
+}
+
+/// Checks that the calculator agrees with the formulas it dispatches to.
+#[test]
+fn calculator_points() {
+    let query = pages::calculator::Query {
+        sex: "F",
+        bodyweight: "60",
+        total: "400",
+        equipment: Some("Single-ply"),
+        event: None,
+        age: None,
+        target: Some("500"),
+        system: Some("dots"),
+    };
+    let calculation = pages::calculator::calculate(&query).unwrap();
+    assert_eq!(calculation.points.len(), coefficients::FORMULAS.len());
+
+    let bw = WeightKg::from_i32(60);
+    let total = WeightKg::from_i32(400);
+    let dots = calculation
+        .points
+        .iter()
+        .find(|p| p.slug == "dots")
+        .unwrap();
+    assert_eq!(dots.points, coefficients::dots(Sex::F, bw, total));
+
+    // The target is above the current score, so only a total can reach it.
+    let target = calculation.target.unwrap();
+    assert_eq!(target.system, "Dots");
+    let needed = target.totalkg.unwrap();
+    assert!(needed > total);
+    assert!(coefficients::dots(Sex::F, bw, needed) >= Points::from_i32(500));
+    assert!(target.max_bodyweightkg.is_none());
+
+    // A target needs both a score and a system.
+    let query = pages::calculator::Query {
+        system: None,
+        ..query
+    };
+    assert!(pages::calculator::calculate(&query).is_none());
+}
@@ -20,7 +20,7 @@ fn basic_rankings_search() {
 
     // Check that the result is for the specified lifter.
     let list = algorithms::get_full_sorted_uniqued(&selection, &db);
-    let lifter = db.get_lifter(db.get_entry(list.0[row]).lifter_id);
+    let lifter = db.get_lifter(db.get_entry(list.indices()[row]).lifter_id);
     assert_eq!(lifter.name, "Sean Stangl");
 }
 
@@ -36,7 +36,7 @@ fn backwards_name_search() {
 
     // Check that the result is for the specified lifter.
     let list = algorithms::get_full_sorted_uniqued(&selection, &db);
-    let lifter = db.get_lifter(db.get_entry(list.0[row]).lifter_id);
+    let lifter = db.get_lifter(db.get_entry(list.indices()[row]).lifter_id);
     assert_eq!(lifter.name, "Sean Stangl");
 }
 
@@ -52,6 +52,76 @@ fn instagram_search() {
 
     // Check that the result is for the specified lifter.
     let list = algorithms::get_full_sorted_uniqued(&selection, &db);
-    let lifter = db.get_lifter(db.get_entry(list.0[row]).lifter_id);
+    let lifter = db.get_lifter(db.get_entry(list.indices()[row]).lifter_id);
     assert_eq!(lifter.name, "Sean Stangl");
 }
+
+/// Checks that searching all lifters ranks the expected lifter first.
+#[test]
+fn lifter_search() {
+    let db = common::db();
+
+    // Searches should find by full name in either order, by a single word,
+    // by a prefix, by Cyrillic name, and by Instagram handle.
+    for query in &[
+        "Sean Stangl",
+        "stangl sean",
+        "Sean Stan",
+        "Шон Стангл",
+        "@ferruix",
+    ] {
+        let res = search_lifters(&db, query);
+        assert_eq!(res.lifters[0].username, "seanstangl", "query: {}", query);
+    }
+
+    // Searching for one word of a name also finds that lifter.
+    let res = search_lifters(&db, "stangl");
+    assert!(res.lifters.iter().any(|l| l.username == "seanstangl"));
+}
+
+/// Checks that misspelled names are still found.
+#[test]
+fn fuzzy_lifter_search() {
+    let db = common::db();
+    let res = search_lifters(&db, "Sean Stangel");
+    assert_eq!(res.lifters[0].username, "seanstangl");
+
+    let res = search_lifters(&db, "Saen Stangl");
+    assert!(res.lifters.iter().any(|l| l.username == "seanstangl"));
+
+    // Distance is counted in chars, so a Cyrillic typo is a single edit.
+    let res = search_lifters(&db, "Стагл");
+    assert!(res.lifters.iter().any(|l| l.username == "seanstangl"));
+}
+
+/// Checks that empty and nonsense searches return nothing.
+#[test]
+fn empty_lifter_search() {
+    let db = common::db();
+    assert!(search_lifters(&db, "").lifters.is_empty());
+    assert!(search_lifters(&db, "  ").lifters.is_empty());
+    assert!(search_lifters(&db, "zzzzzzzzzzzzzzzzzz").lifters.is_empty());
+}
+
+/// Checks that searching all performances finds each of a lifter's rows.
+#[test]
+fn all_performances_search() {
+    let db = common::db();
+    let mut selection = Selection::default();
+    selection.performances = PerformancesSelection::AllPerformances;
+
+    let first = search_rankings(&db, &selection, 0, "Sean Stangl")
+        .next_index
+        .unwrap();
+    let second = search_rankings(&db, &selection, first + 1, "Sean Stangl")
+        .next_index
+        .unwrap();
+    assert!(second > first);
+
+    // Both results are for the specified lifter.
+    let list = algorithms::get_full_sorted_uniqued(&selection, &db);
+    for row in &[first, second] {
+        let lifter = db.get_lifter(db.get_entry(list.indices()[*row]).lifter_id);
+        assert_eq!(lifter.name, "Sean Stangl");
+    }
+}